@@ -0,0 +1,60 @@
+//! Keeps only hits with a status the caller is interested in.
+//!
+//! cargo run --example custom_filter -- http://localhost:8080 wordlist.txt 200,301
+
+use std::{env, sync::mpsc};
+
+use yadb::prelude::*;
+
+struct AllowedStatus {
+    allowed: Vec<u16>,
+}
+
+impl ResponseFilter for AllowedStatus {
+    fn keep(&self, response: &ResponseInfo) -> bool {
+        self.allowed.contains(&response.status)
+    }
+}
+
+struct Printer;
+
+impl ProgressHandler for Printer {
+    fn hit(&mut self, hit: HitResult) {
+        println!("{hit}");
+    }
+
+    fn log(&mut self, level: LogLevel, msg: String) {
+        if level != LogLevel::INFO {
+            eprintln!("[{level}] {msg}");
+        }
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let mut args = env::args().skip(1);
+    let target = args.next().expect("Target URL required");
+    let wordlist = args.next().expect("Wordlist path required");
+    let allowed = args
+        .next()
+        .unwrap_or_else(|| "200".to_string())
+        .split(',')
+        .filter_map(|code| code.trim().parse().ok())
+        .collect();
+
+    let (tx, rx) = mpsc::channel::<WorkerMessage>();
+
+    let handle = WorkerBuilder::default()
+        .uri(&target)
+        .wordlist(&wordlist)
+        .response_filter(AllowedStatus { allowed })
+        .message_sender(tx.into())
+        .build()?
+        .spawn();
+
+    for msg in rx {
+        Printer.handle(msg);
+    }
+
+    handle.join()?;
+    Ok(())
+}
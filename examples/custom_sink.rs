@@ -0,0 +1,53 @@
+//! Writes hits to a tab-separated file through a custom output sink.
+//!
+//! cargo run --example custom_sink -- http://localhost:8080 wordlist.txt hits.tsv
+
+use std::{
+    env,
+    fs::File,
+    io::{BufWriter, Write},
+    sync::mpsc,
+};
+
+use yadb::prelude::*;
+
+struct TsvSink {
+    writer: BufWriter<File>,
+}
+
+impl OutputSink for TsvSink {
+    fn on_hit(&mut self, hit: &HitResult) -> anyhow::Result<()> {
+        writeln!(self.writer, "{}\t{}", hit.url, hit.status)?;
+        Ok(())
+    }
+
+    fn on_finish(&mut self) -> anyhow::Result<()> {
+        Ok(self.writer.flush()?)
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let mut args = env::args().skip(1);
+    let target = args.next().expect("Target URL required");
+    let wordlist = args.next().expect("Wordlist path required");
+    let output = args.next().unwrap_or_else(|| "hits.tsv".to_string());
+
+    let (tx, rx) = mpsc::channel::<WorkerMessage>();
+
+    let handle = WorkerBuilder::default()
+        .uri(&target)
+        .wordlist(&wordlist)
+        .scan_mode(ScanMode::Directory)
+        .output_sink(TsvSink {
+            writer: BufWriter::new(File::create(output)?),
+        })
+        .message_sender(tx.into())
+        .build()?
+        .spawn();
+
+    // The sink is fed by the worker; the channel still has to be drained.
+    for _ in rx {}
+
+    handle.join()?;
+    Ok(())
+}
@@ -0,0 +1,108 @@
+//! Extends a scan with a plugin that would normally live in its own crate:
+//! it tries editor backups of every word, drops login redirects and counts
+//! hits per status.
+//!
+//! cargo run --example plugin -- http://localhost:8080 wordlist.txt
+
+use std::{env, sync::mpsc};
+
+use yadb::prelude::*;
+
+mod backups {
+    use std::{
+        collections::BTreeMap,
+        sync::{Arc, Mutex},
+    };
+
+    use yadb::prelude::*;
+
+    /// Tries `word~`, as left behind by editors.
+    struct EditorBackup;
+
+    impl WordTransform for EditorBackup {
+        fn apply(&self, word: &str) -> String {
+            format!("{word}~")
+        }
+    }
+
+    /// Drops redirects to a login page, which say nothing about the path.
+    struct NoLoginRedirects;
+
+    impl ResponseFilter for NoLoginRedirects {
+        fn keep(&self, response: &ResponseInfo) -> bool {
+            !(300..400).contains(&response.status)
+                || !response
+                    .headers
+                    .get("location")
+                    .and_then(|location| location.to_str().ok())
+                    .is_some_and(|location| location.contains("login"))
+        }
+    }
+
+    type Counts = Arc<Mutex<BTreeMap<u16, usize>>>;
+
+    struct StatusCounter(Counts);
+
+    impl OutputSink for StatusCounter {
+        fn on_hit(&mut self, hit: &HitResult) -> anyhow::Result<()> {
+            *self.0.lock().unwrap().entry(hit.status).or_default() += 1;
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    pub struct BackupsPlugin {
+        counts: Counts,
+    }
+
+    impl BackupsPlugin {
+        /// Hits per status so far.
+        pub fn counts(&self) -> BTreeMap<u16, usize> {
+            self.counts.lock().unwrap().clone()
+        }
+    }
+
+    impl Plugin for BackupsPlugin {
+        fn name(&self) -> &str {
+            "backups"
+        }
+
+        fn register(&self, registry: &mut PluginRegistry) -> anyhow::Result<()> {
+            registry
+                .register_candidate_transform(EditorBackup)
+                .register_filter(NoLoginRedirects)
+                .register_sink(StatusCounter(self.counts.clone()));
+            Ok(())
+        }
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let mut args = env::args().skip(1);
+    let target = args.next().expect("Target URL required");
+    let wordlist = args.next().expect("Wordlist path required");
+
+    let plugin = backups::BackupsPlugin::default();
+    let (tx, rx) = mpsc::channel::<WorkerMessage>();
+
+    let handle = WorkerBuilder::default()
+        .uri(&target)
+        .wordlist(&wordlist)
+        .plugin(&plugin)
+        .message_sender(tx.into())
+        .build()?
+        .spawn();
+
+    for msg in rx {
+        if let WorkerMessage::Hit(hit) = msg {
+            println!("{hit}");
+        }
+    }
+
+    handle.join()?;
+
+    for (status, count) in plugin.counts() {
+        println!("{status}: {count}");
+    }
+    Ok(())
+}
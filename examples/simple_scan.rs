@@ -0,0 +1,38 @@
+//! Scans a target and prints every hit.
+//!
+//! cargo run --example simple_scan -- http://localhost:8080 /usr/share/wordlists/dirb/common.txt
+
+use std::{env, sync::mpsc};
+
+use yadb::prelude::*;
+
+fn main() -> anyhow::Result<()> {
+    let mut args = env::args().skip(1);
+    let target = args.next().expect("Target URL required");
+    let wordlist = args.next().expect("Wordlist path required");
+
+    let (tx, rx) = mpsc::channel::<WorkerMessage>();
+
+    let handle: WorkerHandle = WorkerBuilder::default()
+        .uri(&target)
+        .wordlist(&wordlist)
+        .threads(10)
+        .message_sender(tx.into())
+        .build()?
+        .spawn();
+
+    for msg in rx {
+        if let WorkerMessage::Hit(hit) = msg {
+            println!("{hit}");
+        }
+    }
+
+    let report = handle.join()?;
+    println!(
+        "{} hits, {} requests in {:.1}s",
+        report.hits.len(),
+        report.requests,
+        report.duration.as_secs_f64()
+    );
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use std::{
+    io::{self, BufRead, IsTerminal},
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+/// Forwards the lines typed while a scan runs, so that `p` and Enter can
+/// pause or resume it and prompts can be answered without racing for stdin.
+/// `None` when stdin isn't a terminal, e.g. under nohup.
+pub fn listen() -> Option<Receiver<String>> {
+    if !io::stdin().is_terminal() {
+        return None;
+    }
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if tx.send(line.trim().to_string()).is_err() {
+                break;
+            }
+        }
+    });
+    Some(rx)
+}
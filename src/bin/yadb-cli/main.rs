@@ -0,0 +1,100 @@
+mod keys;
+mod report;
+mod scan;
+#[cfg(unix)]
+mod signals;
+mod stats;
+mod templates;
+mod wordlist;
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process,
+};
+
+use clap::{Parser, Subcommand};
+use yadb::lib::i18n::{self, Lang};
+
+use crate::scan::{ScanArgs, ScanState};
+
+#[derive(Parser)]
+#[command(name = "yadb-cli")]
+#[command(version)]
+#[command(about = "Yet Another Directory Buster")]
+#[command(long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Language of summaries (defaults to $YADB_LANG or the locale)
+    #[arg(long, global = true, value_enum)]
+    lang: Option<Lang>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Scan a target for directories, files or virtual hosts
+    Scan(Box<ScanArgs>),
+
+    /// Convert saved results to HTML, CSV or Markdown
+    Report(report::ReportArgs),
+
+    /// Continue a scan started with --state
+    Resume {
+        /// State file of the scan
+        state: PathBuf,
+    },
+
+    /// List the scan templates of ~/.config/yadb/templates
+    Templates,
+
+    /// Show the usage statistics of scans run with --stats
+    Stats,
+
+    /// Dedupe, merge and inspect wordlists
+    #[command(subcommand)]
+    Wordlist(wordlist::WordlistCommand),
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if let Some(lang) = cli.lang {
+        i18n::set_lang(lang);
+    }
+
+    let result = match cli.command {
+        Command::Scan(args) => {
+            let raw_args = env::args().skip_while(|arg| arg != "scan").skip(1);
+            scan::run(*args, raw_args.collect());
+            Ok(())
+        }
+        Command::Report(args) => report::run(args),
+        Command::Resume { state } => resume(&state),
+        Command::Templates => templates::run(),
+        Command::Stats => stats::run(),
+        Command::Wordlist(command) => wordlist::run(command),
+    };
+
+    if let Err(err) = result {
+        eprintln!("Error: {err:#}");
+        process::exit(1);
+    }
+}
+
+fn resume(path: &Path) -> anyhow::Result<()> {
+    let state = ScanState::load(path)?;
+    env::set_current_dir(&state.dir)?;
+
+    let argv = ["yadb-cli", "scan"]
+        .into_iter()
+        .chain(state.args.iter().map(String::as_str));
+    match Cli::try_parse_from(argv)
+        .unwrap_or_else(|err| err.exit())
+        .command
+    {
+        Command::Scan(args) => scan::run(*args, state.args),
+        _ => unreachable!("state files hold scan arguments"),
+    }
+    Ok(())
+}
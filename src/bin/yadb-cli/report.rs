@@ -0,0 +1,40 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Write},
+    path::PathBuf,
+};
+
+use anyhow::Context;
+use clap::Args;
+use yadb::lib::report::{self, ReportFormat};
+
+#[derive(Args)]
+pub struct ReportArgs {
+    /// Results saved with `--sink jsonl=PATH` or `--progress-jsonl`
+    input: PathBuf,
+
+    /// Report format
+    #[arg(short, long, value_enum, default_value_t = ReportFormat::Html)]
+    format: ReportFormat,
+
+    /// Write the report here instead of stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+pub fn run(args: ReportArgs) -> anyhow::Result<()> {
+    let input =
+        File::open(&args.input).with_context(|| format!("Can't open {}", args.input.display()))?;
+    let results = report::read_results(BufReader::new(input))?;
+
+    let mut writer: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(BufWriter::new(
+            File::create(path).with_context(|| format!("Can't create {}", path.display()))?,
+        )),
+        None => Box::new(io::stdout().lock()),
+    };
+
+    report::write_report(args.format, &results, &mut writer)?;
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,1164 @@
+use std::{
+    env, fs,
+    fs::OpenOptions,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{
+        Mutex,
+        mpsc::{self, Receiver, RecvTimeoutError},
+    },
+    time::Duration,
+};
+
+use anyhow::Context;
+use clap::Args;
+use console::style;
+use indicatif::MultiProgress;
+use serde::{Deserialize, Serialize};
+use yadb::lib::{
+    i18n::{Msg, tr_args},
+    logger::{
+        file_logger::FileLogger,
+        traits::{NullLogger, WorkerLogger},
+    },
+    output::{
+        stats::{StatsSink, UsageStats},
+        stream::{self, StreamSink},
+    },
+    progress_handler::{
+        indicatif_handler::IndicatifHandler, json_handler::JsonHandler, traits::ProgressHandler,
+    },
+    report::{ScanReport, ScanSummary},
+    template::ScanTemplate,
+    util,
+    worker::{
+        builder::{DEFAULT_THREADS_NUMBER, WorkerBuilder},
+        candidates::Transform,
+        config::{Engine, HttpMethod, ScanMode, WorkerConfig},
+        filter::NumberRange,
+        messages::WorkerMessage,
+        politeness::Politeness,
+        profile::BrowserProfile,
+    },
+};
+
+use crate::keys;
+#[cfg(unix)]
+use crate::signals;
+
+/// How often `--progress-jsonl` reports progress.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+/// How often requests for a progress line are looked at while no message comes.
+const CONTROL_POLL: Duration = Duration::from_millis(200);
+
+#[derive(Args)]
+pub struct ScanArgs {
+    /// Number of threads [default: 50]
+    #[arg(short, long)]
+    threads: Option<usize>,
+
+    /// Requests in flight at once (replaces --threads)
+    #[arg(long, conflicts_with = "threads")]
+    concurrency: Option<usize>,
+
+    /// Start with a few threads and adapt their number, up to --threads, to the target's latency and errors
+    #[arg(long)]
+    auto_threads: bool,
+
+    /// How requests are sent; async needs yadb built with the async feature
+    #[arg(long, value_enum, default_value_t = Engine::Threads)]
+    engine: Engine,
+
+    /// OS threads of the async engine (ignored by the default engine)
+    #[arg(long)]
+    workers: Option<usize>,
+
+    /// Timeout of request (e.g. 5s, 2s500ms)
+    #[arg(long, default_value = "5s", value_parser = parse_timeout)]
+    timeout: Duration,
+
+    /// Stop the scan after this much time (e.g. 1h30m)
+    #[arg(long, value_parser = parse_max_time)]
+    max_time: Option<Duration>,
+
+    /// Move on from a directory once it has been scanned this long (e.g. 10m)
+    #[arg(long, value_parser = parse_max_time)]
+    max_time_per_dir: Option<Duration>,
+
+    /// Abort the scan once this many requests in a row failed (0 = never)
+    #[arg(long)]
+    max_errors: Option<usize>,
+
+    /// Start the threads one by one over this time instead of all at once (e.g. 30s)
+    #[arg(long, value_parser = util::parse_duration)]
+    ramp_up: Option<Duration>,
+
+    /// Delay between requests of each thread (e.g. 100ms)
+    #[arg(long, value_parser = parse_delay)]
+    delay: Option<Duration>,
+
+    /// Cap the requests per second of all threads together (0 lifts a preset's cap)
+    #[arg(long, value_name = "N")]
+    rate: Option<u32>,
+
+    /// Random extra pause of up to this much before each request (e.g. 200ms)
+    #[arg(long, value_parser = parse_delay)]
+    jitter: Option<Duration>,
+
+    /// Retry requests that failed without a response this many times
+    #[arg(long, value_name = "N")]
+    retries: Option<u32>,
+
+    /// Preset of concurrency, rate limit, delay, jitter, retries and user agent;
+    /// other flags override its values
+    #[arg(long, value_enum)]
+    politeness: Option<Politeness>,
+
+    /// Recursivly parse directories and files (recursion depth)
+    #[arg(short, long, default_value_t = 0)]
+    recursion: usize,
+
+    /// Path to wordlist
+    #[arg(short, long, required_unless_present = "template")]
+    wordlist: Option<String>,
+
+    /// Refuse to scan unless the wordlist has this hex SHA-256
+    #[arg(long, value_name = "HASH")]
+    wordlist_sha256: Option<String>,
+
+    /// Start from a template of ~/.config/yadb/templates (see `yadb-cli templates`)
+    #[arg(long, value_name = "NAME")]
+    template: Option<String>,
+
+    /// Target URL
+    #[arg(short = 'u', long)]
+    target_url: String,
+
+    /// Proxy URL
+    #[arg(short, long)]
+    proxy_url: Option<String>,
+
+    /// File of proxies, one per line, that requests rotate through round-robin
+    #[arg(long, value_name = "PATH")]
+    proxy_list: Option<String>,
+
+    /// Output file
+    #[arg(short, long)]
+    output: Option<String>,
+
+    /// Load cookies from this file at start and save them back at the end
+    #[arg(long)]
+    cookie_jar: Option<String>,
+
+    /// Imitate the headers of a browser
+    #[arg(long, value_enum)]
+    profile_browser: Option<BrowserProfile>,
+
+    /// Send this header with every request, e.g. "Authorization: Bearer ${TOKEN}" (repeatable)
+    #[arg(short = 'H', long, value_name = "NAME: VALUE", value_parser = util::parse_header)]
+    header: Vec<(String, String)>,
+
+    /// Pause and ask before continuing when a WAF starts blocking requests
+    #[arg(long)]
+    waf_pause: bool,
+
+    /// Local address to send requests from (repeat to rotate between several)
+    #[arg(long, value_delimiter = ',')]
+    bind_address: Vec<String>,
+
+    /// Only report responses whose body matches this regex
+    #[arg(long)]
+    match_regex: Option<String>,
+
+    /// Drop responses whose body matches this regex
+    #[arg(long)]
+    filter_regex: Option<String>,
+
+    /// Attach the named groups of this regex matching a hit's body to it, e.g.
+    /// 'Version (?<version>[\d.]+)' (repeatable; --match-regex groups count too)
+    #[arg(long)]
+    capture: Vec<String>,
+
+    /// Drop responses of this size in bytes or range of sizes like 100-200 (repeat or separate with commas)
+    #[arg(long, value_delimiter = ',', value_name = "SIZE")]
+    filter_size: Vec<NumberRange>,
+
+    /// Drop responses whose body has this many words or a number in a range like 10-20
+    /// (repeat or separate with commas)
+    #[arg(long, value_delimiter = ',', value_name = "COUNT")]
+    filter_words: Vec<NumberRange>,
+
+    /// Drop responses whose body has this many lines or a number in a range like 10-20
+    /// (repeat or separate with commas)
+    #[arg(long, value_delimiter = ',', value_name = "COUNT")]
+    filter_lines: Vec<NumberRange>,
+
+    /// Only report responses with these status codes (repeat or separate with commas)
+    #[arg(long, value_delimiter = ',', value_name = "CODES", value_parser = util::parse_status)]
+    status_codes: Vec<u16>,
+
+    /// Drop responses with these status codes on top of 404 (repeat or separate with commas)
+    #[arg(long, value_delimiter = ',', value_name = "CODES", value_parser = util::parse_status)]
+    exclude_status: Vec<u16>,
+
+    /// Report responses with the same body only once
+    #[arg(long)]
+    dedupe: bool,
+
+    /// Hide favicons, stylesheets, scripts and other static assets from the results
+    #[arg(long)]
+    hide_static: bool,
+
+    /// Recognize WordPress, Tomcat or Jenkins and probe their sensitive paths
+    #[arg(long)]
+    check_packs: bool,
+
+    /// Request every hit once more after the scan and mark the ones that changed
+    #[arg(long)]
+    verify: bool,
+
+    /// Request the words in random order
+    #[arg(long)]
+    shuffle: bool,
+
+    /// Read the wordlist as words with counts (e.g. `admin,1520`) and request the most frequent first
+    #[arg(long, conflicts_with = "shuffle")]
+    by_frequency: bool,
+
+    /// Also request word.EXT for every word, e.g. `-x php,bak` (repeat or separate with commas)
+    #[arg(short = 'x', long, value_delimiter = ',', value_name = "EXT")]
+    extensions: Vec<String>,
+
+    /// Also try every word rewritten this way (repeat or separate with commas)
+    #[arg(long, value_enum, value_delimiter = ',')]
+    transform: Vec<Transform>,
+
+    /// Request words containing `/` as nested paths instead of encoding the slash
+    #[arg(long)]
+    multi_segment: bool,
+
+    /// Request every word with a trailing slash, as a directory (default)
+    #[arg(long, overrides_with = "no_slash")]
+    add_slash: bool,
+
+    /// Request words without a trailing slash, for routes that don't take one
+    #[arg(long, overrides_with = "add_slash")]
+    no_slash: bool,
+
+    /// Print how many requests the scan would send per directory and exit
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Seed for random choices, to reproduce an earlier scan
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Write progress as JSON lines to this file or FIFO ("-" for stderr)
+    #[arg(long, value_name = "PATH")]
+    progress_jsonl: Option<String>,
+
+    /// Print a status line with progress, rate, hits, errors and ETA this often
+    /// (e.g. 10s); shown even when output is captured and bars aren't drawn
+    #[arg(long, value_name = "INTERVAL", value_parser = parse_stats_interval)]
+    stats_interval: Option<Duration>,
+
+    /// Forward hits to a collector as they are found (tcp://host:port or tls://host:port)
+    #[arg(long, value_name = "URL")]
+    stream_results: Option<String>,
+
+    /// File keeping hits while the collector is down [default: one per scan in the data directory]
+    #[arg(long, value_name = "PATH")]
+    stream_spill: Option<PathBuf>,
+
+    /// Also write results to KIND=TARGET (file, jsonl, csv, sqlite, webhook, stream or stats);
+    /// webhook:NAME posts hits with a payload template of webhooks.toml
+    #[arg(long, value_name = "KIND=TARGET")]
+    sink: Vec<String>,
+
+    /// Add the scan to the local usage statistics (see `yadb-cli stats`)
+    #[arg(long)]
+    stats: bool,
+
+    /// Read at most this much of response bodies during the scan (e.g. 200M)
+    #[arg(long, value_name = "SIZE", value_parser = util::parse_size)]
+    capture_quota: Option<u64>,
+
+    /// Read only this much of bodies of uninteresting responses (e.g. 4K)
+    #[arg(long, value_name = "SIZE", value_parser = util::parse_size)]
+    capture_sample: Option<u64>,
+
+    /// Scan https:// instead when the http:// target redirects every request there
+    #[arg(long)]
+    upgrade_https: bool,
+
+    /// Send HEAD first and GET only paths that don't answer 404 to it
+    #[arg(long)]
+    head_first: bool,
+
+    /// Don't probe every directory for what it answers to paths that don't exist
+    #[arg(long)]
+    no_calibration: bool,
+
+    /// Recurse into every hit instead of only those that look like directories
+    #[arg(long)]
+    force_recursion: bool,
+
+    /// Follow up to N redirects of every path and report where they end
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    follow_redirects: usize,
+
+    /// Skip the paths robots.txt disallows, for engagements that require it
+    #[arg(long)]
+    respect_robots: bool,
+
+    /// Request paths like logout or delete even when sending cookies or credentials
+    #[arg(long)]
+    allow_dangerous: bool,
+
+    /// Words skipped in authenticated scans, replacing the built-in list (repeat or separate with commas)
+    #[arg(long, value_delimiter = ',', value_name = "WORD")]
+    dangerous_word: Vec<String>,
+
+    /// Time DNS, connect, TLS and first byte of each hit over a fresh connection
+    #[arg(long)]
+    timings: bool,
+
+    /// Known-good path to request now and then, alerting when its answer changes
+    #[arg(long, value_name = "PATH")]
+    canary: Option<String>,
+
+    /// Time between canary requests (e.g. 10s) [default: 30s]
+    #[arg(long, value_parser = parse_max_time)]
+    canary_interval: Option<Duration>,
+
+    /// Stop when hits dry up deep into the wordlist instead of only suggesting it
+    #[arg(long)]
+    early_stop: bool,
+
+    /// Hits per 1000 requests below which stopping is suggested [default: 1]
+    #[arg(long, value_name = "N")]
+    early_stop_hits: Option<usize>,
+
+    /// Read at most this much of each response body (e.g. 256K) [default: 1M]
+    #[arg(long, value_name = "SIZE", value_parser = util::parse_size)]
+    max_body_size: Option<u64>,
+
+    /// Keep at most this many hits in memory, spilling the rest to a temporary file
+    #[arg(long, value_name = "N")]
+    max_stored_hits: Option<usize>,
+
+    /// Keep at most this much of hits in memory, spilling the rest to a temporary file (e.g. 64M)
+    #[arg(long, value_name = "SIZE", value_parser = util::parse_size)]
+    max_stored_bytes: Option<u64>,
+
+    /// Results of an earlier scan (JSONL); its hits are re-checked with conditional requests
+    #[arg(long, value_name = "PATH")]
+    since: Option<String>,
+
+    /// Keep a state file for `yadb-cli resume` until the scan completes; the
+    /// progress goes to PATH.checkpoint unless --resume names a file
+    #[arg(long, value_name = "PATH")]
+    state: Option<PathBuf>,
+
+    /// Save progress to this file while scanning and continue from it when it
+    /// exists, e.g. after a crash (removed once the scan completes)
+    #[arg(long, value_name = "PATH")]
+    resume: Option<String>,
+
+    /// Scan mode
+    #[arg(short, long, value_enum, default_value_t = ScanMode::Directory)]
+    mode: ScanMode,
+
+    /// HTTP method every word is requested with; HEAD skips the bodies
+    #[arg(short = 'X', long, value_enum, ignore_case = true, default_value_t = HttpMethod::Get)]
+    method: HttpMethod,
+}
+
+/// What `yadb-cli resume` needs to continue a scan.
+#[derive(Serialize, Deserialize)]
+pub struct ScanState {
+    /// Directory relative paths of the scan are resolved against.
+    pub dir: PathBuf,
+    /// Arguments of the `scan` subcommand.
+    pub args: Vec<String>,
+}
+
+impl ScanState {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let data =
+            fs::read_to_string(path).with_context(|| format!("Can't read {}", path.display()))?;
+        serde_json::from_str(&data)
+            .with_context(|| format!("Invalid state file {}", path.display()))
+    }
+
+    fn save(&self, path: &Path) -> anyhow::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Can't write {}", path.display()))
+    }
+}
+
+/// Checkpoint kept next to the state file `state`, so `yadb-cli resume`
+/// goes on from where the scan got.
+fn state_checkpoint(state: &Path) -> String {
+    format!("{}.checkpoint", state.display())
+}
+
+fn parse_timeout(value: &str) -> Result<Duration, String> {
+    let timeout = util::parse_duration(value)?;
+    if timeout.is_zero() || timeout > Duration::from_secs(3600) {
+        return Err("timeout must be between 1ms and 1h".to_string());
+    }
+    Ok(timeout)
+}
+
+fn parse_max_time(value: &str) -> Result<Duration, String> {
+    let max_time = util::parse_duration(value)?;
+    if max_time.is_zero() {
+        return Err("max time must be greater than zero".to_string());
+    }
+    Ok(max_time)
+}
+
+fn parse_stats_interval(value: &str) -> Result<Duration, String> {
+    let interval = util::parse_duration(value)?;
+    if interval.is_zero() {
+        return Err("stats interval must be greater than zero".to_string());
+    }
+    Ok(interval)
+}
+
+fn parse_delay(value: &str) -> Result<Duration, String> {
+    let delay = util::parse_duration(value)?;
+    if delay > Duration::from_secs(60) {
+        return Err("delay can't be longer than 1m".to_string());
+    }
+    Ok(delay)
+}
+
+fn open_progress_jsonl(path: &str) -> io::Result<Box<dyn Write>> {
+    if path == "-" {
+        return Ok(Box::new(io::stderr()));
+    }
+
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?;
+    Ok(Box::new(file))
+}
+
+/// Runs a scan; `raw_args` are saved to the state file for resuming.
+pub fn run(args: ScanArgs, raw_args: Vec<String>) {
+    util::print_logo();
+
+    let template = match args.template.as_deref().map(ScanTemplate::find) {
+        Some(Ok(template)) => Some(template),
+        Some(Err(err)) => {
+            println!("Error: {err:#}");
+            return;
+        }
+        None => None,
+    };
+    if let Some(template) = &template {
+        println!("Template: {}", style(&template.name).cyan());
+    }
+
+    match (args.concurrency, args.threads) {
+        (Some(max), _) | (None, Some(max)) if args.auto_threads => {
+            println!("Threads: {}", style(format!("auto, up to {max}")).cyan())
+        }
+        (None, None) if args.auto_threads && args.politeness.is_none() => println!(
+            "Threads: {}",
+            style(format!("auto, up to {DEFAULT_THREADS_NUMBER}")).cyan()
+        ),
+        (Some(concurrency), _) => println!("Concurrency: {}", style(concurrency).cyan()),
+        (None, Some(threads)) => println!("Threads: {}", style(threads).cyan()),
+        (None, None) if args.politeness.is_none() => {
+            println!("Threads: {}", style(DEFAULT_THREADS_NUMBER).cyan())
+        }
+        (None, None) => {}
+    }
+    let checkpoint = args
+        .resume
+        .clone()
+        .or_else(|| args.state.as_deref().map(state_checkpoint));
+    let resuming = checkpoint
+        .as_deref()
+        .is_some_and(|path| Path::new(path).exists());
+
+    if args.engine == Engine::Async {
+        println!("Engine: {}", style("async").cyan());
+    }
+    if let Some(workers) = args.workers {
+        println!("Runtime workers: {}", style(workers).cyan());
+    }
+    println!(
+        "Recursion depth: {}",
+        style(args.recursion.to_string()).cyan()
+    );
+    println!(
+        "Timeout: {}",
+        style(util::format_duration(args.timeout)).cyan()
+    );
+    if let Some(max_time) = args.max_time {
+        println!(
+            "Max time: {}",
+            style(util::format_duration(max_time)).cyan()
+        );
+    }
+    if let Some(max_time) = args.max_time_per_dir {
+        println!(
+            "Max time per directory: {}",
+            style(util::format_duration(max_time)).cyan()
+        );
+    }
+    if let Some(max_errors) = args.max_errors.filter(|max_errors| *max_errors > 0) {
+        println!("Max errors in a row: {}", style(max_errors).cyan());
+    }
+    if let Some(delay) = args.delay {
+        println!("Delay: {}", style(util::format_duration(delay)).cyan());
+    }
+    if let Some(rate) = args.rate.filter(|rate| *rate > 0) {
+        println!("Rate limit: {}", style(format!("{rate}/s")).cyan());
+    }
+    if let Some(ramp_up) = args.ramp_up {
+        println!("Ramp-up: {}", style(util::format_duration(ramp_up)).cyan());
+    }
+    let wordlist = args.wordlist.clone().or_else(|| {
+        template
+            .as_ref()
+            .and_then(|template| template.wordlist.as_ref())
+            .map(|path| path.display().to_string())
+    });
+    if let Some(wordlist) = wordlist {
+        println!("Wordlist path: {}", style(wordlist).cyan());
+    }
+    println!("Target: {}", style(args.target_url.to_string()).cyan());
+    println!("Mode: {}", style(format!("{:?}", args.mode)).cyan());
+    if args.method != HttpMethod::Get {
+        println!("Method: {}", style(args.method).cyan());
+    }
+    if let Some(proxy_url) = args.proxy_url.as_ref() {
+        println!("Proxy: {}\n", style(proxy_url.to_string()).cyan())
+    }
+
+    if let Some(proxy_list) = args.proxy_list.as_ref() {
+        println!("Proxy list: {}", style(proxy_list).cyan());
+    }
+
+    if let Some(profile) = args.profile_browser {
+        println!("Browser profile: {}", style(format!("{profile:?}")).cyan());
+    }
+
+    if !args.bind_address.is_empty() {
+        println!(
+            "Bind address: {}",
+            style(args.bind_address.join(", ")).cyan()
+        );
+    }
+
+    if !args.extensions.is_empty() {
+        let extensions = args
+            .extensions
+            .iter()
+            .map(|ext| ext.trim_start_matches('.'))
+            .collect::<Vec<_>>();
+        println!("Extensions: {}", style(extensions.join(", ")).cyan());
+    }
+
+    if !args.header.is_empty() {
+        let names = args
+            .header
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        println!("Headers: {}", style(names.join(", ")).cyan());
+    }
+
+    if let Some(cookie_jar) = args.cookie_jar.as_ref() {
+        println!("Cookie jar: {}", style(cookie_jar.to_string()).cyan());
+    }
+
+    if let Some(output) = args.output.as_ref() {
+        println!("Output: {}\n", style(output.to_string()).cyan());
+    }
+
+    let logger = if let Some(output) = args.output {
+        match FileLogger::new(output, resuming) {
+            Ok(log) => WorkerLogger::FileLogger(Mutex::new(log)),
+            Err(err) => {
+                println!("Error: {err}");
+                return;
+            }
+        }
+    } else {
+        WorkerLogger::NullLogger(NullLogger::default())
+    };
+
+    let (tx, rx) = mpsc::channel::<WorkerMessage>();
+
+    let mut worker = template
+        .as_ref()
+        .map_or_else(WorkerBuilder::default, |template| {
+            template.apply(WorkerBuilder::default())
+        })
+        .recursive(args.recursion)
+        .timeout(args.timeout)
+        .uri(&args.target_url)
+        .scan_mode(args.mode)
+        .method(args.method)
+        .engine(args.engine)
+        .waf_pause(args.waf_pause)
+        .verify_hits(args.verify)
+        .shuffle(args.shuffle)
+        .by_frequency(args.by_frequency)
+        .message_sender(tx.into());
+
+    if let Some(concurrency) = args.concurrency.or(args.threads) {
+        worker = worker.concurrency(concurrency);
+    }
+
+    if args.auto_threads {
+        worker = worker.auto_threads(true);
+    }
+
+    if let Some(politeness) = args.politeness {
+        worker = worker.politeness(politeness);
+    }
+
+    if let Some(rate) = args.rate {
+        worker = worker.rate_limit(rate);
+    }
+
+    if let Some(jitter) = args.jitter {
+        worker = worker.jitter(jitter);
+    }
+
+    if let Some(retries) = args.retries {
+        worker = worker.retries(retries);
+    }
+
+    // Flags only add to what a template turned on.
+    if args.hide_static {
+        worker = worker.suppress_static(true);
+    }
+
+    if args.dedupe {
+        worker = worker.dedupe_bodies(true);
+    }
+
+    if args.check_packs {
+        worker = worker.check_packs(true);
+    }
+
+    if let Some(wordlist) = args.wordlist.as_ref() {
+        worker = worker.wordlist(wordlist);
+    }
+
+    if let Some(hash) = args.wordlist_sha256.as_ref() {
+        worker = worker.wordlist_sha256(hash);
+    }
+
+    if let Some(proxy_url) = args.proxy_url.as_ref() {
+        worker = worker.proxy_url(proxy_url);
+    }
+
+    if let Some(proxy_list) = args.proxy_list.as_ref() {
+        worker = worker.proxy_list(proxy_list);
+    }
+
+    if let Some(profile) = args.profile_browser {
+        worker = worker.browser_profile(profile);
+    }
+
+    worker = worker.headers(args.header.iter().map(|(name, value)| (name, value)));
+
+    if let Some(cookie_jar) = args.cookie_jar.as_ref() {
+        worker = worker.cookie_jar(cookie_jar);
+    }
+
+    for address in &args.bind_address {
+        worker = worker.bind_address(address);
+    }
+
+    if let Some(regex) = args.match_regex.as_ref() {
+        worker = worker.match_regex(regex);
+    }
+
+    if let Some(regex) = args.filter_regex.as_ref() {
+        worker = worker.filter_regex(regex);
+    }
+
+    for regex in &args.capture {
+        worker = worker.capture_regex(regex);
+    }
+
+    if !args.extensions.is_empty() {
+        worker = worker.extensions(&args.extensions);
+    }
+
+    for transform in &args.transform {
+        worker = worker.transform(*transform);
+    }
+
+    if args.multi_segment {
+        worker = worker.multi_segment_words(true);
+    }
+
+    if args.no_slash {
+        worker = worker.add_slash(false);
+    }
+
+    for size in &args.filter_size {
+        worker = worker.filter_size(*size);
+    }
+    for words in &args.filter_words {
+        worker = worker.filter_words(*words);
+    }
+    for lines in &args.filter_lines {
+        worker = worker.filter_lines(*lines);
+    }
+    for status in &args.status_codes {
+        worker = worker.status_code(*status);
+    }
+    for status in &args.exclude_status {
+        worker = worker.exclude_status(*status);
+    }
+
+    if let Some(max_time) = args.max_time {
+        worker = worker.max_time(max_time);
+    }
+    if let Some(max_time) = args.max_time_per_dir {
+        worker = worker.max_time_per_dir(max_time);
+    }
+
+    if let Some(max_errors) = args.max_errors.filter(|max_errors| *max_errors > 0) {
+        worker = worker.max_errors(max_errors);
+    }
+
+    if let Some(delay) = args.delay {
+        worker = worker.delay(delay);
+    }
+
+    if let Some(ramp_up) = args.ramp_up {
+        worker = worker.ramp_up(ramp_up);
+    }
+
+    if let Some(url) = args.stream_results.as_deref() {
+        let spill = args
+            .stream_spill
+            .clone()
+            .unwrap_or_else(|| stream::default_spill_path(url));
+        match StreamSink::new(url, spill) {
+            Ok(stream) => worker = worker.output_sink(stream),
+            Err(err) => println!("Can't stream results: {err}"),
+        }
+    }
+
+    for spec in &args.sink {
+        worker = worker.output(spec);
+    }
+
+    if args.stats {
+        match UsageStats::path() {
+            Some(path) => worker = worker.output_sink(StatsSink::new(path)),
+            None => println!("Can't record usage statistics: no home directory"),
+        }
+    }
+
+    if let Some(seed) = args.seed {
+        worker = worker.seed(seed);
+    }
+
+    if let Some(path) = checkpoint.as_deref() {
+        if resuming {
+            println!("Resuming from: {}", style(path).cyan());
+        }
+        worker = worker.checkpoint(path);
+    }
+
+    if let Some(path) = args.since.as_deref() {
+        println!("Previous results: {}", style(path).cyan());
+        worker = worker.previous_results(path);
+    }
+
+    if let Some(workers) = args.workers {
+        worker = worker.runtime_workers(workers);
+    }
+
+    if let Some(quota) = args.capture_quota {
+        worker = worker.capture_quota(quota);
+    }
+
+    if let Some(sample) = args.capture_sample {
+        worker = worker.capture_sample(sample);
+    }
+
+    if let Some(hits) = args.max_stored_hits {
+        worker = worker.max_stored_hits(hits);
+    }
+
+    if args.upgrade_https {
+        worker = worker.upgrade_https(true);
+    }
+
+    if args.head_first {
+        worker = worker.head_first(true);
+    }
+
+    if args.no_calibration {
+        worker = worker.calibration(false);
+    }
+
+    if args.force_recursion {
+        worker = worker.force_recursion(true);
+    }
+
+    if args.follow_redirects > 0 {
+        worker = worker.follow_redirects(args.follow_redirects);
+    }
+
+    if args.respect_robots {
+        worker = worker.respect_robots(true);
+    }
+
+    if args.timings {
+        worker = worker.phase_timings(true);
+    }
+
+    if args.allow_dangerous {
+        worker = worker.allow_dangerous(true);
+    }
+
+    if !args.dangerous_word.is_empty() {
+        worker = worker.dangerous_words(args.dangerous_word.iter().cloned());
+    }
+
+    if let Some(path) = args.canary.as_ref() {
+        worker = worker.canary(path);
+    }
+
+    if let Some(interval) = args.canary_interval {
+        worker = worker.canary_interval(interval);
+    }
+
+    if args.early_stop {
+        worker = worker.early_stop(true);
+    }
+
+    if let Some(hits) = args.early_stop_hits {
+        worker = worker.early_stop_hits(hits);
+    }
+
+    if let Some(bytes) = args.max_body_size {
+        worker = worker.max_body_size(bytes);
+    }
+
+    if let Some(bytes) = args.max_stored_bytes {
+        worker = worker.max_stored_bytes(bytes as usize);
+    }
+
+    let worker = worker.build();
+    if let Ok(worker) = &worker {
+        print_politeness(worker.config());
+    }
+
+    match worker {
+        Ok(worker) if args.dry_run => match worker.candidates() {
+            Ok(generator) => println!(
+                "Requests per directory: {}",
+                style(generator.count()).cyan()
+            ),
+            Err(err) => println!("Error: {err}"),
+        },
+
+        Ok(worker) => {
+            println!("Seed: {}\n", style(worker.config().seed).cyan());
+
+            if let Some(path) = args.state.as_deref() {
+                let state = env::current_dir().map(|dir| ScanState {
+                    dir,
+                    args: raw_args,
+                });
+                match state
+                    .map_err(anyhow::Error::from)
+                    .and_then(|s| s.save(path))
+                {
+                    Ok(()) => println!("State file: {}\n", style(path.display()).cyan()),
+                    Err(err) => println!("Can't save state: {err:#}"),
+                }
+            }
+
+            #[cfg(unix)]
+            let signals = match signals::forward(worker.control()) {
+                Ok(signals) => Some(signals),
+                Err(err) => {
+                    println!("Can't handle SIGUSR1 and SIGUSR2: {err}");
+                    None
+                }
+            };
+
+            let handle = worker.spawn();
+
+            let multi_progress = MultiProgress::new();
+            let mut handler = IndicatifHandler::new(&multi_progress, logger);
+            if let Some(interval) = args.stats_interval {
+                handler = handler.with_stats_interval(interval);
+            }
+            let mut progress_jsonl = match args.progress_jsonl.as_deref().map(open_progress_jsonl) {
+                Some(Ok(writer)) => Some(JsonHandler::new(writer).with_interval(PROGRESS_INTERVAL)),
+                Some(Err(err)) => {
+                    println!("Can't open progress stream: {err}");
+                    None
+                }
+                None => None,
+            };
+            let mut summary = None;
+            let mut aborted = None;
+            let keys = keys::listen();
+            if keys.is_some() {
+                let _ = multi_progress.println("Type p and Enter to pause or resume the scan");
+            }
+            loop {
+                if handle.control().take_stats_request() {
+                    handler.print_stats_now();
+                }
+                let typed = keys.iter().flat_map(Receiver::try_iter).collect::<Vec<_>>();
+                for line in typed {
+                    if line.eq_ignore_ascii_case("p") {
+                        let note = if handle.control().toggle_pause() {
+                            "Paused, type p and Enter to resume"
+                        } else {
+                            "Resumed"
+                        };
+                        let _ = multi_progress.println(style(note).yellow().to_string());
+                    }
+                }
+                let msg = match rx.recv_timeout(CONTROL_POLL) {
+                    Ok(msg) => msg,
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                };
+
+                // A WAF pauses the scan by itself only with --waf-pause.
+                let waf_paused =
+                    matches!(msg, WorkerMessage::WafDetected(_)) && handle.control().is_paused();
+                match &msg {
+                    WorkerMessage::Finished(finished) => summary = Some(finished.clone()),
+                    WorkerMessage::Aborted(reason) => aborted = Some(reason.clone()),
+                    _ => {}
+                }
+                if let Some(progress_jsonl) = progress_jsonl.as_mut() {
+                    progress_jsonl.handle(msg.clone());
+                }
+                handler.handle(msg);
+
+                if waf_paused {
+                    let resume = multi_progress.suspend(|| {
+                        print!("Requests are being blocked. Continue scanning? [y/N] ");
+                        let _ = io::stdout().flush();
+                        let answer = match &keys {
+                            Some(keys) => keys.recv().unwrap_or_default(),
+                            None => {
+                                let mut answer = String::new();
+                                let _ = io::stdin().read_line(&mut answer);
+                                answer
+                            }
+                        };
+                        answer.trim().eq_ignore_ascii_case("y")
+                    });
+
+                    if resume {
+                        handle.control().resume();
+                    } else {
+                        handle.control().stop();
+                    }
+                }
+            }
+
+            #[cfg(unix)]
+            if let Some(signals) = signals {
+                signals.close();
+            }
+
+            let report = match handle.join() {
+                Ok(report) => report,
+                Err(err) => {
+                    println!("Error: {err}");
+                    return;
+                }
+            };
+
+            if !report.stopped
+                && let Some(path) = args.state.as_deref()
+            {
+                let _ = fs::remove_file(path);
+            }
+
+            if let Some(summary) = &summary {
+                print_finished(summary);
+            }
+            if let Some(reason) = &aborted {
+                println!(
+                    "{}",
+                    tr_args(Msg::Aborted, &[("reason", &style(reason).red())])
+                );
+            }
+            print_summary(&report);
+        }
+
+        Err(err) => println!("Error: {err}"),
+    }
+}
+
+/// Spells out the pacing a `--politeness` preset resulted in, overrides included.
+fn print_politeness(config: &WorkerConfig) {
+    let Some(politeness) = config.politeness else {
+        return;
+    };
+
+    let rate_limit = config
+        .rate_limit
+        .map_or("none".to_string(), |rate| format!("{rate}/s"));
+    let user_agent = config
+        .browser_profile
+        .map_or("default".to_string(), |profile| format!("{profile:?}"));
+    println!(
+        "Politeness: {} (concurrency {}, rate limit {}, delay {}, jitter {}, retries {}, user agent {})",
+        style(politeness).cyan(),
+        config.concurrency,
+        rate_limit,
+        util::format_duration(config.delay),
+        util::format_duration(config.jitter),
+        config.retries,
+        user_agent,
+    );
+}
+
+/// Totals of the scan, as the worker sent them at its end.
+fn print_finished(summary: &ScanSummary) {
+    let duration = util::format_duration(Duration::from_millis(summary.elapsed.as_millis() as u64));
+    println!(
+        "\n{}",
+        tr_args(
+            Msg::Finished,
+            &[
+                ("duration", &style(duration).cyan()),
+                ("requests", &style(summary.requests).cyan()),
+                ("errors", &style(summary.errors).cyan()),
+                ("hits", &style(summary.hits).cyan()),
+            ],
+        )
+    );
+
+    if let Some(latency) = summary.average_latency {
+        println!(
+            "{}",
+            tr_args(
+                Msg::AverageLatency,
+                &[(
+                    "latency",
+                    &style(format!("{:.1}ms", latency.as_secs_f64() * 1000.0)).cyan()
+                )],
+            )
+        );
+    }
+}
+
+fn print_summary(report: &ScanReport) {
+    if let Some(vendor) = report.waf {
+        println!(
+            "{}",
+            tr_args(Msg::WafDetected, &[("vendor", &style(vendor).red())])
+        );
+    }
+
+    if let Some(stack) = report.stack {
+        println!(
+            "{}",
+            tr_args(Msg::StackDetected, &[("stack", &style(stack).cyan())])
+        );
+    }
+
+    if !report.header_findings.is_empty() {
+        println!(
+            "{}",
+            tr_args(
+                Msg::HeaderFindings,
+                &[("count", &style(report.header_findings.len()).yellow())],
+            )
+        );
+        for finding in &report.header_findings {
+            println!("  {finding}");
+        }
+    }
+
+    if !report.partial_dirs.is_empty() {
+        println!(
+            "{}",
+            tr_args(
+                Msg::PartialDirs,
+                &[("count", &style(report.partial_dirs.len()).yellow())],
+            )
+        );
+        for dir in &report.partial_dirs {
+            println!("  {dir}");
+        }
+    }
+
+    if !report.latency.is_empty() {
+        println!(
+            "{}",
+            tr_args(
+                Msg::Latency,
+                &[("percentiles", &style(&report.latency).cyan())]
+            )
+        );
+        for line in report.latency.ascii() {
+            println!("  {line}");
+        }
+    }
+
+    let capture = report.capture;
+    if let Some(quota) = capture.quota {
+        println!(
+            "{}",
+            tr_args(
+                Msg::BodiesCaptured,
+                &[
+                    ("used", &style(util::format_size(capture.used)).cyan()),
+                    ("quota", &util::format_size(quota)),
+                    ("full", &capture.full),
+                    ("sampled", &capture.sampled),
+                    ("skipped", &capture.skipped),
+                ],
+            )
+        );
+    }
+
+    if report.hits.spilled() > 0 {
+        println!(
+            "{}",
+            tr_args(
+                Msg::HitsSpilled,
+                &[
+                    ("spilled", &style(report.hits.spilled()).yellow()),
+                    ("kept", &report.hits.in_memory().len()),
+                ],
+            )
+        );
+    }
+
+    let hidden_static = report
+        .hits
+        .iter()
+        .map(|hits| hits.filter(|hit| hit.static_asset).count())
+        .unwrap_or_default();
+    if hidden_static > 0 {
+        println!(
+            "{}",
+            tr_args(Msg::StaticHidden, &[("count", &style(hidden_static).dim())])
+        );
+    }
+}
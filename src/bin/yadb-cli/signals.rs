@@ -0,0 +1,28 @@
+use std::{sync::Arc, thread};
+
+use signal_hook::{
+    consts::{SIGUSR1, SIGUSR2},
+    iterator::{Handle, Signals},
+};
+use yadb::lib::worker::control::WorkerControl;
+
+/// Turns `SIGUSR1` into a request for a progress line and `SIGUSR2` into a
+/// pause or resume of the scan controlled by `control`, for scans running
+/// under nohup or tmux. Close the returned handle when the scan is over.
+pub fn forward(control: Arc<WorkerControl>) -> std::io::Result<Handle> {
+    let mut signals = Signals::new([SIGUSR1, SIGUSR2])?;
+    let handle = signals.handle();
+
+    thread::spawn(move || {
+        for signal in signals.forever() {
+            match signal {
+                SIGUSR1 => control.request_stats(),
+                SIGUSR2 => {
+                    control.toggle_pause();
+                }
+                _ => {}
+            }
+        }
+    });
+    Ok(handle)
+}
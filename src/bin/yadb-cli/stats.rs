@@ -0,0 +1,38 @@
+use anyhow::anyhow;
+use console::style;
+use yadb::lib::output::stats::UsageStats;
+
+/// Wordlists listed by [`run`].
+const TOP_WORDLISTS: usize = 5;
+
+/// Prints the totals of the usage statistics file.
+pub fn run() -> anyhow::Result<()> {
+    let path = UsageStats::path().ok_or_else(|| anyhow!("No home directory"))?;
+    let stats = UsageStats::load(&path)?;
+
+    if stats.scans == 0 {
+        eprintln!(
+            "No scans recorded in {} yet, run them with --stats",
+            path.display()
+        );
+        return Ok(());
+    }
+
+    println!("Scans: {}", style(stats.scans).cyan());
+    println!("Requests: {}", style(stats.requests).cyan());
+    println!("Hits: {}", style(stats.hits).cyan());
+    println!(
+        "Average rate: {} requests/s",
+        style(format!("{:.1}", stats.average_rps())).cyan()
+    );
+
+    let wordlists = stats.top_wordlists(TOP_WORDLISTS);
+    if !wordlists.is_empty() {
+        println!("Top wordlists:");
+        for (wordlist, scans) in wordlists {
+            println!("  {wordlist} ({scans})");
+        }
+    }
+
+    Ok(())
+}
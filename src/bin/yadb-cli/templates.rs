@@ -0,0 +1,52 @@
+use console::style;
+use yadb::lib::template::ScanTemplate;
+
+/// Lists the templates with what they set; unreadable files are reported on stderr.
+pub fn run() -> anyhow::Result<()> {
+    let templates = ScanTemplate::load_all()?;
+
+    if templates.is_empty() {
+        if let Some(dir) = ScanTemplate::dir() {
+            eprintln!("No templates in {}", dir.display());
+        }
+        return Ok(());
+    }
+
+    for (path, template) in templates {
+        let template = match template {
+            Ok(template) => template,
+            Err(err) => {
+                eprintln!("{} {err:#}", style("Skipped:").yellow());
+                continue;
+            }
+        };
+
+        println!(
+            "{} ({})",
+            style(&template.name).cyan().bold(),
+            path.display()
+        );
+        if !template.description.is_empty() {
+            println!("  {}", template.description);
+        }
+        if let Some(wordlist) = &template.wordlist {
+            println!("  Wordlist: {}", wordlist.display());
+        }
+        if !template.headers.is_empty() {
+            println!("  Headers: {}", template.headers.len());
+        }
+        if !template.checks.is_empty() {
+            println!("  Checks: {}", template.checks.len());
+        }
+        if !template.stages.is_empty() {
+            let stages = template
+                .stages
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            println!("  Stages: {}", stages.join(" → "));
+        }
+    }
+
+    Ok(())
+}
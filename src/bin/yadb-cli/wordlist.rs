@@ -0,0 +1,132 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use clap::{Args, Subcommand};
+use console::style;
+use yadb::lib::{
+    i18n::{Msg, tr},
+    wordlist::{Cleanup, Wordlist, WordlistStats},
+};
+
+#[derive(Subcommand)]
+pub enum WordlistCommand {
+    /// Remove blank lines and repeated words
+    Dedupe {
+        wordlist: PathBuf,
+
+        #[command(flatten)]
+        cleanup: CleanupArgs,
+    },
+
+    /// Join several wordlists into one without duplicates
+    Merge {
+        #[arg(required = true, num_args = 2..)]
+        wordlists: Vec<PathBuf>,
+
+        #[command(flatten)]
+        cleanup: CleanupArgs,
+    },
+
+    /// Print line, duplicate, length and encoding counts
+    Stats { wordlist: PathBuf },
+}
+
+#[derive(Args)]
+pub struct CleanupArgs {
+    /// Sort the words
+    #[arg(long)]
+    sort: bool,
+
+    /// Drop words shorter than this many characters
+    #[arg(long)]
+    min_len: Option<usize>,
+
+    /// Drop words longer than this many characters
+    #[arg(long)]
+    max_len: Option<usize>,
+
+    /// Write the result here instead of stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+impl CleanupArgs {
+    fn cleanup(&self) -> Cleanup {
+        Cleanup {
+            sort: self.sort,
+            min_len: self.min_len,
+            max_len: self.max_len,
+        }
+    }
+}
+
+pub fn run(command: WordlistCommand) -> anyhow::Result<()> {
+    match command {
+        WordlistCommand::Dedupe { wordlist, cleanup } => clean(read(&wordlist)?, &cleanup),
+        WordlistCommand::Merge { wordlists, cleanup } => {
+            let mut merged = Wordlist::default();
+            for path in &wordlists {
+                merged.merge(read(path)?);
+            }
+            clean(merged, &cleanup)
+        }
+        WordlistCommand::Stats { wordlist } => {
+            let (_, stats) = Cleanup::default().apply(read(&wordlist)?);
+            for (msg, value) in [
+                (Msg::Lines, stats.lines),
+                (Msg::UniqueWords, stats.words),
+                (Msg::Duplicates, stats.duplicates),
+                (Msg::BlankLines, stats.blank),
+                (Msg::InvalidUtf8Lines, stats.invalid_utf8),
+                (Msg::LongestWord, stats.longest),
+            ] {
+                println!("{}: {}", tr(msg), style(value).cyan());
+            }
+            Ok(())
+        }
+    }
+}
+
+fn read(path: &Path) -> anyhow::Result<Wordlist> {
+    Wordlist::read(path).with_context(|| format!("Can't read {}", path.display()))
+}
+
+/// Writes the cleaned words and reports what was removed on stderr.
+fn clean(wordlist: Wordlist, args: &CleanupArgs) -> anyhow::Result<()> {
+    let (words, stats) = args.cleanup().apply(wordlist);
+
+    let mut writer: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(BufWriter::new(
+            File::create(path).with_context(|| format!("Can't create {}", path.display()))?,
+        )),
+        None => Box::new(io::stdout().lock()),
+    };
+
+    for word in &words {
+        writeln!(writer, "{word}")?;
+    }
+    writer.flush()?;
+
+    print_summary(&stats);
+    Ok(())
+}
+
+fn print_summary(stats: &WordlistStats) {
+    eprintln!(
+        "{} words written, {} duplicates, {} blank and {} out-of-range lines removed",
+        style(stats.words).cyan(),
+        style(stats.duplicates).cyan(),
+        style(stats.blank).cyan(),
+        style(stats.out_of_range).cyan(),
+    );
+    if stats.invalid_utf8 > 0 {
+        eprintln!(
+            "{} lines weren't valid UTF-8 and had bytes replaced",
+            style(stats.invalid_utf8).yellow()
+        );
+    }
+}
@@ -1,12 +1,118 @@
+use std::{
+    backtrace::Backtrace,
+    env, fs,
+    panic::{self, PanicHookInfo},
+    path::PathBuf,
+    thread::{self, ThreadId},
+};
+
+use chrono::Local;
+use clap::Parser;
+use color_eyre::eyre::eyre;
 use crossterm::cursor::SetCursorStyle;
-use yadb::lib::tui::app::App;
+use yadb::lib::{
+    tui::{app::App, settings::Settings},
+    util,
+    worker::handle::{self, DROP_GRACE},
+};
+
+#[derive(Parser)]
+#[command(name = "yadb-tui")]
+#[command(version)]
+#[command(about = "Yet Another Directory Buster")]
+struct Args {
+    /// Print the saved workers as plain text instead of starting the interface
+    #[arg(long)]
+    headless_dump: bool,
+
+    /// Write the options and templates to a TOML file, e.g. for a dotfiles repository
+    #[arg(long, value_name = "PATH", conflicts_with = "import_settings")]
+    export_settings: Option<PathBuf>,
+
+    /// Take over the options and templates of a file written by --export-settings
+    #[arg(long, value_name = "PATH")]
+    import_settings: Option<PathBuf>,
+}
 
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
+    let args = Args::parse();
+
+    if let Some(path) = args.export_settings {
+        Settings::current()
+            .and_then(|settings| settings.save(&path))
+            .map_err(|err| eyre!("{err:#}"))?;
+        println!("Settings exported to {}", path.display());
+        return Ok(());
+    }
+
+    if let Some(path) = args.import_settings {
+        Settings::load(&path)
+            .and_then(|settings| settings.apply())
+            .map_err(|err| eyre!("{err:#}"))?;
+        println!("Settings imported from {}", path.display());
+        return Ok(());
+    }
+
+    if args.headless_dump {
+        let app = App::new();
+        if let Some(err) = app.error() {
+            return Err(eyre!("{err}"));
+        }
+        println!("{}", app.dump());
+        return Ok(());
+    }
+
     let terminal = ratatui::init();
+    install_panic_hook();
     _ = crossterm::execute!(std::io::stdout(), SetCursorStyle::SteadyBar);
     let result = App::new().run(terminal);
+    restore_terminal();
+    // Scans stopped on quit still flush their outputs and checkpoints.
+    handle::wait_for_dropped(DROP_GRACE);
+    result
+}
+
+fn restore_terminal() {
     ratatui::restore();
     _ = crossterm::execute!(std::io::stdout(), SetCursorStyle::DefaultUserShape);
-    result
+}
+
+/// On a panic of the interface thread, gives the terminal back to the shell
+/// and leaves a crash log before the usual report is printed.
+///
+/// Panics of worker threads are caught and shown as failed workers, so they
+/// are left alone with the interface running.
+fn install_panic_hook() {
+    let ui_thread = thread::current().id();
+    let previous = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().id() != ui_thread {
+            return;
+        }
+
+        restore_terminal();
+        match write_crash_log(info, ui_thread) {
+            Ok(path) => eprintln!("Crash log written to {}", path.display()),
+            Err(err) => eprintln!("Can't write crash log: {err}"),
+        }
+        previous(info);
+    }));
+}
+
+fn write_crash_log(info: &PanicHookInfo, thread: ThreadId) -> std::io::Result<PathBuf> {
+    let dir = util::config_dir().unwrap_or_else(env::temp_dir);
+    fs::create_dir_all(&dir)?;
+
+    let now = Local::now();
+    let path = dir.join(format!("crash-{}.log", now.format("%Y%m%d-%H%M%S")));
+    let log = format!(
+        "yadb-tui {} crashed at {}\nThread: {thread:?}\n{info}\n\nBacktrace:\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        now.to_rfc3339(),
+        Backtrace::force_capture(),
+    );
+    fs::write(&path, log)?;
+    Ok(path)
 }
@@ -1,6 +1,32 @@
 pub mod lib {
+    pub mod i18n;
     pub mod logger;
+    pub mod output;
+    pub mod progress_handler;
+    pub mod report;
+    pub mod template;
     pub mod tui;
     pub mod util;
+    pub mod wordlist;
     pub mod worker;
 }
+
+/// The types most programs need to drive a scan.
+pub mod prelude {
+    pub use crate::lib::{
+        logger::traits::LogLevel,
+        output::OutputSink,
+        progress_handler::traits::{ProgressHandler, ProgressKind},
+        report::ScanReport,
+        worker::{
+            builder::{BuilderError, WorkerBuilder},
+            candidates::WordTransform,
+            config::ScanMode,
+            filter::{ResponseFilter, ResponseInfo},
+            handle::WorkerHandle,
+            hit::HitResult,
+            messages::WorkerMessage,
+            plugin::{Plugin, PluginRegistry},
+        },
+    };
+}
@@ -0,0 +1,432 @@
+//! User-facing strings of the TUI and CLI in every supported language.
+
+use std::{
+    env,
+    fmt::Display,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Language of the interface.
+#[derive(Debug, Default, Clone, Copy, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    #[default]
+    En,
+    Ru,
+}
+
+impl Lang {
+    /// Reads a language from a code or locale such as `ru` or `ru_RU.UTF-8`.
+    pub fn parse(code: &str) -> Option<Self> {
+        let code = code.split(['_', '-', '.']).next()?.to_ascii_lowercase();
+        match code.as_str() {
+            "en" => Some(Lang::En),
+            "ru" => Some(Lang::Ru),
+            _ => None,
+        }
+    }
+
+    /// `$YADB_LANG`, then the locale variables, then English.
+    pub fn from_env() -> Self {
+        ["YADB_LANG", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Lang::parse(&value))
+            .unwrap_or_default()
+    }
+}
+
+/// 0 until a language is picked, then the [`Lang`] index plus one.
+static LANG: AtomicU8 = AtomicU8::new(0);
+
+/// Current language, taken from the environment unless [`set_lang`] was called.
+pub fn lang() -> Lang {
+    match LANG.load(Ordering::Relaxed) {
+        1 => Lang::En,
+        2 => Lang::Ru,
+        _ => {
+            let lang = Lang::from_env();
+            set_lang(lang);
+            lang
+        }
+    }
+}
+
+pub fn set_lang(lang: Lang) {
+    let value = match lang {
+        Lang::En => 1,
+        Lang::Ru => 2,
+    };
+    LANG.store(value, Ordering::Relaxed);
+}
+
+/// Strings that have translations.
+///
+/// Ones with `{name}` placeholders are filled with [`tr_args`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Msg {
+    WorkersTitle,
+    WorkersMarkedTitle,
+    InfoTitle,
+    HelpTitle,
+    ErrorTitle,
+    Help,
+    Group,
+    Workers,
+    Waiting,
+    Running,
+    Done,
+    Failed,
+    Results,
+    HelpSwitchTabs,
+    HelpAddWorker,
+    HelpAddFromClipboard,
+    HelpDeleteWorker,
+    HelpStartStop,
+    HelpCollapse,
+    HelpStartGroup,
+    HelpExportGroup,
+    HelpDump,
+    HelpTemplate,
+    HelpFilter,
+    NoTemplates,
+    TemplatesTitle,
+    HelpMark,
+    HelpClearMarks,
+    HelpMarked,
+    HelpMoveFocus,
+    HelpEdit,
+    HelpPause,
+    FieldName,
+    FieldGroup,
+    FieldTemplate,
+    FieldUri,
+    FieldThreads,
+    FieldRecursion,
+    FieldTimeout,
+    FieldRateLimit,
+    FieldMethod,
+    FieldStatusCodes,
+    FieldWordlist,
+    FieldProxy,
+    FieldResumeFile,
+    FieldAutoExport,
+    Run,
+    Logs,
+    ResultsStaticHidden,
+    CurrentlyRequesting,
+    Arguments,
+    RecursionProgress,
+    TotalProgress,
+    Eta,
+    RateLimited,
+    Throttled,
+    WarmingUp,
+    Paused,
+    Exported,
+    ExportFailed,
+    Dumped,
+    NoUrlInClipboard,
+    Finished,
+    WafDetected,
+    StackDetected,
+    HeaderFindings,
+    PartialDirs,
+    Latency,
+    AverageLatency,
+    Aborted,
+    ActiveThreads,
+    BodiesCaptured,
+    HitsSpilled,
+    StaticHidden,
+    Lines,
+    UniqueWords,
+    Duplicates,
+    BlankLines,
+    InvalidUtf8Lines,
+    LongestWord,
+    ModeFlat,
+    ModeRecursive,
+    Stage,
+    AllResultsTitle,
+    CompareTitle,
+    CompareNeedsTwo,
+    HelpCompare,
+    HelpAllResults,
+    HelpScrollResults,
+    ColumnWorker,
+    ColumnStatus,
+    ColumnSize,
+    ColumnUrl,
+    ColumnTriage,
+    TriageInteresting,
+    TriageFalsePositive,
+    TriageDone,
+    HelpTriage,
+    TerminalTooSmall,
+}
+
+/// `msg` in the current language.
+pub fn tr(msg: Msg) -> &'static str {
+    match lang() {
+        Lang::En => en(msg),
+        Lang::Ru => ru(msg),
+    }
+}
+
+/// `msg` in the current language with its `{name}` placeholders filled.
+/// Only placeholders of the message itself are, not ones that turn up in
+/// the values put in.
+pub fn tr_args(msg: Msg, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = String::new();
+    let mut rest = tr(msg);
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let value = rest.find('}').and_then(|end| {
+            let (_, value) = args.iter().find(|(name, _)| *name == &rest[1..end])?;
+            Some((end, value))
+        });
+        match value {
+            Some((end, value)) => {
+                text.push_str(&value.to_string());
+                rest = &rest[end + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+fn en(msg: Msg) -> &'static str {
+    match msg {
+        Msg::WorkersTitle => "Workers",
+        Msg::WorkersMarkedTitle => "Workers ({marked} marked)",
+        Msg::InfoTitle => "Info",
+        Msg::HelpTitle => "Help",
+        Msg::ErrorTitle => "Error",
+        Msg::Help => "Help",
+        Msg::Group => "Group",
+        Msg::Workers => "Workers",
+        Msg::Waiting => "Waiting",
+        Msg::Running => "Running",
+        Msg::Done => "Done",
+        Msg::Failed => "Failed",
+        Msg::Results => "Results",
+        Msg::HelpSwitchTabs => "Switch tabs",
+        Msg::HelpAddWorker => "Add worker",
+        Msg::HelpAddFromClipboard => "Add worker for the URL in clipboard",
+        Msg::HelpDeleteWorker => "Delete worker",
+        Msg::HelpStartStop => "Start waiting worker, open others",
+        Msg::HelpCollapse => "Collapse/expand group",
+        Msg::HelpStartGroup => "Start all waiting workers of group",
+        Msg::HelpExportGroup => "Export results of group",
+        Msg::HelpDump => "Write worker or group as plain text",
+        Msg::HelpTemplate => "Add worker from a template",
+        Msg::HelpFilter => "Filter workers by name or host",
+        Msg::NoTemplates => "No templates in {dir}",
+        Msg::TemplatesTitle => "Templates",
+        Msg::HelpMark => "Mark worker or group",
+        Msg::HelpClearMarks => "Clear marks",
+        Msg::HelpMarked => "Start / Stop / Delete marked",
+        Msg::HelpMoveFocus => "Move focus",
+        Msg::HelpEdit => "Edit property or press button",
+        Msg::HelpPause => "Pause or resume the scan",
+        Msg::FieldName => "Name",
+        Msg::FieldGroup => "Group",
+        Msg::FieldTemplate => "Template",
+        Msg::FieldUri => "URI",
+        Msg::FieldThreads => "Threads",
+        Msg::FieldRecursion => "Recursion depth",
+        Msg::FieldTimeout => "Max timeout",
+        Msg::FieldRateLimit => "Rate limit (requests/s)",
+        Msg::FieldMethod => "HTTP method",
+        Msg::FieldStatusCodes => "Status codes (!code to drop)",
+        Msg::FieldWordlist => "Wordlist path",
+        Msg::FieldProxy => "Proxy URL",
+        Msg::FieldResumeFile => "Resume from file",
+        Msg::FieldAutoExport => "Auto-export directory",
+        Msg::Run => "Run",
+        Msg::Logs => "Logs",
+        Msg::ResultsStaticHidden => "Results ({hidden} static hidden)",
+        Msg::CurrentlyRequesting => "Currently requesting",
+        Msg::Arguments => "Arguments",
+        Msg::RecursionProgress => "Current recursion progress",
+        Msg::TotalProgress => "Total progress",
+        Msg::Eta => "ETA {eta}",
+        Msg::RateLimited => "rate limited",
+        Msg::Throttled => "throttled",
+        Msg::WarmingUp => "warming up",
+        Msg::Paused => "paused",
+        Msg::Exported => "Exported {count} results to {path}",
+        Msg::ExportFailed => "Can't export to {path}: {err}",
+        Msg::Dumped => "Wrote plain-text state to {path}",
+        Msg::NoUrlInClipboard => "Clipboard doesn't contain an HTTP(S) URL",
+        Msg::Finished => {
+            "Finished in {duration}: {requests} requests, {errors} errors, {hits} hits"
+        }
+        Msg::WafDetected => "WAF detected: {vendor}",
+        Msg::StackDetected => "Stack detected: {stack}",
+        Msg::HeaderFindings => "Interesting headers: {count}",
+        Msg::PartialDirs => "Partially scanned directories (time budget spent): {count}",
+        Msg::Latency => "Latency: {percentiles}",
+        Msg::AverageLatency => "Average latency: {latency}",
+        Msg::Aborted => "Aborted: {reason}",
+        Msg::ActiveThreads => "{threads} threads",
+        Msg::BodiesCaptured => {
+            "Bodies captured: {used} of {quota} ({full} full, {sampled} sampled, {skipped} skipped)"
+        }
+        Msg::HitsSpilled => "Hits spilled to disk: {spilled} ({kept} kept in memory)",
+        Msg::StaticHidden => "Static assets hidden: {count}",
+        Msg::Lines => "Lines",
+        Msg::UniqueWords => "Unique words",
+        Msg::Duplicates => "Duplicates",
+        Msg::BlankLines => "Blank lines",
+        Msg::InvalidUtf8Lines => "Invalid UTF-8 lines",
+        Msg::LongestWord => "Longest word",
+        Msg::ModeFlat => "flat",
+        Msg::ModeRecursive => "recursive ({depth})",
+        Msg::Stage => "stage {number}/{count}: {name}",
+        Msg::AllResultsTitle => "All results ({count})",
+        Msg::CompareTitle => {
+            "{left} / {right}: {common} common, {changed} changed, {only_left} / {only_right} unique"
+        }
+        Msg::CompareNeedsTwo => "Mark exactly two workers with <Space> to compare them",
+        Msg::HelpCompare => "Compare the two marked workers side by side",
+        Msg::HelpAllResults => "Show / hide results of all workers, <e> exports them",
+        Msg::HelpScrollResults => "Move through all results",
+        Msg::ColumnWorker => "Worker",
+        Msg::ColumnStatus => "Status",
+        Msg::ColumnSize => "Size",
+        Msg::ColumnUrl => "URL",
+        Msg::ColumnTriage => "Triage",
+        Msg::TriageInteresting => "interesting",
+        Msg::TriageFalsePositive => "false positive",
+        Msg::TriageDone => "done",
+        Msg::HelpTriage => "Mark result as interesting / false positive / done",
+        Msg::TerminalTooSmall => {
+            "Terminal too small: {width}×{height}, need {min_width}×{min_height}"
+        }
+    }
+}
+
+fn ru(msg: Msg) -> &'static str {
+    match msg {
+        Msg::WorkersTitle => "Воркеры",
+        Msg::WorkersMarkedTitle => "Воркеры (отмечено: {marked})",
+        Msg::InfoTitle => "Сведения",
+        Msg::HelpTitle => "Справка",
+        Msg::ErrorTitle => "Ошибка",
+        Msg::Help => "Справка",
+        Msg::Group => "Группа",
+        Msg::Workers => "Воркеры",
+        Msg::Waiting => "Ожидают",
+        Msg::Running => "Выполняются",
+        Msg::Done => "Завершены",
+        Msg::Failed => "С ошибкой",
+        Msg::Results => "Результаты",
+        Msg::HelpSwitchTabs => "Переключить вкладку",
+        Msg::HelpAddWorker => "Добавить воркер",
+        Msg::HelpAddFromClipboard => "Добавить воркер для URL из буфера обмена",
+        Msg::HelpDeleteWorker => "Удалить воркер",
+        Msg::HelpStartStop => "Запустить ожидающий воркер, открыть остальные",
+        Msg::HelpCollapse => "Свернуть/развернуть группу",
+        Msg::HelpStartGroup => "Запустить все ожидающие воркеры группы",
+        Msg::HelpExportGroup => "Экспортировать результаты группы",
+        Msg::HelpDump => "Сохранить воркер или группу как текст",
+        Msg::HelpTemplate => "Добавить воркер из шаблона",
+        Msg::HelpFilter => "Фильтр воркеров по имени или хосту",
+        Msg::NoTemplates => "Нет шаблонов в {dir}",
+        Msg::TemplatesTitle => "Шаблоны",
+        Msg::HelpMark => "Отметить воркер или группу",
+        Msg::HelpClearMarks => "Снять отметки",
+        Msg::HelpMarked => "Запустить / остановить / удалить отмеченные",
+        Msg::HelpMoveFocus => "Переместить фокус",
+        Msg::HelpEdit => "Изменить поле или нажать кнопку",
+        Msg::HelpPause => "Приостановить или продолжить сканирование",
+        Msg::FieldName => "Имя",
+        Msg::FieldGroup => "Группа",
+        Msg::FieldTemplate => "Шаблон",
+        Msg::FieldUri => "URI",
+        Msg::FieldThreads => "Потоки",
+        Msg::FieldRecursion => "Глубина рекурсии",
+        Msg::FieldTimeout => "Макс. таймаут",
+        Msg::FieldRateLimit => "Лимит запросов в секунду",
+        Msg::FieldMethod => "HTTP-метод",
+        Msg::FieldStatusCodes => "Коды ответа (!код — скрыть)",
+        Msg::FieldWordlist => "Путь к словарю",
+        Msg::FieldProxy => "URL прокси",
+        Msg::FieldResumeFile => "Продолжить из файла",
+        Msg::FieldAutoExport => "Каталог автоэкспорта",
+        Msg::Run => "Запустить",
+        Msg::Logs => "Журнал",
+        Msg::ResultsStaticHidden => "Результаты (скрыто статики: {hidden})",
+        Msg::CurrentlyRequesting => "Текущий запрос",
+        Msg::Arguments => "Параметры",
+        Msg::RecursionProgress => "Прогресс текущего уровня рекурсии",
+        Msg::TotalProgress => "Общий прогресс",
+        Msg::Eta => "осталось {eta}",
+        Msg::RateLimited => "ограничение скорости",
+        Msg::Throttled => "замедлено",
+        Msg::WarmingUp => "разгон",
+        Msg::Paused => "пауза",
+        Msg::Exported => "Экспортировано результатов: {count}, файл {path}",
+        Msg::ExportFailed => "Не удалось экспортировать в {path}: {err}",
+        Msg::Dumped => "Состояние сохранено в {path}",
+        Msg::NoUrlInClipboard => "В буфере обмена нет HTTP(S) URL",
+        Msg::Finished => {
+            "Завершено за {duration}: запросов {requests}, ошибок {errors}, находок {hits}"
+        }
+        Msg::WafDetected => "Обнаружен WAF: {vendor}",
+        Msg::StackDetected => "Обнаружен стек: {stack}",
+        Msg::HeaderFindings => "Интересные заголовки: {count}",
+        Msg::PartialDirs => "Частично просканированные директории (время вышло): {count}",
+        Msg::Latency => "Время ответа: {percentiles}",
+        Msg::AverageLatency => "Среднее время ответа: {latency}",
+        Msg::Aborted => "Прервано: {reason}",
+        Msg::ActiveThreads => "потоков: {threads}",
+        Msg::BodiesCaptured => {
+            "Сохранено тел ответов: {used} из {quota} (полностью {full}, частично {sampled}, пропущено {skipped})"
+        }
+        Msg::HitsSpilled => "Находок выгружено на диск: {spilled} (в памяти {kept})",
+        Msg::StaticHidden => "Скрыто статических файлов: {count}",
+        Msg::Lines => "Строк",
+        Msg::UniqueWords => "Уникальных слов",
+        Msg::Duplicates => "Дубликатов",
+        Msg::BlankLines => "Пустых строк",
+        Msg::InvalidUtf8Lines => "Строк с неверным UTF-8",
+        Msg::LongestWord => "Самое длинное слово",
+        Msg::ModeFlat => "без рекурсии",
+        Msg::ModeRecursive => "рекурсия ({depth})",
+        Msg::Stage => "этап {number}/{count}: {name}",
+        Msg::AllResultsTitle => "Все результаты ({count})",
+        Msg::CompareTitle => {
+            "{left} / {right}: общих {common}, изменилось {changed}, уникальных {only_left} / {only_right}"
+        }
+        Msg::CompareNeedsTwo => "Отметьте ровно два воркера клавишей <Space>, чтобы сравнить их",
+        Msg::HelpCompare => "Сравнить два отмеченных воркера бок о бок",
+        Msg::HelpAllResults => "Показать / скрыть результаты всех воркеров, <e> экспортирует их",
+        Msg::HelpScrollResults => "Перемещаться по всем результатам",
+        Msg::ColumnWorker => "Воркер",
+        Msg::ColumnStatus => "Статус",
+        Msg::ColumnSize => "Размер",
+        Msg::ColumnUrl => "URL",
+        Msg::ColumnTriage => "Разбор",
+        Msg::TriageInteresting => "интересно",
+        Msg::TriageFalsePositive => "ложное срабатывание",
+        Msg::TriageDone => "готово",
+        Msg::HelpTriage => "Отметить результат как интересный / ложный / готовый",
+        Msg::TerminalTooSmall => {
+            "Терминал слишком мал: {width}×{height}, нужно {min_width}×{min_height}"
+        }
+    }
+}
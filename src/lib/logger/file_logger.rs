@@ -1,7 +1,10 @@
 use super::traits::LogLevel;
 use anyhow::Result;
 use chrono::Local;
-use std::{fs::File, io::Write};
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+};
 
 use crate::lib::logger::traits::Logger;
 
@@ -11,8 +14,15 @@ pub struct FileLogger {
 }
 
 impl FileLogger {
-    pub fn new(path: String) -> Result<Self> {
-        let file = File::create(path)?;
+    /// Opens the log at `path`, adding to it when `append` rather than
+    /// starting it over.
+    pub fn new(path: String, append: bool) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(path)?;
         Ok(FileLogger { file: Some(file) })
     }
 }
@@ -24,12 +34,7 @@ impl Logger for FileLogger {
 
             str += &Local::now().format("[%H:%M:%S] ").to_string();
 
-            str += match level {
-                LogLevel::INFO => "[INFO] ",
-                LogLevel::WARN => "[WARN] ",
-                LogLevel::ERROR => "[ERROR] ",
-                LogLevel::CRITICAL => "[CRITICAL] ",
-            };
+            str += &format!("[{level}] ");
 
             str += &msg;
             str += "\n";
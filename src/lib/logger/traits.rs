@@ -1,7 +1,8 @@
-use std::sync::Mutex;
+use std::{fmt, sync::Mutex};
 
 use crate::lib::logger::file_logger::FileLogger;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum LogLevel {
     INFO,
     WARN,
@@ -9,6 +10,18 @@ pub enum LogLevel {
     CRITICAL,
 }
 
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let str = match self {
+            LogLevel::INFO => "INFO",
+            LogLevel::WARN => "WARN",
+            LogLevel::ERROR => "ERROR",
+            LogLevel::CRITICAL => "CRITICAL",
+        };
+        f.write_str(str)
+    }
+}
+
 #[derive(Debug)]
 pub enum WorkerLogger {
     NullLogger(NullLogger),
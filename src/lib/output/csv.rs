@@ -0,0 +1,91 @@
+use std::{
+    borrow::Cow,
+    fs::File,
+    io::{BufWriter, Write},
+};
+
+use anyhow::Result;
+
+use crate::lib::{
+    output::{self, OutputSink},
+    report::ScanMetadata,
+    worker::hit::HitResult,
+};
+
+pub(crate) const HEADER: &str = "url,status,title,content_type,size,captures";
+
+/// Hits as RFC 4180 CSV with a header row, preceded by the scan metadata
+/// as `#` comment lines.
+pub struct CsvSink {
+    writer: BufWriter<File>,
+    header: bool,
+}
+
+impl CsvSink {
+    pub fn new(path: &str) -> std::io::Result<Self> {
+        Ok(Self {
+            writer: BufWriter::new(output::open_file(path)?),
+            header: false,
+        })
+    }
+
+    fn header(&mut self) -> std::io::Result<()> {
+        if !self.header {
+            self.header = true;
+            writeln!(self.writer, "{HEADER}")?;
+        }
+        Ok(())
+    }
+}
+
+fn escape(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// Writes `hit` as a row matching [`HEADER`], its captures joined as
+/// `name=value` pairs separated by `;`.
+pub(crate) fn write_row(writer: &mut impl Write, hit: &HitResult) -> std::io::Result<()> {
+    let captures = hit
+        .captures
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>()
+        .join(";");
+    writeln!(
+        writer,
+        "{},{},{},{},{},{}",
+        escape(&hit.url),
+        hit.status,
+        escape(hit.title.as_deref().unwrap_or_default()),
+        escape(hit.content_type.as_deref().unwrap_or_default()),
+        hit.size.map(|size| size.to_string()).unwrap_or_default(),
+        escape(&captures),
+    )
+}
+
+impl OutputSink for CsvSink {
+    fn on_start(&mut self, metadata: &ScanMetadata) -> Result<()> {
+        // A resumed scan adds rows under the header written before.
+        if output::start_file(self.writer.get_mut(), metadata.resumed)? {
+            self.header = true;
+        }
+        if !self.header {
+            metadata.write_comments(&mut self.writer)?;
+        }
+        Ok(self.header()?)
+    }
+
+    fn on_hit(&mut self, hit: &HitResult) -> Result<()> {
+        self.header()?;
+        Ok(write_row(&mut self.writer, hit)?)
+    }
+
+    fn on_finish(&mut self) -> Result<()> {
+        self.header()?;
+        Ok(self.writer.flush()?)
+    }
+}
@@ -0,0 +1,58 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+};
+
+use anyhow::Result;
+use chrono::Local;
+
+use crate::lib::{
+    logger::traits::LogLevel,
+    output::{self, OutputSink},
+    report::ScanMetadata,
+    worker::hit::HitResult,
+};
+
+/// Human-readable log of hits and warnings, formatted like the `-o` log file.
+pub struct FileSink {
+    writer: BufWriter<File>,
+}
+
+impl FileSink {
+    pub fn new(path: &str) -> std::io::Result<Self> {
+        Ok(Self {
+            writer: BufWriter::new(output::open_file(path)?),
+        })
+    }
+
+    fn line(&mut self, level: LogLevel, msg: &str) -> Result<()> {
+        writeln!(
+            self.writer,
+            "{}[{level}] {msg}",
+            Local::now().format("[%H:%M:%S] ")
+        )?;
+        Ok(())
+    }
+}
+
+impl OutputSink for FileSink {
+    fn on_start(&mut self, metadata: &ScanMetadata) -> Result<()> {
+        output::start_file(self.writer.get_mut(), metadata.resumed)?;
+        Ok(metadata.write_comments(&mut self.writer)?)
+    }
+
+    fn on_hit(&mut self, hit: &HitResult) -> Result<()> {
+        self.line(LogLevel::INFO, &hit.to_string())
+    }
+
+    fn on_log(&mut self, level: LogLevel, msg: &str) -> Result<()> {
+        if level == LogLevel::INFO {
+            return Ok(());
+        }
+        self.line(level, msg)
+    }
+
+    fn on_finish(&mut self) -> Result<()> {
+        Ok(self.writer.flush()?)
+    }
+}
@@ -0,0 +1,104 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+};
+
+use anyhow::Result;
+use chrono::Local;
+use serde::Serialize;
+
+use crate::lib::{
+    logger::traits::LogLevel,
+    output::{self, OutputSink},
+    report::ScanMetadata,
+    worker::{
+        canary::CanaryAlert, headers::HeaderFinding, hit::HitResult, latency::LatencyHistogram,
+        tls::TlsInfo,
+    },
+};
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Record<'a> {
+    Metadata(&'a ScanMetadata),
+    Hit(&'a HitResult),
+    Variant { url: &'a str, variant: &'a str },
+    HeaderFinding(&'a HeaderFinding),
+    Tls(&'a TlsInfo),
+    Canary(&'a CanaryAlert),
+    Log { level: String, message: &'a str },
+    Latency(&'a LatencyHistogram),
+}
+
+/// One JSON object per line for every hit, folded variant, header finding,
+/// log message and the latencies of the scan, between a metadata record and its copy completed with the end
+/// time.
+pub struct JsonlSink {
+    writer: BufWriter<File>,
+    metadata: Option<ScanMetadata>,
+}
+
+impl JsonlSink {
+    pub fn new(path: &str) -> std::io::Result<Self> {
+        Ok(Self {
+            writer: BufWriter::new(output::open_file(path)?),
+            metadata: None,
+        })
+    }
+
+    fn write(&mut self, record: Record) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, &record)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+impl OutputSink for JsonlSink {
+    fn on_start(&mut self, metadata: &ScanMetadata) -> Result<()> {
+        output::start_file(self.writer.get_mut(), metadata.resumed)?;
+        self.metadata = Some(metadata.clone());
+        self.write(Record::Metadata(metadata))
+    }
+
+    fn on_hit(&mut self, hit: &HitResult) -> Result<()> {
+        self.write(Record::Hit(hit))
+    }
+
+    fn on_variant(&mut self, url: &str, variant: &str) -> Result<()> {
+        self.write(Record::Variant { url, variant })
+    }
+
+    fn on_header_finding(&mut self, finding: &HeaderFinding) -> Result<()> {
+        self.write(Record::HeaderFinding(finding))
+    }
+
+    fn on_tls(&mut self, tls: &TlsInfo) -> Result<()> {
+        if let Some(metadata) = self.metadata.as_mut() {
+            metadata.add_tls(tls.clone());
+        }
+        self.write(Record::Tls(tls))
+    }
+
+    fn on_canary(&mut self, alert: &CanaryAlert) -> Result<()> {
+        self.write(Record::Canary(alert))
+    }
+
+    fn on_log(&mut self, level: LogLevel, message: &str) -> Result<()> {
+        self.write(Record::Log {
+            level: level.to_string(),
+            message,
+        })
+    }
+
+    fn on_latency(&mut self, latency: &LatencyHistogram) -> Result<()> {
+        self.write(Record::Latency(latency))
+    }
+
+    fn on_finish(&mut self) -> Result<()> {
+        if let Some(mut metadata) = self.metadata.take() {
+            metadata.finished = Some(Local::now());
+            self.write(Record::Metadata(&metadata))?;
+        }
+        Ok(self.writer.flush()?)
+    }
+}
@@ -0,0 +1,206 @@
+pub mod csv;
+pub mod file;
+pub mod jsonl;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod stats;
+pub mod stream;
+pub mod webhook;
+
+use std::{
+    fmt,
+    fs::{File, OpenOptions},
+    io::{self, Seek, SeekFrom},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Result;
+use thiserror::Error;
+
+use crate::lib::{
+    logger::traits::LogLevel,
+    report::ScanMetadata,
+    worker::{
+        canary::CanaryAlert,
+        headers::HeaderFinding,
+        hit::HitResult,
+        latency::LatencyHistogram,
+        messages::{ProgressChangeMessage, ProgressMessage, WorkerMessage},
+        tls::TlsInfo,
+    },
+};
+
+/// Destination for scan results, fed directly by the worker.
+///
+/// Adding a format only takes a new implementation registered with
+/// [`WorkerBuilder::output_sink`](crate::lib::worker::builder::WorkerBuilder::output_sink).
+pub trait OutputSink: Send {
+    /// Called before any hit with what the scan is about to do.
+    fn on_start(&mut self, _metadata: &ScanMetadata) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_hit(&mut self, hit: &HitResult) -> Result<()>;
+
+    /// A word of the wordlist was requested.
+    fn on_request(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// `variant` answered like the hit at `url` and was folded into it.
+    fn on_variant(&mut self, _url: &str, _variant: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_header_finding(&mut self, _finding: &HeaderFinding) -> Result<()> {
+        Ok(())
+    }
+
+    /// Handshake details of a host, once per host.
+    fn on_tls(&mut self, _tls: &TlsInfo) -> Result<()> {
+        Ok(())
+    }
+
+    /// The canary's answer changed notably, or is back to normal.
+    fn on_canary(&mut self, _alert: &CanaryAlert) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_log(&mut self, _level: LogLevel, _msg: &str) -> Result<()> {
+        Ok(())
+    }
+
+    /// Latencies of the whole scan, once before [`OutputSink::on_finish`].
+    fn on_latency(&mut self, _latency: &LatencyHistogram) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called once after the scan; flush and close here.
+    fn on_finish(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Error, Debug, Clone)]
+pub enum OutputError {
+    #[error("Invalid output spec {0} (expected KIND=TARGET)")]
+    InvalidSpec(String),
+
+    #[error("Unknown output kind: {0}")]
+    UnknownKind(String),
+
+    #[error("Can't open output {0}: {1}")]
+    Open(String, String),
+}
+
+/// Sinks registered on a worker; clones share the same sinks.
+#[derive(Clone, Default)]
+pub struct OutputSinks(Vec<Arc<Mutex<dyn OutputSink>>>);
+
+impl fmt::Debug for OutputSinks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OutputSinks({})", self.0.len())
+    }
+}
+
+impl OutputSinks {
+    pub fn push(&mut self, sink: impl OutputSink + 'static) {
+        self.0.push(Arc::new(Mutex::new(sink)));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Passes hits, requests, header findings, canary alerts, logs and
+    /// latencies to every sink, collecting their errors.
+    pub fn dispatch(&self, msg: &WorkerMessage) -> Vec<anyhow::Error> {
+        self.each(|sink| match msg {
+            WorkerMessage::Started(metadata) => sink.on_start(metadata),
+            WorkerMessage::Hit(hit) => sink.on_hit(hit),
+            WorkerMessage::Progress(ProgressMessage::Total(ProgressChangeMessage::Advance)) => {
+                sink.on_request()
+            }
+            WorkerMessage::Progress(ProgressMessage::Total(ProgressChangeMessage::AdvanceBy(
+                steps,
+            ))) => (0..*steps).try_for_each(|_| sink.on_request()),
+            WorkerMessage::Variant { url, variant } => sink.on_variant(url, variant),
+            WorkerMessage::HeaderFinding(finding) => sink.on_header_finding(finding),
+            WorkerMessage::Tls(tls) => sink.on_tls(tls),
+            WorkerMessage::Canary(alert) => sink.on_canary(alert),
+            WorkerMessage::Log(level, text) => sink.on_log(*level, text),
+            WorkerMessage::Latency(latency) => sink.on_latency(latency),
+            _ => Ok(()),
+        })
+    }
+
+    pub fn finish(&self) -> Vec<anyhow::Error> {
+        self.each(|sink| sink.on_finish())
+    }
+
+    fn each(&self, mut f: impl FnMut(&mut dyn OutputSink) -> Result<()>) -> Vec<anyhow::Error> {
+        self.0
+            .iter()
+            .filter_map(|sink| f(&mut *sink.lock().unwrap()).err())
+            .collect()
+    }
+}
+
+/// Opens the file of a file sink, leaving what's in it until [`start_file`]
+/// knows whether the scan was resumed.
+pub(crate) fn open_file(path: &str) -> io::Result<File> {
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+/// Empties `file` for a new scan, or moves to its end when `resumed` so the
+/// hits found before stay. Returns whether there is anything before.
+pub(crate) fn start_file(file: &mut File, resumed: bool) -> io::Result<bool> {
+    if resumed {
+        Ok(file.seek(SeekFrom::End(0))? > 0)
+    } else {
+        file.set_len(0)?;
+        Ok(false)
+    }
+}
+
+/// Opens a sink from a `KIND=TARGET` spec such as `csv=hits.csv` or
+/// `webhook=https://example.com/hook`. `webhook:NAME=URL` posts hits with the
+/// payload template `NAME` of [`webhook::PayloadTemplates`].
+pub fn from_spec(spec: &str, sinks: &mut OutputSinks) -> Result<(), OutputError> {
+    let (kind, target) = spec
+        .split_once('=')
+        .filter(|(kind, target)| !kind.is_empty() && !target.is_empty())
+        .ok_or_else(|| OutputError::InvalidSpec(spec.to_string()))?;
+    let open_err = |err: &dyn fmt::Display| OutputError::Open(target.to_string(), err.to_string());
+
+    match kind {
+        "file" => sinks.push(file::FileSink::new(target).map_err(|e| open_err(&e))?),
+        "jsonl" => sinks.push(jsonl::JsonlSink::new(target).map_err(|e| open_err(&e))?),
+        "csv" => sinks.push(csv::CsvSink::new(target).map_err(|e| open_err(&e))?),
+        #[cfg(feature = "sqlite")]
+        "sqlite" => sinks.push(sqlite::SqliteSink::new(target).map_err(|e| open_err(&e))?),
+        "stats" => sinks.push(stats::StatsSink::new(target)),
+        "webhook" => sinks.push(webhook::WebhookSink::new(target).map_err(|e| open_err(&e))?),
+        "stream" => sinks.push(
+            stream::StreamSink::new(target, stream::default_spill_path(target))
+                .map_err(|e| open_err(&e))?,
+        ),
+        kind if let Some(name) = kind.strip_prefix("webhook:") => {
+            let template = webhook::PayloadTemplates::load()
+                .and_then(|templates| templates.get(name))
+                .map_err(|e| open_err(&format!("{e:#}")))?;
+            sinks.push(
+                webhook::WebhookSink::new(target)
+                    .map_err(|e| open_err(&e))?
+                    .template(template),
+            );
+        }
+        kind => return Err(OutputError::UnknownKind(kind.to_string())),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::{Connection, params};
+
+use crate::lib::{
+    logger::traits::LogLevel, output::OutputSink, report::ScanMetadata, worker::hit::HitResult,
+};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS scans (
+        id INTEGER PRIMARY KEY,
+        tool TEXT NOT NULL,
+        command_line TEXT NOT NULL,
+        target TEXT NOT NULL,
+        wordlist TEXT NOT NULL,
+        wordlist_sha256 TEXT,
+        seed TEXT NOT NULL,
+        started_at TEXT NOT NULL,
+        finished_at TEXT
+    );
+    CREATE TABLE IF NOT EXISTS hits (
+        id INTEGER PRIMARY KEY,
+        found_at TEXT NOT NULL,
+        url TEXT NOT NULL,
+        status INTEGER NOT NULL,
+        title TEXT,
+        content_type TEXT,
+        size INTEGER
+    );
+    CREATE TABLE IF NOT EXISTS logs (
+        id INTEGER PRIMARY KEY,
+        logged_at TEXT NOT NULL,
+        level TEXT NOT NULL,
+        message TEXT NOT NULL
+    );
+";
+
+/// Appends scans, hits and warnings to a SQLite database, creating the tables
+/// if needed.
+pub struct SqliteSink {
+    conn: Connection,
+    /// Row of the running scan in `scans`.
+    scan: Option<i64>,
+}
+
+impl SqliteSink {
+    pub fn new(path: &str) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn, scan: None })
+    }
+}
+
+impl OutputSink for SqliteSink {
+    fn on_start(&mut self, metadata: &ScanMetadata) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO scans (tool, command_line, target, wordlist, wordlist_sha256, seed, started_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                metadata.tool,
+                serde_json::to_string(&metadata.command_line)?,
+                metadata.target,
+                metadata.wordlist.display().to_string(),
+                metadata.wordlist_sha256,
+                metadata.seed.to_string(),
+                metadata.started.to_rfc3339(),
+            ],
+        )?;
+        self.scan = Some(self.conn.last_insert_rowid());
+        Ok(())
+    }
+
+    fn on_hit(&mut self, hit: &HitResult) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO hits (found_at, url, status, title, content_type, size)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                Utc::now().to_rfc3339(),
+                hit.url,
+                hit.status,
+                hit.title,
+                hit.content_type,
+                hit.size.map(|size| size as i64),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn on_log(&mut self, level: LogLevel, msg: &str) -> Result<()> {
+        if level == LogLevel::INFO {
+            return Ok(());
+        }
+
+        self.conn.execute(
+            "INSERT INTO logs (logged_at, level, message) VALUES (?1, ?2, ?3)",
+            params![Utc::now().to_rfc3339(), level.to_string(), msg],
+        )?;
+        Ok(())
+    }
+
+    fn on_finish(&mut self) -> Result<()> {
+        if let Some(scan) = self.scan.take() {
+            self.conn.execute(
+                "UPDATE scans SET finished_at = ?1 WHERE id = ?2",
+                params![Utc::now().to_rfc3339(), scan],
+            )?;
+        }
+        Ok(())
+    }
+}
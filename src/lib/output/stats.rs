@@ -0,0 +1,136 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Instant,
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::lib::{output::OutputSink, report::ScanMetadata, util, worker::hit::HitResult};
+
+const STATS_FILE: &str = "stats.json";
+
+/// Held while a sink adds its scan to the file, so that workers finishing
+/// together don't overwrite each other's totals.
+static FILE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Totals of every scan run with the `stats` sink. They never leave the
+/// machine; they are there to tune one's own defaults by.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsageStats {
+    pub scans: u64,
+    pub requests: u64,
+    pub hits: u64,
+    /// Time spent scanning, for the average rate.
+    pub seconds: f64,
+    /// Scans run per wordlist path.
+    pub wordlists: BTreeMap<String, u64>,
+}
+
+impl UsageStats {
+    /// `stats.json` in the [data directory](util::data_dir).
+    pub fn path() -> Option<PathBuf> {
+        Some(util::data_dir()?.join(STATS_FILE))
+    }
+
+    /// Reads the totals; a missing file means nothing was recorded yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(data) => serde_json::from_str(&data)
+                .with_context(|| format!("Invalid stats file {}", path.display())),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err).with_context(|| format!("Can't read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("Can't create {}", dir.display()))?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Can't write {}", path.display()))
+    }
+
+    /// Requests per second over all scans.
+    pub fn average_rps(&self) -> f64 {
+        if self.seconds > 0.0 {
+            self.requests as f64 / self.seconds
+        } else {
+            0.0
+        }
+    }
+
+    /// The `count` wordlists used most, most used first.
+    pub fn top_wordlists(&self, count: usize) -> Vec<(&str, u64)> {
+        let mut wordlists = self
+            .wordlists
+            .iter()
+            .map(|(path, scans)| (path.as_str(), *scans))
+            .collect::<Vec<_>>();
+        wordlists.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        wordlists.truncate(count);
+        wordlists
+    }
+}
+
+/// Adds the requests, hits and wordlist of the scan to the [`UsageStats`]
+/// file when it finishes.
+pub struct StatsSink {
+    path: PathBuf,
+    started: Option<Instant>,
+    wordlist: Option<String>,
+    requests: u64,
+    hits: u64,
+}
+
+impl StatsSink {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            started: None,
+            wordlist: None,
+            requests: 0,
+            hits: 0,
+        }
+    }
+}
+
+impl OutputSink for StatsSink {
+    fn on_start(&mut self, metadata: &ScanMetadata) -> Result<()> {
+        self.started = Some(Instant::now());
+        self.wordlist = Some(metadata.wordlist.display().to_string());
+        Ok(())
+    }
+
+    fn on_request(&mut self) -> Result<()> {
+        self.requests += 1;
+        Ok(())
+    }
+
+    fn on_hit(&mut self, _hit: &HitResult) -> Result<()> {
+        self.hits += 1;
+        Ok(())
+    }
+
+    fn on_finish(&mut self) -> Result<()> {
+        let Some(started) = self.started.take() else {
+            return Ok(());
+        };
+
+        let _lock = FILE_LOCK.lock().unwrap();
+        let mut stats = UsageStats::load(&self.path)?;
+        stats.scans += 1;
+        stats.requests += self.requests;
+        stats.hits += self.hits;
+        stats.seconds += started.elapsed().as_secs_f64();
+        if let Some(wordlist) = self.wordlist.take() {
+            *stats.wordlists.entry(wordlist).or_default() += 1;
+        }
+        stats.save(&self.path)
+    }
+}
@@ -0,0 +1,332 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    net::{TcpStream, ToSocketAddrs},
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use chrono::{Local, SecondsFormat, Utc};
+use rustls::{ClientConfig, ClientConnection, RootCertStore, StreamOwned, pki_types::ServerName};
+use serde::Serialize;
+use tempfile::NamedTempFile;
+use thiserror::Error;
+use url::{ParseError, Url};
+
+use crate::lib::{output::OutputSink, report::ScanMetadata, util, worker::hit::HitResult};
+
+/// Sinks opened by this process, so their default spill files differ.
+static SINKS: AtomicU64 = AtomicU64::new(0);
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+/// Syslog PRI for facility local0, severity informational.
+const SYSLOG_PRI: u8 = 134;
+
+#[derive(Error, Debug)]
+pub enum StreamError {
+    #[error("Can't parse stream URL: {0}")]
+    UrlParseError(#[from] ParseError),
+
+    #[error("Unsupported stream scheme: {0} (expected tcp:// or tls://)")]
+    UnsupportedScheme(String),
+
+    #[error("Stream URL has no host")]
+    NoHost,
+
+    #[error("TLS setup failed: {0}")]
+    Tls(#[from] rustls::Error),
+}
+
+struct Collector {
+    host: String,
+    port: u16,
+    tls: Option<Arc<ClientConfig>>,
+}
+
+impl Collector {
+    fn parse(url: &str) -> Result<Self, StreamError> {
+        let url = Url::parse(url)?;
+        let tls = match url.scheme() {
+            "tcp" => None,
+            "tls" => Some(Arc::new(tls_config()?)),
+            scheme => return Err(StreamError::UnsupportedScheme(scheme.to_string())),
+        };
+
+        Ok(Self {
+            host: url.host_str().ok_or(StreamError::NoHost)?.to_string(),
+            port: url.port().unwrap_or(if tls.is_some() { 6514 } else { 514 }),
+            tls,
+        })
+    }
+
+    fn connect(&self) -> io::Result<Box<dyn Write + Send>> {
+        let mut last_err = io::Error::new(io::ErrorKind::NotFound, "collector address not found");
+        let mut tcp = None;
+        for addr in (self.host.as_str(), self.port).to_socket_addrs()? {
+            match TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT) {
+                Ok(stream) => {
+                    tcp = Some(stream);
+                    break;
+                }
+                Err(err) => last_err = err,
+            }
+        }
+        let tcp = tcp.ok_or(last_err)?;
+
+        let Some(config) = &self.tls else {
+            return Ok(Box::new(tcp));
+        };
+
+        let server_name = ServerName::try_from(self.host.clone())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+        let conn = ClientConnection::new(config.clone(), server_name).map_err(io::Error::other)?;
+        Ok(Box::new(StreamOwned::new(conn, tcp)))
+    }
+}
+
+fn tls_config() -> Result<ClientConfig, rustls::Error> {
+    let roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+
+    Ok(
+        ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()?
+            .with_root_certificates(roots)
+            .with_no_client_auth(),
+    )
+}
+
+/// A spill file of its own for a sink forwarding to `url`, named after the
+/// collector and the time the sink started, in the
+/// [data directory](util::data_dir). Sinks running at once, like the workers
+/// of the interface, would otherwise replay and drop each other's lines.
+pub fn default_spill_path(url: &str) -> PathBuf {
+    let collector = Url::parse(url)
+        .ok()
+        .and_then(|url| Some(format!("{}-{}", url.host_str()?, url.port().unwrap_or(0))))
+        .unwrap_or_default()
+        .replace(
+            |c: char| !c.is_ascii_alphanumeric() && c != '.' && c != '-',
+            "_",
+        );
+    let name = format!(
+        "stream-{collector}-{}-{}-{}.spill",
+        Local::now().format("%Y%m%d-%H%M%S"),
+        std::process::id(),
+        SINKS.fetch_add(1, Ordering::Relaxed),
+    );
+    util::data_dir().map_or_else(|| PathBuf::from(&name), |dir| dir.join(&name))
+}
+
+/// Delivers lines to the collector, reconnecting with exponential backoff.
+///
+/// While the collector is down lines are appended to the spill file, which is
+/// replayed after the next successful connect. Only the lines the collector
+/// took are dropped, so a connection lost halfway neither loses nor repeats
+/// any.
+struct Forwarder {
+    collector: Collector,
+    spill_path: PathBuf,
+    conn: Option<Box<dyn Write + Send>>,
+    backoff: Duration,
+    retry_at: Instant,
+}
+
+impl Forwarder {
+    fn run(mut self, rx: Receiver<String>) {
+        let mut pending: Vec<String> = Vec::new();
+
+        loop {
+            match rx.recv_timeout(MIN_BACKOFF) {
+                Ok(line) => pending.push(line),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.retry_at = Instant::now();
+                    self.deliver(&mut pending);
+                    break;
+                }
+            }
+
+            self.deliver(&mut pending);
+        }
+    }
+
+    fn deliver(&mut self, pending: &mut Vec<String>) {
+        if self.conn.is_none() && Instant::now() >= self.retry_at {
+            self.reconnect();
+        }
+
+        if let Some(conn) = self.conn.as_mut() {
+            let (written, sent) = write_lines(conn, pending.iter().map(String::as_str));
+            pending.drain(..written);
+            if sent.is_err() {
+                self.disconnect();
+            }
+        }
+
+        if pending.is_empty() {
+            return;
+        }
+
+        if let Some(dir) = self.spill_path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let spilled = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.spill_path)
+            .and_then(|mut file| {
+                pending
+                    .iter()
+                    .try_for_each(|line| file.write_all(line.as_bytes()))
+            });
+
+        if spilled.is_ok() {
+            pending.clear();
+        }
+    }
+
+    fn reconnect(&mut self) {
+        let Ok(mut conn) = self.collector.connect() else {
+            self.disconnect();
+            return;
+        };
+
+        let spill = fs::read_to_string(&self.spill_path).unwrap_or_default();
+        let (written, sent) = write_lines(&mut conn, spill.split_inclusive('\n'));
+        if written > 0 {
+            let rest: String = spill.split_inclusive('\n').skip(written).collect();
+            let _ = replace_spill(&self.spill_path, &rest);
+        }
+
+        match sent {
+            Ok(()) => {
+                self.conn = Some(conn);
+                self.backoff = MIN_BACKOFF;
+            }
+            Err(_) => self.disconnect(),
+        }
+    }
+
+    fn disconnect(&mut self) {
+        self.conn = None;
+        self.retry_at = Instant::now() + self.backoff;
+        self.backoff = (self.backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+/// Writes `lines` to `conn` and flushes it, returning how many were written
+/// before any error. Those are taken to be delivered, as nothing tells how
+/// much of a failed write got through.
+fn write_lines<'a>(
+    conn: &mut dyn Write,
+    lines: impl IntoIterator<Item = &'a str>,
+) -> (usize, io::Result<()>) {
+    let mut written = 0;
+    for line in lines {
+        if let Err(err) = conn.write_all(line.as_bytes()) {
+            return (written, Err(err));
+        }
+        written += 1;
+    }
+    (written, conn.flush())
+}
+
+/// Replaces the spill file with `rest`, the lines not replayed yet, through a
+/// temporary file renamed over it so a crash keeps either the old or the new
+/// contents.
+fn replace_spill(path: &Path, rest: &str) -> io::Result<()> {
+    if rest.is_empty() {
+        return fs::remove_file(path);
+    }
+
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty());
+    let mut file = NamedTempFile::new_in(dir.unwrap_or(Path::new(".")))?;
+    file.write_all(rest.as_bytes())?;
+    file.persist(path)?;
+    Ok(())
+}
+
+/// Streams the scan metadata and hits to a remote collector as RFC 5424
+/// syslog lines carrying JSON.
+pub struct StreamSink {
+    sender: Option<Sender<String>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl StreamSink {
+    /// Starts forwarding to `url` (`tcp://host:port` or `tls://host:port`).
+    pub fn new(url: &str, spill_path: PathBuf) -> Result<Self, StreamError> {
+        let forwarder = Forwarder {
+            collector: Collector::parse(url)?,
+            spill_path,
+            conn: None,
+            backoff: MIN_BACKOFF,
+            retry_at: Instant::now(),
+        };
+
+        let (sender, rx) = mpsc::channel();
+        let thread = thread::spawn(move || forwarder.run(rx));
+
+        Ok(Self {
+            sender: Some(sender),
+            thread: Some(thread),
+        })
+    }
+
+    /// Delivers what's left and stops the forwarding thread.
+    pub fn close(&mut self) {
+        self.sender.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl StreamSink {
+    /// Queues a syslog line with `msg_id` and `data` as JSON.
+    fn send(&self, msg_id: &str, data: &impl Serialize) -> anyhow::Result<()> {
+        let Some(sender) = &self.sender else {
+            return Ok(());
+        };
+
+        let line = format!(
+            "<{SYSLOG_PRI}>1 {} - yadb {} {msg_id} - {}\n",
+            Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            std::process::id(),
+            serde_json::to_string(data)?,
+        );
+        let _ = sender.send(line);
+        Ok(())
+    }
+}
+
+impl OutputSink for StreamSink {
+    fn on_start(&mut self, metadata: &ScanMetadata) -> anyhow::Result<()> {
+        self.send("metadata", metadata)
+    }
+
+    fn on_hit(&mut self, hit: &HitResult) -> anyhow::Result<()> {
+        self.send("hit", hit)
+    }
+
+    fn on_finish(&mut self) -> anyhow::Result<()> {
+        self.close();
+        Ok(())
+    }
+}
+
+impl Drop for StreamSink {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
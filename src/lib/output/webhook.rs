@@ -0,0 +1,204 @@
+use std::{collections::BTreeMap, fs, io::ErrorKind, path::PathBuf, time::Duration};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use ureq::Agent;
+use url::Url;
+
+use crate::lib::{
+    output::OutputSink,
+    report::ScanMetadata,
+    util,
+    worker::{canary::CanaryAlert, hit::HitResult},
+};
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+const PAYLOADS_FILE: &str = "webhooks.toml";
+/// Placeholders a payload template may use.
+const PLACEHOLDERS: &[&str] = &["url", "status", "severity", "worker"];
+
+/// Alerts, told apart from hits by their `type`.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Alert<'a> {
+    Canary(&'a CanaryAlert),
+}
+
+/// Bodies posted for hits instead of the hit itself, so they match what
+/// Slack, Discord or another receiver expects. Loaded from `webhooks.toml`
+/// in the [config directory](util::config_dir):
+///
+/// ```toml
+/// [templates]
+/// slack = '{"text": "[{{severity}}] {{url}} answered {{status}} ({{worker}})"}'
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PayloadTemplates {
+    pub templates: BTreeMap<String, String>,
+}
+
+impl PayloadTemplates {
+    pub fn path() -> Option<PathBuf> {
+        Some(util::config_dir()?.join(PAYLOADS_FILE))
+    }
+
+    /// Reads the templates; a missing file means there are none.
+    pub fn load() -> Result<Self> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+        match fs::read_to_string(&path) {
+            Ok(data) => toml::from_str(&data)
+                .with_context(|| format!("Invalid webhook templates {}", path.display())),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err).with_context(|| format!("Can't read {}", path.display())),
+        }
+    }
+
+    /// The template called `name`, checked to only use known placeholders and
+    /// to give valid JSON.
+    pub fn get(&self, name: &str) -> Result<String> {
+        let Some(template) = self.templates.get(name) else {
+            bail!("No webhook template named {name}");
+        };
+
+        let mut rest = template.as_str();
+        while let Some(start) = rest.find("{{") {
+            let Some(end) = rest[start..].find("}}") else {
+                bail!("Unclosed placeholder in webhook template {name}");
+            };
+            let placeholder = rest[start + 2..start + end].trim();
+            if !PLACEHOLDERS.contains(&placeholder) {
+                bail!("Unknown placeholder {{{{{placeholder}}}}} in webhook template {name}");
+            }
+            rest = &rest[start + end + 2..];
+        }
+
+        let sample = render(
+            template,
+            &[
+                ("url", "u"),
+                ("status", "200"),
+                ("severity", "info"),
+                ("worker", "w"),
+            ],
+        );
+        serde_json::from_str::<serde_json::Value>(&sample)
+            .with_context(|| format!("Webhook template {name} isn't valid JSON"))?;
+        Ok(template.clone())
+    }
+}
+
+/// Replaces the `{{name}}` placeholders of `template`, escaping the values
+/// for JSON strings.
+fn render(template: &str, values: &[(&str, &str)]) -> String {
+    let mut body = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        body.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+        let name = rest[start + 2..start + end].trim();
+        if let Some((_, value)) = values.iter().find(|(key, _)| *key == name) {
+            let quoted = serde_json::to_string(value).unwrap_or_default();
+            body.push_str(&quoted[1..quoted.len() - 1]);
+        }
+        rest = &rest[start + end + 2..];
+    }
+    body.push_str(rest);
+    body
+}
+
+/// POSTs every hit and alert as JSON to a URL.
+pub struct WebhookSink {
+    url: Url,
+    agent: Agent,
+    /// Body of hits in place of the hit's JSON.
+    template: Option<String>,
+    worker: Option<String>,
+}
+
+impl WebhookSink {
+    pub fn new(url: &str) -> Result<Self, url::ParseError> {
+        let agent = Agent::config_builder()
+            .timeout_global(Some(WEBHOOK_TIMEOUT))
+            .http_status_as_error(false)
+            .build()
+            .into();
+
+        Ok(Self {
+            url: Url::parse(url)?,
+            agent,
+            template: None,
+            worker: None,
+        })
+    }
+
+    /// Posts hits as `template` filled in; see [`PayloadTemplates`].
+    pub fn template(mut self, template: String) -> Self {
+        self.template = Some(template);
+        self
+    }
+
+    /// Name `{{worker}}` stands for, the target's host unless set.
+    pub fn worker(mut self, worker: impl Into<String>) -> Self {
+        self.worker = Some(worker.into());
+        self
+    }
+}
+
+impl WebhookSink {
+    fn post(&self, body: impl Serialize) -> Result<()> {
+        let res = self.agent.post(self.url.as_str()).send_json(body)?;
+        if !res.status().is_success() {
+            bail!("webhook {} answered {}", self.url, res.status());
+        }
+        Ok(())
+    }
+
+    fn post_templated(&self, template: &str, hit: &HitResult) -> Result<()> {
+        let body = render(
+            template,
+            &[
+                ("url", &hit.url),
+                ("status", &hit.status.to_string()),
+                ("severity", &hit.severity().to_string()),
+                ("worker", self.worker.as_deref().unwrap_or_default()),
+            ],
+        );
+        let res = self
+            .agent
+            .post(self.url.as_str())
+            .header("Content-Type", "application/json")
+            .send(body)?;
+        if !res.status().is_success() {
+            bail!("webhook {} answered {}", self.url, res.status());
+        }
+        Ok(())
+    }
+}
+
+impl OutputSink for WebhookSink {
+    fn on_start(&mut self, metadata: &ScanMetadata) -> Result<()> {
+        if self.worker.is_none() {
+            self.worker = Url::parse(&metadata.target)
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_string));
+        }
+        Ok(())
+    }
+
+    fn on_hit(&mut self, hit: &HitResult) -> Result<()> {
+        match &self.template {
+            Some(template) => self.post_templated(template, hit),
+            None => self.post(hit),
+        }
+    }
+
+    fn on_canary(&mut self, alert: &CanaryAlert) -> Result<()> {
+        self.post(Alert::Canary(alert))
+    }
+}
@@ -0,0 +1,343 @@
+use std::{
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use indicatif::{MultiProgress, ProgressBar, ProgressState, ProgressStyle};
+use url::Url;
+
+use crate::lib::{
+    logger::traits::{LogLevel, WorkerLogger},
+    progress_handler::{
+        rate::RateWindow,
+        traits::{ProgressHandler, ProgressKind},
+    },
+    report::ScanMetadata,
+    worker::{
+        canary::{CanaryAlert, CanaryChange},
+        config::HttpMethod,
+        headers::HeaderFinding,
+        hit::{HitResult, Verification},
+        scheduler::{SchedulerSnapshot, SchedulerState},
+        tls::TlsInfo,
+        waf::WafVendor,
+    },
+};
+
+/// Bars of recursion levels below the target; deeper directories share the
+/// last one.
+const MAX_LEVEL_BARS: usize = 4;
+
+/// Renders worker progress as indicatif bars, one for the total and one per
+/// recursion level being scanned, and forwards logs to a [`WorkerLogger`].
+pub struct IndicatifHandler {
+    multi_progress: MultiProgress,
+    current: ProgressBar,
+    /// Bars of the directories being scanned below the target, by depth.
+    levels: Vec<ProgressBar>,
+    total: ProgressBar,
+    logger: WorkerLogger,
+    /// Method of the scan, printed with its hits.
+    method: HttpMethod,
+    hidden_static: usize,
+    stats: StatsLine,
+    /// Rate of the total bar, also read by its ETA.
+    rate: Arc<Mutex<RateWindow>>,
+}
+
+/// Counters of the status line.
+struct StatsLine {
+    /// Printed this often when set.
+    interval: Option<Duration>,
+    last_printed: Instant,
+    /// Requests done when the line was last printed.
+    last_done: u64,
+    hits: usize,
+    errors: usize,
+}
+
+impl IndicatifHandler {
+    pub fn new(multi_progress: &MultiProgress, logger: WorkerLogger) -> Self {
+        let current = multi_progress.add(ProgressBar::no_length());
+        current.set_style(
+            ProgressStyle::with_template("{spinner:.green} {prefix:.bold.dim} {wide_msg}").unwrap(),
+        );
+
+        let rate = Arc::<Mutex<RateWindow>>::default();
+        let total = multi_progress.add(ProgressBar::no_length());
+        let bar_rate = rate.clone();
+        total.set_style(
+            ProgressStyle::with_template(
+                "[{elapsed_precise}] [{wide_bar:.cyan/blue}] {pos:>7}/{len:7} ({eta}) {prefix:.dim}",
+            )
+            .unwrap()
+            .with_key("eta", move |state: &ProgressState, w: &mut dyn Write| {
+                let eta = bar_rate
+                    .lock()
+                    .unwrap()
+                    .format_eta(state.pos(), state.len().unwrap_or_default());
+                write!(w, "{eta}").unwrap()
+            })
+            .progress_chars("#>-"),
+        );
+
+        Self {
+            multi_progress: multi_progress.clone(),
+            current,
+            levels: Vec::new(),
+            total,
+            logger,
+            method: HttpMethod::default(),
+            hidden_static: 0,
+            stats: StatsLine {
+                interval: None,
+                last_printed: Instant::now(),
+                last_done: 0,
+                hits: 0,
+                errors: 0,
+            },
+            rate,
+        }
+    }
+
+    /// Prints a status line with progress, request rate, hits, errors and
+    /// ETA every `interval`, also when the bars aren't drawn.
+    pub fn with_stats_interval(mut self, interval: Duration) -> Self {
+        self.stats.interval = Some(interval);
+        self
+    }
+
+    fn print_stats(&mut self) {
+        let Some(interval) = self.stats.interval else {
+            return;
+        };
+        if self.stats.last_printed.elapsed() < interval {
+            return;
+        }
+
+        let line = self.stats_line();
+        if self.multi_progress.is_hidden() {
+            println!("{line}");
+        } else {
+            let _ = self.multi_progress.println(line);
+        }
+    }
+
+    /// Prints the status line right away on stderr and logs it, whatever the
+    /// interval.
+    pub fn print_stats_now(&mut self) {
+        let line = self.stats_line();
+        if self.multi_progress.is_hidden() {
+            eprintln!("{line}");
+        } else {
+            let _ = self.multi_progress.println(&line);
+        }
+        self.logger.log(LogLevel::INFO, line);
+    }
+
+    /// Progress, request rate since the last line, hits, errors and ETA.
+    fn stats_line(&mut self) -> String {
+        let stats = &mut self.stats;
+        let now = Instant::now();
+        let since_last = now.duration_since(stats.last_printed);
+
+        let done = self.total.position();
+        let total = self.total.length().unwrap_or_default();
+        let percent = if total == 0 {
+            0.0
+        } else {
+            done as f64 * 100.0 / total as f64
+        };
+        let rps = done.saturating_sub(stats.last_done) as f64 / since_last.as_secs_f64();
+
+        let eta = self.rate.lock().unwrap().format_eta(done, total);
+
+        let line = format!(
+            "[stats] {percent:.1}% ({done}/{total}) | {rps:.0} req/s | {} hits | {} errors | ETA {eta}",
+            stats.hits, stats.errors
+        );
+        stats.last_printed = now;
+        stats.last_done = done;
+        line
+    }
+
+    /// Number of hits flagged as static assets and kept out of the output.
+    pub fn hidden_static(&self) -> usize {
+        self.hidden_static
+    }
+
+    /// `Current` is the bar of the deepest directory being scanned.
+    fn bar(&self, kind: ProgressKind) -> &ProgressBar {
+        match kind {
+            ProgressKind::Total => &self.total,
+            ProgressKind::Current => self.levels.last().unwrap_or(&self.current),
+        }
+    }
+}
+
+impl ProgressHandler for IndicatifHandler {
+    fn set_message(&mut self, kind: ProgressKind, msg: String) {
+        self.bar(kind).set_message(msg);
+    }
+
+    fn set_size(&mut self, kind: ProgressKind, size: usize) {
+        self.bar(kind).set_length(size.try_into().unwrap());
+    }
+
+    fn start(&mut self, kind: ProgressKind, size: usize) {
+        let bar = self.bar(kind);
+        bar.reset();
+        bar.set_length(size.try_into().unwrap());
+    }
+
+    fn advance(&mut self, kind: ProgressKind) {
+        self.advance_by(kind, 1);
+    }
+
+    fn advance_by(&mut self, kind: ProgressKind, steps: usize) {
+        self.bar(kind).inc(steps as u64);
+        if kind == ProgressKind::Total {
+            self.rate.lock().unwrap().record(self.total.position());
+            self.print_stats();
+        }
+    }
+
+    fn print(&mut self, kind: ProgressKind, msg: String) {
+        self.bar(kind).println(msg);
+    }
+
+    fn finish(&mut self, kind: ProgressKind) {
+        self.bar(kind).finish();
+    }
+
+    fn started(&mut self, metadata: ScanMetadata) {
+        self.method = metadata.method;
+        for (label, value) in metadata.fields() {
+            self.logger.log(LogLevel::INFO, format!("{label}: {value}"));
+        }
+    }
+
+    fn stage(&mut self, index: usize, count: usize, name: String) {
+        let msg = format!("Stage {}/{count}: {name}", index + 1);
+        self.total.reset();
+        self.rate.lock().unwrap().reset();
+        self.current.println(&msg);
+        self.logger.log(LogLevel::INFO, msg);
+    }
+
+    /// Drops the bars of levels deeper than `depth`, whose directories are
+    /// done, and points the bar of `depth` at the new directory.
+    fn directory(&mut self, url: String, depth: usize) {
+        let depth = depth.min(MAX_LEVEL_BARS);
+
+        while self.levels.len() > depth {
+            if let Some(bar) = self.levels.pop() {
+                bar.finish_and_clear();
+                self.multi_progress.remove(&bar);
+            }
+        }
+        while self.levels.len() < depth {
+            let bar = self
+                .multi_progress
+                .insert_before(&self.total, ProgressBar::no_length());
+            bar.set_style(
+                ProgressStyle::with_template(
+                    "  {prefix:.bold.dim} [{bar:20.cyan/blue}] {pos}/{len} {wide_msg}",
+                )
+                .unwrap()
+                .progress_chars("#>-"),
+            );
+            self.levels.push(bar);
+        }
+
+        let bar = self.bar(ProgressKind::Current);
+        bar.reset();
+        if depth > 0 {
+            let path = Url::parse(&url).map_or(url, |url| url.path().to_string());
+            bar.set_prefix(path);
+        }
+    }
+
+    fn log(&mut self, level: LogLevel, msg: String) {
+        if matches!(level, LogLevel::WARN | LogLevel::ERROR | LogLevel::CRITICAL) {
+            self.stats.errors += 1;
+        }
+        self.logger.log(level, msg);
+    }
+
+    fn hit(&mut self, hit: HitResult) {
+        if hit.static_asset {
+            self.hidden_static += 1;
+            return;
+        }
+
+        self.stats.hits += 1;
+        self.current.println(format!("{} {hit}", self.method));
+        self.logger.log(LogLevel::INFO, hit.to_string());
+    }
+
+    fn variant(&mut self, url: String, variant: String) {
+        self.logger
+            .log(LogLevel::INFO, format!("{variant} answers like {url}"));
+    }
+
+    fn concurrency(&mut self, threads: usize) {
+        self.total.set_prefix(format!("{threads} threads"));
+        self.logger
+            .log(LogLevel::INFO, format!("Concurrency: {threads} threads"));
+    }
+
+    fn waf_detected(&mut self, vendor: WafVendor) {
+        let msg = format!("WAF detected: {vendor}");
+        self.current.println(&msg);
+        self.logger.log(LogLevel::WARN, msg);
+    }
+
+    fn header_finding(&mut self, finding: HeaderFinding) {
+        self.current.println(format!("HEADER {finding}"));
+        self.logger.log(LogLevel::INFO, finding.to_string());
+    }
+
+    fn tls(&mut self, tls: TlsInfo) {
+        let level = if tls.problem.is_some() {
+            LogLevel::WARN
+        } else {
+            LogLevel::INFO
+        };
+        self.current.println(format!("TLS {tls}"));
+        self.logger.log(level, format!("TLS {tls}"));
+    }
+
+    fn canary(&mut self, alert: CanaryAlert) {
+        let level = match alert.change {
+            CanaryChange::Recovered => LogLevel::INFO,
+            _ => LogLevel::WARN,
+        };
+        self.current.println(format!("CANARY {alert}"));
+        self.logger.log(level, format!("Canary {alert}"));
+    }
+
+    fn scheduler(&mut self, snapshot: SchedulerSnapshot) {
+        let prefix = match snapshot.state {
+            SchedulerState::Running => "",
+            SchedulerState::Limited => "limited",
+            SchedulerState::Throttled => "throttled",
+            SchedulerState::WarmingUp => "warming up",
+            SchedulerState::Paused => "paused",
+            SchedulerState::Stopped => "stopping",
+        };
+        self.current.set_prefix(prefix);
+    }
+
+    fn verified(&mut self, hit: HitResult, verification: Verification) {
+        let msg = format!("{hit} ({verification})");
+        match verification {
+            Verification::Confirmed => self.logger.log(LogLevel::INFO, msg),
+            Verification::Flaky { .. } => {
+                self.current.println(format!("FLAKY {msg}"));
+                self.logger.log(LogLevel::WARN, msg);
+            }
+        }
+    }
+}
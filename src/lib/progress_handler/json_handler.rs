@@ -0,0 +1,369 @@
+use std::{
+    io::Write,
+    time::{Duration, Instant},
+};
+
+use chrono::Local;
+use serde::Serialize;
+
+use crate::lib::{
+    logger::traits::LogLevel,
+    progress_handler::traits::{ProgressHandler, ProgressKind},
+    report::{ScanMetadata, ScanSummary},
+    worker::{
+        canary::CanaryAlert,
+        capture::CaptureUsage,
+        headers::HeaderFinding,
+        hit::{HitResult, Verification},
+        latency::LatencyHistogram,
+        scheduler::SchedulerSnapshot,
+        tls::TlsInfo,
+        waf::WafVendor,
+    },
+};
+
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum JsonEvent<'a> {
+    Metadata(&'a ScanMetadata),
+    Stage {
+        index: usize,
+        count: usize,
+        name: String,
+    },
+    Directory {
+        url: String,
+        depth: usize,
+    },
+    SetMessage {
+        bar: &'a str,
+        message: String,
+    },
+    SetSize {
+        bar: &'a str,
+        size: usize,
+    },
+    Start {
+        bar: &'a str,
+        size: usize,
+    },
+    Advance {
+        bar: &'a str,
+    },
+    AdvanceBy {
+        bar: &'a str,
+        steps: usize,
+    },
+    Print {
+        bar: &'a str,
+        message: String,
+    },
+    Finish {
+        bar: &'a str,
+    },
+    Log {
+        level: String,
+        message: String,
+    },
+    Hit(HitResult),
+    WafDetected {
+        vendor: WafVendor,
+    },
+    HeaderFinding(HeaderFinding),
+    Tls(&'a TlsInfo),
+    Canary(CanaryAlert),
+    Verified {
+        hit: HitResult,
+        verification: Verification,
+    },
+    Variant {
+        url: String,
+        variant: String,
+    },
+    Progress(&'a Stats),
+    Scheduler(SchedulerSnapshot),
+    Concurrency {
+        threads: usize,
+    },
+    Capture(CaptureUsage),
+    Latency(&'a LatencyHistogram),
+    Aborted {
+        reason: String,
+    },
+    Finished(&'a ScanSummary),
+    Failed {
+        reason: String,
+    },
+}
+
+/// Counters reported by periodic `progress` events.
+#[derive(Debug, Default, Serialize)]
+struct Stats {
+    current_done: usize,
+    current_total: usize,
+    total_done: usize,
+    total: usize,
+    hits: usize,
+    errors: usize,
+    elapsed_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    capture: Option<CaptureUsage>,
+}
+
+/// Writes every worker message as a single JSON line.
+///
+/// With [`JsonHandler::with_interval`] bar updates are folded into a `progress`
+/// event written at most once per interval, which suits progress displays.
+pub struct JsonHandler<W: Write> {
+    writer: W,
+    interval: Option<Duration>,
+    started: Instant,
+    last_progress: Option<Instant>,
+    stats: Stats,
+    /// Written again with the end time once the scan is over.
+    metadata: Option<ScanMetadata>,
+}
+
+impl<W: Write> JsonHandler<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            interval: None,
+            started: Instant::now(),
+            last_progress: None,
+            stats: Stats::default(),
+            metadata: None,
+        }
+    }
+
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    fn write(&mut self, event: JsonEvent) {
+        if let Ok(line) = serde_json::to_string(&event) {
+            let _ = writeln!(self.writer, "{line}");
+        }
+    }
+
+    /// Writes a `progress` event unless one was written less than an interval ago.
+    fn progress(&mut self, force: bool) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+
+        let now = Instant::now();
+        if !force
+            && self
+                .last_progress
+                .is_some_and(|last| now.duration_since(last) < interval)
+        {
+            return;
+        }
+
+        self.last_progress = Some(now);
+        self.stats.elapsed_ms = now.duration_since(self.started).as_millis();
+
+        if let Ok(line) = serde_json::to_string(&JsonEvent::Progress(&self.stats)) {
+            let _ = writeln!(self.writer, "{line}");
+            let _ = self.writer.flush();
+        }
+    }
+}
+
+fn bar_name(kind: ProgressKind) -> &'static str {
+    match kind {
+        ProgressKind::Total => "total",
+        ProgressKind::Current => "current",
+    }
+}
+
+impl<W: Write> ProgressHandler for JsonHandler<W> {
+    fn started(&mut self, metadata: ScanMetadata) {
+        self.write(JsonEvent::Metadata(&metadata));
+        self.metadata = Some(metadata);
+    }
+
+    fn stage(&mut self, index: usize, count: usize, name: String) {
+        self.stats.total_done = 0;
+        self.write(JsonEvent::Stage { index, count, name });
+    }
+
+    fn directory(&mut self, url: String, depth: usize) {
+        self.write(JsonEvent::Directory { url, depth });
+    }
+
+    fn set_message(&mut self, kind: ProgressKind, message: String) {
+        if self.interval.is_some() {
+            return;
+        }
+
+        self.write(JsonEvent::SetMessage {
+            bar: bar_name(kind),
+            message,
+        });
+    }
+
+    fn set_size(&mut self, kind: ProgressKind, size: usize) {
+        if self.interval.is_some() {
+            match kind {
+                ProgressKind::Total => self.stats.total = size,
+                ProgressKind::Current => {
+                    self.stats.current_total = size;
+                    self.stats.current_done = 0;
+                }
+            }
+            return self.progress(false);
+        }
+
+        self.write(JsonEvent::SetSize {
+            bar: bar_name(kind),
+            size,
+        });
+    }
+
+    fn start(&mut self, kind: ProgressKind, size: usize) {
+        if self.interval.is_some() {
+            return self.set_size(kind, size);
+        }
+
+        self.write(JsonEvent::Start {
+            bar: bar_name(kind),
+            size,
+        });
+    }
+
+    fn advance(&mut self, kind: ProgressKind) {
+        if self.interval.is_some() {
+            match kind {
+                ProgressKind::Total => self.stats.total_done += 1,
+                ProgressKind::Current => self.stats.current_done += 1,
+            }
+            return self.progress(false);
+        }
+
+        self.write(JsonEvent::Advance {
+            bar: bar_name(kind),
+        });
+    }
+
+    fn advance_by(&mut self, kind: ProgressKind, steps: usize) {
+        if self.interval.is_some() {
+            match kind {
+                ProgressKind::Total => self.stats.total_done += steps,
+                ProgressKind::Current => self.stats.current_done += steps,
+            }
+            return self.progress(false);
+        }
+
+        self.write(JsonEvent::AdvanceBy {
+            bar: bar_name(kind),
+            steps,
+        });
+    }
+
+    fn print(&mut self, kind: ProgressKind, message: String) {
+        self.write(JsonEvent::Print {
+            bar: bar_name(kind),
+            message,
+        });
+    }
+
+    fn finish(&mut self, kind: ProgressKind) {
+        self.progress(true);
+        self.write(JsonEvent::Finish {
+            bar: bar_name(kind),
+        });
+        if kind == ProgressKind::Total
+            && let Some(mut metadata) = self.metadata.take()
+        {
+            metadata.finished = Some(Local::now());
+            self.write(JsonEvent::Metadata(&metadata));
+        }
+        let _ = self.writer.flush();
+    }
+
+    fn log(&mut self, level: LogLevel, message: String) {
+        if matches!(level, LogLevel::WARN | LogLevel::ERROR | LogLevel::CRITICAL) {
+            self.stats.errors += 1;
+        }
+
+        self.write(JsonEvent::Log {
+            level: level.to_string(),
+            message,
+        });
+    }
+
+    fn hit(&mut self, hit: HitResult) {
+        self.stats.hits += 1;
+        self.write(JsonEvent::Hit(hit));
+    }
+
+    fn waf_detected(&mut self, vendor: WafVendor) {
+        self.write(JsonEvent::WafDetected { vendor });
+    }
+
+    fn header_finding(&mut self, finding: HeaderFinding) {
+        self.write(JsonEvent::HeaderFinding(finding));
+    }
+
+    fn tls(&mut self, tls: TlsInfo) {
+        self.write(JsonEvent::Tls(&tls));
+        if let Some(metadata) = self.metadata.as_mut() {
+            metadata.add_tls(tls);
+        }
+    }
+
+    fn canary(&mut self, alert: CanaryAlert) {
+        self.write(JsonEvent::Canary(alert));
+    }
+
+    fn verified(&mut self, hit: HitResult, verification: Verification) {
+        self.write(JsonEvent::Verified { hit, verification });
+    }
+
+    fn variant(&mut self, url: String, variant: String) {
+        self.write(JsonEvent::Variant { url, variant });
+    }
+
+    fn scheduler(&mut self, snapshot: SchedulerSnapshot) {
+        if self.interval.is_some() {
+            return;
+        }
+
+        self.write(JsonEvent::Scheduler(snapshot));
+    }
+
+    fn concurrency(&mut self, threads: usize) {
+        self.write(JsonEvent::Concurrency { threads });
+    }
+
+    fn failed(&mut self, reason: String) {
+        self.progress(true);
+        self.write(JsonEvent::Failed { reason });
+        let _ = self.writer.flush();
+    }
+
+    fn capture(&mut self, usage: CaptureUsage) {
+        if self.interval.is_some() {
+            self.stats.capture = Some(usage);
+            return;
+        }
+
+        self.write(JsonEvent::Capture(usage));
+    }
+
+    fn latency(&mut self, latency: LatencyHistogram) {
+        self.write(JsonEvent::Latency(&latency));
+    }
+
+    fn aborted(&mut self, reason: String) {
+        self.write(JsonEvent::Aborted { reason });
+    }
+
+    fn finished(&mut self, summary: ScanSummary) {
+        self.write(JsonEvent::Finished(&summary));
+        let _ = self.writer.flush();
+    }
+}
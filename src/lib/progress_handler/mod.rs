@@ -0,0 +1,4 @@
+pub mod indicatif_handler;
+pub mod json_handler;
+pub mod rate;
+pub mod traits;
@@ -0,0 +1,84 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use crate::lib::util;
+
+/// Span of the samples the rate is computed over.
+const WINDOW: Duration = Duration::from_secs(30);
+/// Samples are kept at least this far apart.
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+/// The rate isn't trusted until the samples span this much.
+const MIN_SPAN: Duration = Duration::from_secs(2);
+
+/// Request rate over the last half minute and the time left at that pace.
+///
+/// Unlike an average since the start, it follows a target slowing down and
+/// the total growing as recursion finds directories, so estimates of long
+/// recursive scans stay realistic. Shared by the CLI and the TUI.
+#[derive(Debug, Clone, Default)]
+pub struct RateWindow {
+    /// Requests done at the time of each sample, oldest first.
+    samples: VecDeque<(Instant, u64)>,
+    /// Latest count, which may be closer to the last sample than the interval.
+    latest: Option<(Instant, u64)>,
+}
+
+impl RateWindow {
+    /// Notes that `done` requests are done by now.
+    pub fn record(&mut self, done: u64) {
+        let now = Instant::now();
+        self.latest = Some((now, done));
+        if self
+            .samples
+            .back()
+            .is_some_and(|(last, _)| now.duration_since(*last) < SAMPLE_INTERVAL)
+        {
+            return;
+        }
+        self.samples.push_back((now, done));
+
+        while self
+            .samples
+            .get(1)
+            .is_some_and(|(time, _)| now.duration_since(*time) >= WINDOW)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Starts over, e.g. when a new pipeline stage resets the progress.
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.latest = None;
+    }
+
+    /// Requests per second over the window, once it spans long enough.
+    pub fn rps(&self) -> Option<f64> {
+        let ((first, first_done), (last, last_done)) =
+            (self.samples.front()?, self.latest.as_ref()?);
+        let span = last.duration_since(*first);
+        if span < MIN_SPAN {
+            return None;
+        }
+        Some(last_done.saturating_sub(*first_done) as f64 / span.as_secs_f64())
+    }
+
+    /// Time left until `total` requests are done at the current rate.
+    pub fn eta(&self, done: u64, total: u64) -> Option<Duration> {
+        let rps = self.rps().filter(|rps| *rps > 0.0)?;
+        if total <= done {
+            return None;
+        }
+        Some(Duration::from_secs(
+            ((total - done) as f64 / rps).ceil() as u64
+        ))
+    }
+
+    /// [`RateWindow::eta`] to the second, or `-` while unknown.
+    pub fn format_eta(&self, done: u64, total: u64) -> String {
+        self.eta(done, total)
+            .map_or_else(|| "-".to_string(), util::format_duration)
+    }
+}
@@ -0,0 +1,99 @@
+use crate::lib::{
+    logger::traits::LogLevel,
+    report::{ScanMetadata, ScanSummary},
+    worker::{
+        canary::CanaryAlert,
+        capture::CaptureUsage,
+        headers::HeaderFinding,
+        hit::{HitResult, Verification},
+        latency::LatencyHistogram,
+        messages::{ProgressChangeMessage, ProgressMessage, WorkerMessage},
+        scheduler::SchedulerSnapshot,
+        tls::TlsInfo,
+        waf::WafVendor,
+    },
+};
+
+/// Which of the two progress bars a message is addressed to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProgressKind {
+    Total,
+    Current,
+}
+
+/// Consumer of [`WorkerMessage`]s.
+///
+/// Frontends implement only the callbacks they care about and feed every
+/// received message into [`ProgressHandler::handle`].
+pub trait ProgressHandler {
+    fn started(&mut self, _metadata: ScanMetadata) {}
+    fn stage(&mut self, _index: usize, _count: usize, _name: String) {}
+    fn directory(&mut self, _url: String, _depth: usize) {}
+    fn set_message(&mut self, _kind: ProgressKind, _msg: String) {}
+    fn set_size(&mut self, _kind: ProgressKind, _size: usize) {}
+    fn start(&mut self, _kind: ProgressKind, _size: usize) {}
+    fn advance(&mut self, _kind: ProgressKind) {}
+    /// `steps` calls of [`ProgressHandler::advance`] unless overridden.
+    fn advance_by(&mut self, kind: ProgressKind, steps: usize) {
+        for _ in 0..steps {
+            self.advance(kind);
+        }
+    }
+    fn print(&mut self, _kind: ProgressKind, _msg: String) {}
+    fn finish(&mut self, _kind: ProgressKind) {}
+    fn log(&mut self, _level: LogLevel, _msg: String) {}
+    fn hit(&mut self, _hit: HitResult) {}
+    fn waf_detected(&mut self, _vendor: WafVendor) {}
+    fn header_finding(&mut self, _finding: HeaderFinding) {}
+    fn tls(&mut self, _tls: TlsInfo) {}
+    fn canary(&mut self, _alert: CanaryAlert) {}
+    fn verified(&mut self, _hit: HitResult, _verification: Verification) {}
+    fn variant(&mut self, _url: String, _variant: String) {}
+    fn scheduler(&mut self, _snapshot: SchedulerSnapshot) {}
+    fn concurrency(&mut self, _threads: usize) {}
+    fn capture(&mut self, _usage: CaptureUsage) {}
+    fn latency(&mut self, _latency: LatencyHistogram) {}
+    fn aborted(&mut self, _reason: String) {}
+    fn finished(&mut self, _summary: ScanSummary) {}
+    fn failed(&mut self, _reason: String) {}
+
+    /// Decodes a worker message and calls the matching callback.
+    fn handle(&mut self, msg: WorkerMessage) {
+        match msg {
+            WorkerMessage::Started(metadata) => self.started(metadata),
+            WorkerMessage::Progress(progress_message) => {
+                let (kind, change) = match progress_message {
+                    ProgressMessage::Total(change) => (ProgressKind::Total, change),
+                    ProgressMessage::Current(change) => (ProgressKind::Current, change),
+                };
+
+                match change {
+                    ProgressChangeMessage::SetMessage(msg) => self.set_message(kind, msg),
+                    ProgressChangeMessage::SetSize(size) => self.set_size(kind, size),
+                    ProgressChangeMessage::Start(size) => self.start(kind, size),
+                    ProgressChangeMessage::Advance => self.advance(kind),
+                    ProgressChangeMessage::AdvanceBy(steps) => self.advance_by(kind, steps),
+                    ProgressChangeMessage::Print(msg) => self.print(kind, msg),
+                    ProgressChangeMessage::Finish => self.finish(kind),
+                }
+            }
+            WorkerMessage::Stage { index, count, name } => self.stage(index, count, name),
+            WorkerMessage::Directory { url, depth } => self.directory(url, depth),
+            WorkerMessage::Log(level, msg) => self.log(level, msg),
+            WorkerMessage::Hit(hit) => self.hit(hit),
+            WorkerMessage::WafDetected(vendor) => self.waf_detected(vendor),
+            WorkerMessage::HeaderFinding(finding) => self.header_finding(finding),
+            WorkerMessage::Tls(tls) => self.tls(tls),
+            WorkerMessage::Canary(alert) => self.canary(alert),
+            WorkerMessage::Verified(hit, verification) => self.verified(hit, verification),
+            WorkerMessage::Variant { url, variant } => self.variant(url, variant),
+            WorkerMessage::Scheduler(snapshot) => self.scheduler(snapshot),
+            WorkerMessage::Concurrency(threads) => self.concurrency(threads),
+            WorkerMessage::Capture(usage) => self.capture(usage),
+            WorkerMessage::Latency(latency) => self.latency(latency),
+            WorkerMessage::Aborted(reason) => self.aborted(reason),
+            WorkerMessage::Finished(summary) => self.finished(summary),
+            WorkerMessage::Failed(reason) => self.failed(reason),
+        }
+    }
+}
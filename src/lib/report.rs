@@ -0,0 +1,649 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    io::{BufRead, Write},
+    path::PathBuf,
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use clap::ValueEnum;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use url::Url;
+
+use crate::lib::{
+    output::csv,
+    util,
+    worker::{
+        candidates::Transform,
+        capture::CaptureUsage,
+        config::{Engine, HttpMethod, ScanMode, WorkerConfig},
+        headers::HeaderFinding,
+        hit::HitResult,
+        html,
+        latency::LatencyHistogram,
+        stack::Stack,
+        store::ResultStore,
+        tls::TlsInfo,
+        waf::WafVendor,
+    },
+};
+
+/// Parameters a scan ran with, echoed in its report.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanParameters {
+    pub target: String,
+    pub wordlist: PathBuf,
+    pub scan_mode: ScanMode,
+    pub method: HttpMethod,
+    pub concurrency: usize,
+    pub engine: Engine,
+    pub recursion_depth: usize,
+    pub seed: u64,
+    pub transforms: Vec<Transform>,
+}
+
+impl From<&WorkerConfig> for ScanParameters {
+    fn from(config: &WorkerConfig) -> Self {
+        Self {
+            target: config.uri.to_string(),
+            wordlist: config.wordlist_path.clone(),
+            scan_mode: config.scan_mode,
+            method: config.method,
+            concurrency: config.concurrency,
+            engine: config.engine,
+            recursion_depth: config.recursion_depth,
+            seed: config.seed,
+            transforms: config.transforms.clone(),
+        }
+    }
+}
+
+/// Where a set of findings came from; every report and output sink starts
+/// with it so results can be audited and the scan reproduced.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanMetadata {
+    /// Name and version of the scanner.
+    pub tool: String,
+    /// Arguments of the process that ran the scan.
+    pub command_line: Vec<String>,
+    pub target: String,
+    /// Method the words were requested with.
+    #[serde(default)]
+    pub method: HttpMethod,
+    pub wordlist: PathBuf,
+    /// `None` when the wordlist couldn't be read for hashing.
+    pub wordlist_sha256: Option<String>,
+    pub seed: u64,
+    #[serde(
+        serialize_with = "serialize_time",
+        deserialize_with = "deserialize_time"
+    )]
+    pub started: DateTime<Local>,
+    /// Filled in once the scan is over.
+    #[serde(
+        default,
+        serialize_with = "serialize_optional_time",
+        deserialize_with = "deserialize_optional_time"
+    )]
+    pub finished: Option<DateTime<Local>>,
+    /// Handshake details of every HTTPS host the scan talked to, filled in
+    /// as hosts are first seen.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tls: Vec<TlsInfo>,
+    /// The scan went on from a checkpoint, so file sinks append to what the
+    /// runs before it wrote.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub resumed: bool,
+}
+
+impl ScanMetadata {
+    pub fn new(config: &WorkerConfig, started: DateTime<Local>) -> Self {
+        Self {
+            tool: format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+            command_line: env::args().collect(),
+            target: config.uri.to_string(),
+            method: config.method,
+            wordlist: config.wordlist_path.clone(),
+            wordlist_sha256: util::sha256_file(&config.wordlist_path).ok(),
+            seed: config.seed,
+            started,
+            finished: None,
+            tls: Vec::new(),
+            resumed: config.resume.is_some(),
+        }
+    }
+
+    /// Labels and values in the order writers show them.
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = vec![
+            ("Tool", self.tool.clone()),
+            ("Command line", self.command_line.join(" ")),
+            ("Target", self.target.clone()),
+            ("Method", self.method.to_string()),
+            ("Wordlist", self.wordlist.display().to_string()),
+            (
+                "Wordlist SHA-256",
+                self.wordlist_sha256
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+            ),
+            ("Seed", self.seed.to_string()),
+            ("Started", self.started.to_rfc3339()),
+            (
+                "Finished",
+                self.finished
+                    .map_or_else(|| "-".to_string(), |finished| finished.to_rfc3339()),
+            ),
+        ];
+        fields.extend(self.tls.iter().map(|tls| ("TLS", tls.to_string())));
+        fields
+    }
+
+    /// Adds the details of a host unless it is already known.
+    pub fn add_tls(&mut self, tls: TlsInfo) {
+        if !self
+            .tls
+            .iter()
+            .any(|known| known.host == tls.host && known.port == tls.port)
+        {
+            self.tls.push(tls);
+        }
+    }
+
+    /// Writes the fields as `# Label: value` lines, for line-based formats.
+    pub fn write_comments(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        for (label, value) in self.fields() {
+            writeln!(writer, "# {label}: {}", value.replace(['\n', '\r'], " "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Totals of a finished scan, sent in
+/// [`WorkerMessage::Finished`](crate::lib::worker::messages::WorkerMessage::Finished).
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanSummary {
+    /// Requests sent, verification requests included.
+    pub requests: usize,
+    pub hits: usize,
+    /// Requests that failed or couldn't be made.
+    pub errors: usize,
+    #[serde(rename = "elapsed_secs", serialize_with = "serialize_duration")]
+    pub elapsed: Duration,
+    /// Mean latency of the answered requests, if any were.
+    #[serde(
+        rename = "average_latency_secs",
+        serialize_with = "serialize_optional_duration"
+    )]
+    pub average_latency: Option<Duration>,
+}
+
+/// Everything a finished scan produced, returned by
+/// [`Worker::run`](crate::lib::worker::unit::Worker::run).
+#[derive(Debug, Serialize)]
+pub struct ScanReport {
+    pub metadata: ScanMetadata,
+    pub parameters: ScanParameters,
+    #[serde(serialize_with = "serialize_time")]
+    pub started: DateTime<Local>,
+    #[serde(rename = "duration_secs", serialize_with = "serialize_duration")]
+    pub duration: Duration,
+    /// Requests sent, verification requests included.
+    pub requests: usize,
+    /// Requests that failed or couldn't be made.
+    pub errors: usize,
+    /// How long the answered requests took.
+    pub latency: LatencyHistogram,
+    /// How long the target took to answer the request made before the scan.
+    #[serde(
+        rename = "baseline_latency_secs",
+        serialize_with = "serialize_optional_duration",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub baseline_latency: Option<Duration>,
+    /// Hits in the order they were found; past the configured limits they
+    /// live in a temporary file read back on iteration.
+    pub hits: ResultStore,
+    /// The scan was stopped or ran out of time before finishing.
+    pub stopped: bool,
+    pub waf: Option<WafVendor>,
+    /// Stack recognized for the check packs.
+    pub stack: Option<Stack>,
+    pub header_findings: Vec<HeaderFinding>,
+    pub capture: CaptureUsage,
+    /// Directories left partially scanned when their time budget ran out.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub partial_dirs: Vec<String>,
+}
+
+fn serialize_time<S: Serializer>(time: &DateTime<Local>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&time.to_rfc3339())
+}
+
+fn deserialize_time<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<DateTime<Local>, D::Error> {
+    let time = String::deserialize(deserializer)?;
+    DateTime::parse_from_rfc3339(&time)
+        .map(|time| time.with_timezone(&Local))
+        .map_err(serde::de::Error::custom)
+}
+
+fn serialize_optional_time<S: Serializer>(
+    time: &Option<DateTime<Local>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match time {
+        Some(time) => serialize_time(time, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn deserialize_optional_time<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<DateTime<Local>>, D::Error> {
+    match Option::<String>::deserialize(deserializer)? {
+        Some(time) => DateTime::parse_from_rfc3339(&time)
+            .map(|time| Some(time.with_timezone(&Local)))
+            .map_err(serde::de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+fn serialize_duration<S: Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64())
+}
+
+fn serialize_optional_duration<S: Serializer>(
+    duration: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match duration {
+        Some(duration) => serialize_duration(duration, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Formats saved results can be turned into.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ReportFormat {
+    Html,
+    Csv,
+    #[value(alias = "md")]
+    Markdown,
+}
+
+/// Hits of a results file and the metadata of the scan that produced them.
+#[derive(Debug, Default)]
+pub struct SavedResults {
+    /// Written before the hits and again with the end time after them; the
+    /// last one read wins.
+    pub metadata: Option<ScanMetadata>,
+    pub hits: Vec<HitResult>,
+    pub header_findings: Vec<HeaderFinding>,
+    /// Latencies of the scan, written once it finished.
+    pub latency: Option<LatencyHistogram>,
+}
+
+/// Reads a results file written by the `jsonl` sink or `--progress-jsonl`.
+///
+/// Folded extension variants are added to their hits, header findings and
+/// latencies are collected; other records (logs, progress) are skipped.
+pub fn read_results(reader: impl BufRead) -> Result<SavedResults> {
+    let mut results = SavedResults::default();
+
+    for (ind, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let record: Value = serde_json::from_str(&line)
+            .with_context(|| format!("Invalid JSON on line {}", ind + 1))?;
+        let kind = record.get("type").or_else(|| record.get("event"));
+        match kind.and_then(Value::as_str) {
+            Some("hit") => results.hits.push(
+                serde_json::from_value(record)
+                    .with_context(|| format!("Invalid hit on line {}", ind + 1))?,
+            ),
+            Some("metadata") => {
+                results.metadata = Some(
+                    serde_json::from_value(record)
+                        .with_context(|| format!("Invalid metadata on line {}", ind + 1))?,
+                )
+            }
+            Some("header_finding") => results.header_findings.push(
+                serde_json::from_value(record)
+                    .with_context(|| format!("Invalid header finding on line {}", ind + 1))?,
+            ),
+            Some("tls") => {
+                let tls = serde_json::from_value(record)
+                    .with_context(|| format!("Invalid TLS details on line {}", ind + 1))?;
+                if let Some(metadata) = results.metadata.as_mut() {
+                    metadata.add_tls(tls);
+                }
+            }
+            Some("latency") => {
+                results.latency = Some(
+                    serde_json::from_value(record)
+                        .with_context(|| format!("Invalid latencies on line {}", ind + 1))?,
+                )
+            }
+            Some("variant") => {
+                let field = |name| record.get(name).and_then(Value::as_str);
+                if let (Some(url), Some(variant)) = (field("url"), field("variant"))
+                    && let Some(hit) = results.hits.iter_mut().rev().find(|hit| hit.url == url)
+                {
+                    hit.variants.push(variant.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(results)
+}
+
+/// Only the hits of a results file, see [`read_results`].
+pub fn read_hits(reader: impl BufRead) -> Result<Vec<HitResult>> {
+    Ok(read_results(reader)?.hits)
+}
+
+/// A path found by one or both of two compared scans.
+#[derive(Debug)]
+pub struct DiffLine {
+    /// Path and query of the hits, which stay the same across hosts.
+    pub path: String,
+    pub left: Option<HitResult>,
+    pub right: Option<HitResult>,
+}
+
+impl DiffLine {
+    pub fn is_common(&self) -> bool {
+        self.left.is_some() && self.right.is_some()
+    }
+
+    pub fn only_left(&self) -> bool {
+        self.right.is_none()
+    }
+
+    pub fn only_right(&self) -> bool {
+        self.left.is_none()
+    }
+
+    /// Found by both scans, but with another status.
+    pub fn is_changed(&self) -> bool {
+        matches!((&self.left, &self.right), (Some(left), Some(right)) if left.status != right.status)
+    }
+}
+
+/// Lines up the hits of two scans by path, so that scans of different hosts,
+/// such as staging and production of one app, compare. Sorted by path.
+pub fn diff_hits(left: Vec<HitResult>, right: Vec<HitResult>) -> Vec<DiffLine> {
+    let mut lines = BTreeMap::new();
+    for (hits, is_left) in [(left, true), (right, false)] {
+        for hit in hits {
+            let path = hit_path(&hit.url);
+            let line = lines.entry(path.clone()).or_insert(DiffLine {
+                path,
+                left: None,
+                right: None,
+            });
+            if is_left {
+                line.left = Some(hit);
+            } else {
+                line.right = Some(hit);
+            }
+        }
+    }
+    lines.into_values().collect()
+}
+
+/// Path and query of `url`, or `url` itself when it doesn't parse.
+fn hit_path(url: &str) -> String {
+    match Url::parse(url) {
+        Ok(url) => match url.query() {
+            Some(query) => format!("{}?{query}", url.path()),
+            None => url.path().to_string(),
+        },
+        Err(_) => url.to_string(),
+    }
+}
+
+/// Groups HTML hits whose [DOM hashes](html::dom_hash) are close, approximating
+/// pages built from one template. Only groups of two or more pages are
+/// returned, largest first.
+pub fn similar_pages(hits: &[HitResult]) -> Vec<Vec<&HitResult>> {
+    let mut groups: Vec<(u64, Vec<&HitResult>)> = Vec::new();
+
+    for hit in hits {
+        let Some(hash) = hit
+            .dom_hash
+            .as_deref()
+            .and_then(|hash| u64::from_str_radix(hash, 16).ok())
+        else {
+            continue;
+        };
+
+        match groups
+            .iter_mut()
+            .find(|(first, _)| html::hash_distance(*first, hash) <= html::SIMILARITY_DISTANCE)
+        {
+            Some((_, members)) => members.push(hit),
+            None => groups.push((hash, vec![hit])),
+        }
+    }
+
+    let mut groups = groups
+        .into_iter()
+        .map(|(_, members)| members)
+        .filter(|members| members.len() > 1)
+        .collect::<Vec<_>>();
+    groups.sort_by_key(|members| std::cmp::Reverse(members.len()));
+    groups
+}
+
+/// Writes `results` as a complete document in `format`, starting with the
+/// metadata of the scan when it is known. CSV holds the hits only.
+pub fn write_report(
+    format: ReportFormat,
+    results: &SavedResults,
+    writer: &mut impl Write,
+) -> std::io::Result<()> {
+    let metadata = results.metadata.as_ref();
+    let hits = &results.hits;
+    let latency = results.latency.as_ref();
+    match format {
+        ReportFormat::Html => write_html(metadata, hits, &results.header_findings, latency, writer),
+        ReportFormat::Csv => {
+            if let Some(metadata) = metadata {
+                metadata.write_comments(writer)?;
+            }
+            writeln!(writer, "{}", csv::HEADER)?;
+            for hit in hits {
+                csv::write_row(writer, hit)?;
+            }
+            Ok(())
+        }
+        ReportFormat::Markdown => {
+            write_markdown(metadata, hits, &results.header_findings, latency, writer)
+        }
+    }
+}
+
+fn write_html(
+    metadata: Option<&ScanMetadata>,
+    hits: &[HitResult],
+    header_findings: &[HeaderFinding],
+    latency: Option<&LatencyHistogram>,
+    writer: &mut impl Write,
+) -> std::io::Result<()> {
+    writeln!(writer, "<!DOCTYPE html>")?;
+    writeln!(writer, "<html>\n<head>\n<meta charset=\"utf-8\">")?;
+    writeln!(writer, "<title>yadb report</title>")?;
+    writeln!(
+        writer,
+        "<style>body{{font-family:sans-serif}}table{{border-collapse:collapse}}\
+         td,th{{border:1px solid #ccc;padding:4px 8px;text-align:left}}</style>"
+    )?;
+    writeln!(writer, "</head>\n<body>")?;
+    writeln!(writer, "<h1>yadb report</h1>")?;
+    if let Some(metadata) = metadata {
+        writeln!(writer, "<table class=\"metadata\">")?;
+        for (label, value) in metadata.fields() {
+            writeln!(
+                writer,
+                "<tr><th>{label}</th><td>{}</td></tr>",
+                escape_html(&value)
+            )?;
+        }
+        writeln!(writer, "</table>")?;
+    }
+    writeln!(writer, "<p>{} hits</p>", hits.len())?;
+    writeln!(writer, "<table>")?;
+    writeln!(
+        writer,
+        "<tr><th>URL</th><th>Status</th><th>Title</th><th>Content type</th><th>Size</th></tr>"
+    )?;
+    for hit in hits {
+        let url = escape_html(&hit.url);
+        let variants = if hit.variants.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "<br><small>same as: {}</small>",
+                escape_html(&hit.variants.join(", "))
+            )
+        };
+        writeln!(
+            writer,
+            "<tr><td><a href=\"{url}\">{url}</a>{variants}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            hit.status,
+            escape_html(hit.title.as_deref().unwrap_or_default()),
+            escape_html(hit.content_type.as_deref().unwrap_or_default()),
+            hit.size.map(|size| size.to_string()).unwrap_or_default(),
+        )?;
+    }
+    writeln!(writer, "</table>")?;
+
+    let groups = similar_pages(hits);
+    if !groups.is_empty() {
+        writeln!(writer, "<h2>Similar pages</h2>")?;
+        for (ind, members) in groups.iter().enumerate() {
+            writeln!(
+                writer,
+                "<h3>Group {} ({} pages)</h3>\n<ul>",
+                ind + 1,
+                members.len()
+            )?;
+            for hit in members {
+                let url = escape_html(&hit.url);
+                writeln!(writer, "<li><a href=\"{url}\">{url}</a></li>")?;
+            }
+            writeln!(writer, "</ul>")?;
+        }
+    }
+
+    if !header_findings.is_empty() {
+        writeln!(writer, "<h2>Interesting headers</h2>\n<ul>")?;
+        for finding in header_findings {
+            writeln!(writer, "<li>{}</li>", escape_html(&finding.to_string()))?;
+        }
+        writeln!(writer, "</ul>")?;
+    }
+
+    if let Some(latency) = latency.filter(|latency| !latency.is_empty()) {
+        writeln!(writer, "<h2>Latency</h2>")?;
+        writeln!(writer, "<p>{latency}</p>")?;
+        writeln!(writer, "<pre>")?;
+        for line in latency.ascii() {
+            writeln!(writer, "{}", escape_html(&line))?;
+        }
+        writeln!(writer, "</pre>")?;
+    }
+
+    writeln!(writer, "</body>\n</html>")
+}
+
+fn write_markdown(
+    metadata: Option<&ScanMetadata>,
+    hits: &[HitResult],
+    header_findings: &[HeaderFinding],
+    latency: Option<&LatencyHistogram>,
+    writer: &mut impl Write,
+) -> std::io::Result<()> {
+    writeln!(writer, "# yadb report\n")?;
+    if let Some(metadata) = metadata {
+        for (label, value) in metadata.fields() {
+            writeln!(writer, "- **{label}:** `{}`", value.replace('`', "'"))?;
+        }
+        writeln!(writer)?;
+    }
+    writeln!(writer, "{} hits\n", hits.len())?;
+    writeln!(writer, "| URL | Status | Title | Content type | Size |")?;
+    writeln!(writer, "|---|---|---|---|---|")?;
+    for hit in hits {
+        let mut url = hit.url.clone();
+        if !hit.variants.is_empty() {
+            url.push_str(&format!(" (same as: {})", hit.variants.join(", ")));
+        }
+        writeln!(
+            writer,
+            "| {} | {} | {} | {} | {} |",
+            escape_markdown(&url),
+            hit.status,
+            escape_markdown(hit.title.as_deref().unwrap_or_default()),
+            escape_markdown(hit.content_type.as_deref().unwrap_or_default()),
+            hit.size.map(|size| size.to_string()).unwrap_or_default(),
+        )?;
+    }
+
+    let groups = similar_pages(hits);
+    if !groups.is_empty() {
+        writeln!(writer, "\n## Similar pages")?;
+        for (ind, members) in groups.iter().enumerate() {
+            writeln!(
+                writer,
+                "\n### Group {} ({} pages)\n",
+                ind + 1,
+                members.len()
+            )?;
+            for hit in members {
+                writeln!(writer, "- {}", escape_markdown(&hit.url))?;
+            }
+        }
+    }
+
+    if !header_findings.is_empty() {
+        writeln!(writer, "\n## Interesting headers\n")?;
+        for finding in header_findings {
+            writeln!(writer, "- {}", escape_markdown(&finding.to_string()))?;
+        }
+    }
+
+    if let Some(latency) = latency.filter(|latency| !latency.is_empty()) {
+        writeln!(writer, "\n## Latency\n\n{latency}\n\n```")?;
+        for line in latency.ascii() {
+            writeln!(writer, "{line}")?;
+        }
+        writeln!(writer, "```")?;
+    }
+    Ok(())
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
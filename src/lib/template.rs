@@ -0,0 +1,163 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+
+use crate::lib::{
+    util,
+    worker::{builder::WorkerBuilder, candidates::Transform, pipeline::Stage},
+};
+
+const TEMPLATES_DIR: &str = "templates";
+
+/// A shareable set of scan settings for a common stack, read from
+/// `templates/*.toml` in the [config directory](util::config_dir).
+///
+/// ```toml
+/// name = "WordPress"
+/// description = "Plugins, themes and leftover installer files"
+/// wordlist = "/usr/share/wordlists/wordpress.txt"
+/// wordlist_sha256 = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+/// filter_sizes = [0]
+/// checks = ["wp-login.php", "readme.html", "wp-json/wp/v2/users"]
+///
+/// [headers]
+/// Accept-Language = "en-US"
+/// ```
+///
+/// Ordered `[[stages]]` turn it into a pipeline, see [`Stage`].
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScanTemplate {
+    /// Name to select the template by; the file name when left out.
+    pub name: String,
+    pub description: String,
+    pub wordlist: Option<PathBuf>,
+    /// Hex SHA-256 the wordlist has to have.
+    pub wordlist_sha256: Option<String>,
+    pub headers: BTreeMap<String, String>,
+    pub match_regex: Option<String>,
+    pub filter_regex: Option<String>,
+    /// Regexes whose named groups are extracted from the bodies of hits.
+    pub captures: Vec<String>,
+    pub filter_sizes: Vec<u64>,
+    pub dedupe: bool,
+    pub hide_static: bool,
+    pub transforms: Vec<Transform>,
+    /// Paths requested on top of the wordlist.
+    pub checks: Vec<String>,
+    /// Run the built-in check pack of the recognized stack.
+    pub check_packs: bool,
+    /// Known-good path watched during the scan, see
+    /// [`WorkerBuilder::canary`].
+    pub canary: Option<String>,
+    pub stages: Vec<Stage>,
+}
+
+impl ScanTemplate {
+    /// `templates` in the config directory.
+    pub fn dir() -> Option<PathBuf> {
+        Some(util::config_dir()?.join(TEMPLATES_DIR))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let data =
+            fs::read_to_string(path).with_context(|| format!("Can't read {}", path.display()))?;
+        let mut template: Self = toml::from_str(&data)
+            .with_context(|| format!("Invalid template {}", path.display()))?;
+
+        if template.name.is_empty() {
+            template.name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+        }
+        Ok(template)
+    }
+
+    /// Every `*.toml` file of the templates directory in name order, each
+    /// with the template or the reason it couldn't be read.
+    pub fn load_all() -> Result<Vec<(PathBuf, Result<Self>)>> {
+        let Some(dir) = Self::dir() else {
+            return Ok(Vec::new());
+        };
+
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => {
+                return Err(err).with_context(|| format!("Can't read {}", dir.display()));
+            }
+        };
+
+        let mut paths = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        Ok(paths
+            .into_iter()
+            .map(|path| {
+                let template = Self::load(&path);
+                (path, template)
+            })
+            .collect())
+    }
+
+    /// The template called `name`, ignoring case.
+    pub fn find(name: &str) -> Result<Self> {
+        let name = name.trim();
+        Self::load_all()?
+            .into_iter()
+            .filter_map(|(_, template)| template.ok())
+            .find(|template| template.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| anyhow!("Template not found: {name}"))
+    }
+
+    /// Sets up `builder` with the template; settings made on the builder
+    /// afterwards take precedence.
+    pub fn apply(&self, mut builder: WorkerBuilder) -> WorkerBuilder {
+        if let Some(wordlist) = &self.wordlist {
+            builder = builder.wordlist(&wordlist.to_string_lossy());
+        }
+        if let Some(hash) = &self.wordlist_sha256 {
+            builder = builder.wordlist_sha256(hash);
+        }
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(regex) = &self.match_regex {
+            builder = builder.match_regex(regex);
+        }
+        if let Some(regex) = &self.filter_regex {
+            builder = builder.filter_regex(regex);
+        }
+        for regex in &self.captures {
+            builder = builder.capture_regex(regex);
+        }
+        for size in &self.filter_sizes {
+            builder = builder.filter_size(*size);
+        }
+        for transform in &self.transforms {
+            builder = builder.transform(*transform);
+        }
+        for stage in &self.stages {
+            builder = builder.stage(stage.clone());
+        }
+        if let Some(canary) = &self.canary {
+            builder = builder.canary(canary);
+        }
+
+        builder
+            .dedupe_bodies(self.dedupe)
+            .suppress_static(self.hide_static)
+            .extra_words(self.checks.iter().cloned())
+            .check_packs(self.check_packs)
+    }
+}
@@ -1,33 +1,57 @@
-use color_eyre::Result;
+use chrono::Local;
+use color_eyre::{Result, eyre::eyre};
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use ratatui::{
     DefaultTerminal, Frame,
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Flex, Layout},
     style::{Style, Stylize},
     text::{Line, Text},
-    widgets::{Block, BorderType, Borders, List, ListItem, ListState},
+    widgets::{
+        Block, BorderType, Borders, List, ListItem, ListState, Paragraph, Row as TableRow, Table,
+    },
 };
+use serde::Serialize;
 use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::Display,
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    rc::Rc,
     sync::mpsc::{self, Receiver},
-    thread::{self},
     time::Duration,
 };
-use tui_input::InputRequest;
+use tui_input::{Input, InputRequest};
+use url::Url;
 
 use crate::lib::{
+    i18n::{self, Lang, Msg, tr, tr_args},
+    output::stats::{StatsSink, UsageStats},
+    progress_handler::traits::ProgressHandler,
+    report::{self, DiffLine, ScanMetadata},
+    template::ScanTemplate,
+    tui::clipboard,
+    tui::session::{self, Retention, Row, Session},
     tui::widgets::{
         field::FieldType,
         popup::Popup,
-        worker_info::{FieldName, Selection, WorkerInfo, WorkerState, WorkerVariant},
+        worker_info::{self, FIELDS, FieldName, Selection, WorkerInfo, WorkerState, WorkerVariant},
     },
     worker::{
-        builder::{BuilderError, WorkerBuilder},
-        messages::{ProgressMessage, WorkerMessage},
+        builder::WorkerBuilder,
+        handle::WorkerHandle,
+        hit::{HitResult, Triage},
+        messages::WorkerMessage,
     },
 };
 
-pub const LOG_MAX: usize = 5;
-pub const MESSAGES_MAX: usize = 20;
+/// Smallest terminal the interface is drawn in; below it only a notice is.
+const MIN_WIDTH: u16 = 60;
+/// A worker in the info pane, inside its borders.
+const MIN_HEIGHT: u16 = worker_info::MIN_HEIGHT + 2;
+
+/// Lines of the All results pane that aren't hits: its borders and header.
+const RESULTS_CHROME: u16 = 3;
 
 #[derive(Debug, Default, PartialEq)]
 enum CurrentWindow {
@@ -38,7 +62,7 @@ enum CurrentWindow {
 
 #[derive(Debug)]
 enum WorkerType {
-    Worker,
+    Worker(WorkerHandle),
     Builder(Box<WorkerBuilder>),
 }
 
@@ -80,14 +104,96 @@ pub struct App {
     workers: Vec<WorkerRx>,
     show_help_popup: bool,
     worker_list_state: ListState,
-    builder_error: Option<BuilderError>,
+    selection: Option<Row>,
+    collapsed_groups: BTreeSet<String>,
+    session_path: Option<PathBuf>,
+    error: Option<String>,
+    notice: Option<String>,
+    /// Language picked in the session file, overriding the environment.
+    lang: Option<Lang>,
+    template_picker: Option<TemplatePicker>,
     input_mode: InputMode,
+    /// Quick filter of the Workers list by worker name or target host.
+    filter: Input,
+    /// URL → name of the worker that found it first, when hits are
+    /// deduplicated across workers.
+    found: Option<HashMap<String, String>>,
+    retention: Retention,
+    /// The Info pane shows the hits of every worker the filter lets through.
+    all_results: bool,
+    /// First line of the All results table on screen.
+    results_scroll: usize,
+    /// Workers shown side by side by the compare view.
+    compare: Option<(usize, usize)>,
+    /// Line of the All results table triage keys apply to.
+    results_selected: usize,
+    /// Verdicts given to hits, by URL.
+    triage: BTreeMap<String, Triage>,
+    /// The All results view, kept until the listed workers or their hits
+    /// change.
+    all_hits_view: Option<(ListedRevisions, Rc<HitsView>)>,
+    /// The compare view, kept until either worker's hits change.
+    compare_view: Option<((u64, u64), Rc<CompareView>)>,
+    /// Where finished workers write their results unless their form says.
+    auto_export: Option<PathBuf>,
+    /// Scans are added to the usage statistics.
+    usage_stats: bool,
+}
+
+/// Hits of the [listed workers](App::listed_workers) with the name of the
+/// worker that found them.
+type HitsView = std::io::Result<Vec<(String, HitResult)>>;
+
+/// Name and hits revision of every listed worker.
+type ListedRevisions = Vec<(String, u64)>;
+
+/// Hits of two compared workers, lined up by path.
+type CompareView = std::io::Result<Vec<DiffLine>>;
+
+/// Templates offered for a new worker and the highlighted one.
+#[derive(Debug)]
+struct TemplatePicker {
+    templates: Vec<ScanTemplate>,
+    selected: usize,
+}
+
+/// A line of a group export.
+#[derive(Serialize)]
+struct ExportedHit<'a> {
+    worker: &'a str,
+    #[serde(flatten)]
+    hit: &'a HitResult,
+}
+
+/// Metadata line written before the hits of each worker in a group export.
+#[derive(Serialize)]
+struct ExportedMetadata<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    worker: &'a str,
+    #[serde(flatten)]
+    metadata: &'a ScanMetadata,
 }
 
 impl App {
-    /// Construct a new instance of [`App`].
+    /// Construct a new instance of [`App`], restoring the previous session.
     pub fn new() -> Self {
-        Self::default()
+        let mut app = Self {
+            session_path: Session::path(),
+            ..Self::default()
+        };
+
+        if let Some(path) = app.session_path.clone() {
+            match Session::load(&path) {
+                Ok(session) => app.restore(session),
+                Err(err) => {
+                    app.error = Some(format!("{err:#}"));
+                    // Don't overwrite a file we couldn't read.
+                    app.session_path = None;
+                }
+            }
+        }
+        app
     }
 
     /// Run the application's main loop.
@@ -97,74 +203,185 @@ impl App {
             self.handle_crossterm_events()?;
             terminal.draw(|frame| self.render(frame))?;
 
+            let mut finished = Vec::new();
             for (sel, worker_state) in self.workers.iter_mut().enumerate() {
-                if let Ok(msg) = worker_state.rx.try_recv() {
-                    match msg {
-                        WorkerMessage::Progress(progress_message) => {
-                            match progress_message {
-                                ProgressMessage::Total(progress_change_message) => {
-                                    match progress_change_message {
-                                        crate::lib::worker::messages::ProgressChangeMessage::SetMessage(_) => {},
-                                        crate::lib::worker::messages::ProgressChangeMessage::SetSize(size) => {
-                                            self.workers_info_state[sel].progress_all_total = size;
-                                        },
-                                        crate::lib::worker::messages::ProgressChangeMessage::Start(_) => {},
-                                        crate::lib::worker::messages::ProgressChangeMessage::Advance => {
-                                            self.workers_info_state[sel].progress_all_now += 1;
-                                        },
-                                        crate::lib::worker::messages::ProgressChangeMessage::Print(_) => {},
-                                        crate::lib::worker::messages::ProgressChangeMessage::Finish => {
-                                            self.workers_info_state[sel].current_parsing = "Done!".to_string();
-                                            self.workers_info_state[sel].worker = WorkerVariant::Worker(true);
-                                        },
-                                    }
-                                },
-                                ProgressMessage::Current(progress_change_message) => {
-                                    match progress_change_message {
-                                        crate::lib::worker::messages::ProgressChangeMessage::SetMessage(str) => {
-                                            self.workers_info_state[sel].current_parsing = str;
-                                        },
-                                        crate::lib::worker::messages::ProgressChangeMessage::SetSize(size) => {
-                                            self.workers_info_state[sel].progress_current_now = 0;
-                                            self.workers_info_state[sel].progress_current_total = size;
-                                        },
-                                        crate::lib::worker::messages::ProgressChangeMessage::Start(_) => {},
-                                        crate::lib::worker::messages::ProgressChangeMessage::Advance => {
-                                            self.workers_info_state[sel].progress_current_now += 1;
-                                        },
-                                        crate::lib::worker::messages::ProgressChangeMessage::Print(msg) => {
-                                            let messages = &mut self.workers_info_state[sel].messages;
-                                            messages.push_back(msg);
-                                            if messages.len() > MESSAGES_MAX {
-                                                messages.pop_front();
-                                            }
-                                        },
-                                        crate::lib::worker::messages::ProgressChangeMessage::Finish => {},
-                                    }
-                                },
-                            }
-                        },
-                        WorkerMessage::Log(loglevel, str) => {
-                            let log = &mut self.workers_info_state[sel].log;
-                            match loglevel {
-                                crate::lib::logger::traits::LogLevel::WARN => log.push_front("[WARN] ".to_owned() + &str),
-                                crate::lib::logger::traits::LogLevel::ERROR => log.push_front("[ERROR] ".to_owned() + &str),
-                                crate::lib::logger::traits::LogLevel::CRITICAL => log.push_front("[CRITICAL]".to_owned() + &str),
-                                _ => {},
+                if let Ok(mut msg) = worker_state.rx.try_recv() {
+                    // The last message of a run that didn't fail, so the
+                    // export has everything it reported.
+                    if let WorkerMessage::Finished(_) = msg {
+                        finished.push(sel);
+                    }
+                    // Such as an unreachable target, found before any request.
+                    if let WorkerMessage::Failed(reason) = &msg {
+                        self.error =
+                            Some(format!("{}: {reason}", self.workers_info_state[sel].name()));
+                    }
+                    if let WorkerMessage::Hit(hit) = &mut msg {
+                        hit.triage = self.triage.get(&hit.url).copied();
+                    }
+                    if let (WorkerMessage::Hit(hit), Some(found)) = (&mut msg, &mut self.found) {
+                        let name = self.workers_info_state[sel].name();
+                        match found.get(&hit.url) {
+                            Some(first) if first != name => {
+                                hit.first_found_by = Some(first.clone());
                             }
-                            if log.len() > LOG_MAX {
-                                log.pop_front();
+                            Some(_) => {}
+                            None => {
+                                found.insert(hit.url.clone(), name.to_string());
                             }
-                        },
+                        }
                     }
+                    self.workers_info_state[sel].handle(msg);
                 }
             }
+            for sel in finished {
+                self.auto_export(sel);
+            }
+        }
+
+        if let Some(path) = &self.session_path {
+            self.session().save(path).map_err(|err| eyre!("{err:#}"))?;
+        }
+
+        for sel in 0..self.workers.len() {
+            self.stop_worker(sel);
         }
         Ok(())
     }
 
+    fn restore(&mut self, session: Session) {
+        self.lang = session.lang;
+        if let Some(lang) = session.lang {
+            i18n::set_lang(lang);
+        }
+        self.collapsed_groups = session.collapsed;
+        self.found = session.dedupe_hits.then(HashMap::new);
+        self.usage_stats = session.usage_stats;
+        self.retention = session.retention;
+        self.triage = session.triage;
+        self.auto_export = session.auto_export;
+        for saved in session.workers {
+            self.add_worker();
+            let state = self.workers_info_state.last_mut().unwrap();
+            for field in FIELDS {
+                if let Some(value) = saved.get(field.key()) {
+                    state.fields_states[field.index()].set(value);
+                }
+            }
+        }
+    }
+
+    fn session(&self) -> Session {
+        let groups = self
+            .workers_info_state
+            .iter()
+            .map(WorkerState::group)
+            .collect::<BTreeSet<_>>();
+
+        Session {
+            lang: self.lang,
+            dedupe_hits: self.found.is_some(),
+            usage_stats: self.usage_stats,
+            retention: self.retention,
+            auto_export: self.auto_export.clone(),
+            collapsed: self
+                .collapsed_groups
+                .iter()
+                .filter(|group| groups.contains(group.as_str()))
+                .cloned()
+                .collect(),
+            workers: self
+                .workers_info_state
+                .iter()
+                .map(|state| {
+                    FIELDS
+                        .iter()
+                        .map(|field| {
+                            let value = state.fields_states[field.index()].get();
+                            (field.key().to_string(), value.to_string())
+                        })
+                        .collect::<BTreeMap<_, _>>()
+                })
+                .collect(),
+            triage: self.triage.clone(),
+        }
+    }
+
+    fn rows(&self) -> Vec<Row> {
+        let filter = self.filter.value().trim().to_lowercase();
+        session::rows(
+            self.workers_info_state.iter().map(WorkerState::group),
+            &self.collapsed_groups,
+            |ind| filter.is_empty() || self.workers_info_state[ind].matches(&filter),
+        )
+    }
+
+    /// Moves the selection to the first row when the filter hid the selected one.
+    fn keep_selection_visible(&mut self) {
+        let rows = self.rows();
+        if self
+            .selection
+            .as_ref()
+            .is_none_or(|selection| !rows.contains(selection))
+        {
+            self.selection = rows.first().cloned();
+        }
+    }
+
+    fn selected_worker(&self) -> Option<usize> {
+        match self.selection {
+            Some(Row::Worker(sel)) => Some(sel),
+            _ => None,
+        }
+    }
+
+    fn selected_group(&self) -> Option<String> {
+        match &self.selection {
+            Some(Row::Group(group)) => Some(group.clone()),
+            Some(Row::Worker(sel)) => Some(self.workers_info_state[*sel].group().to_string()),
+            None => None,
+        }
+    }
+
+    fn move_selection(&mut self, forward: bool) {
+        let rows = self.rows();
+        if rows.is_empty() {
+            return;
+        }
+
+        let position = self
+            .selection
+            .as_ref()
+            .and_then(|selection| rows.iter().position(|row| row == selection));
+        let next = match (position, forward) {
+            (None, _) => 0,
+            (Some(pos), true) => (pos + 1) % rows.len(),
+            (Some(0), false) => rows.len() - 1,
+            (Some(pos), false) => pos - 1,
+        };
+        self.selection = Some(rows[next].clone());
+    }
+
     /// Renders the user interface.
     fn render(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            let notice = tr_args(
+                Msg::TerminalTooSmall,
+                &[
+                    ("width", &area.width),
+                    ("height", &area.height),
+                    ("min_width", &MIN_WIDTH),
+                    ("min_height", &MIN_HEIGHT),
+                ],
+            );
+            let [line] = Layout::vertical([Constraint::Length(1)])
+                .flex(Flex::Center)
+                .areas(area);
+            frame.render_widget(Paragraph::new(notice).centered().yellow(), line);
+            return;
+        }
+
         let layout = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Max(30), Constraint::Min(0)].as_ref())
@@ -173,9 +390,51 @@ impl App {
         let rect_list = layout[0];
         let rect_info = layout[1];
 
-        let workers_title = Line::from(" Workers ").centered();
+        let marked = self.workers_info_state.iter().filter(|w| w.marked).count();
+        let workers_title = if marked == 0 {
+            Line::from(format!(" {} ", tr(Msg::WorkersTitle))).centered()
+        } else {
+            Line::from(format!(
+                " {} ",
+                tr_args(Msg::WorkersMarkedTitle, &[("marked", &marked)])
+            ))
+            .centered()
+        };
 
-        let info_title = Line::from(" Info ");
+        let compared = self.compare.map(|pair| (pair, self.compare_hits(pair)));
+        let all_hits = self.all_results.then(|| self.all_hits());
+        let info_title = match (
+            compared.as_ref().map(|(pair, diff)| (pair, diff.as_ref())),
+            all_hits.as_deref(),
+        ) {
+            (Some(((left, right), Ok(diff))), _) => {
+                self.results_scroll = self.results_scroll.min(diff.len().saturating_sub(1));
+                let count = |f: fn(&&DiffLine) -> bool| diff.iter().filter(f).count();
+                Line::from(format!(
+                    " {} ",
+                    tr_args(
+                        Msg::CompareTitle,
+                        &[
+                            ("left", &self.workers_info_state[*left].name()),
+                            ("right", &self.workers_info_state[*right].name()),
+                            ("common", &count(|line| line.is_common())),
+                            ("changed", &count(|line| line.is_changed())),
+                            ("only_left", &count(|line| line.only_left())),
+                            ("only_right", &count(|line| line.only_right())),
+                        ]
+                    )
+                ))
+            }
+            (_, Some(Ok(hits))) => {
+                self.results_selected = self.results_selected.min(hits.len().saturating_sub(1));
+                self.results_scroll = self.results_scroll.min(hits.len().saturating_sub(1));
+                Line::from(format!(
+                    " {} ",
+                    tr_args(Msg::AllResultsTitle, &[("count", &hits.len())])
+                ))
+            }
+            _ => Line::from(format!(" {} ", tr(Msg::InfoTitle))),
+        };
 
         let mut block_list = Block::default()
             .border_type(BorderType::Rounded)
@@ -187,7 +446,8 @@ impl App {
             .borders(Borders::ALL)
             .title(info_title);
 
-        let help_line = Line::from(vec![" Help - ".into(), "<h> ".bold()]).centered();
+        let help_line =
+            Line::from(vec![format!(" {} - ", tr(Msg::Help)).into(), "<h> ".bold()]).centered();
 
         match self.current_window {
             CurrentWindow::Workers => {
@@ -200,28 +460,94 @@ impl App {
             }
         }
 
-        let block_list_inner = block_list.inner(rect_list);
+        let mut block_list_inner = block_list.inner(rect_list);
         let block_info_inner = block_info.inner(rect_info);
 
         frame.render_widget(block_list, rect_list);
         frame.render_widget(block_info, rect_info);
 
-        let workers_name_list = self
-            .workers_info_state
+        let filtering =
+            self.input_mode == InputMode::Editing && self.current_window == CurrentWindow::Workers;
+        if filtering || !self.filter.value().is_empty() {
+            let [rect_filter, rect_rows] =
+                Layout::vertical([Constraint::Length(1), Constraint::Min(0)])
+                    .areas(block_list_inner);
+            let mut filter = Paragraph::new(format!("/{}", self.filter.value()));
+            if !filtering {
+                filter = filter.dim();
+            }
+            frame.render_widget(filter, rect_filter);
+            if filtering {
+                frame.set_cursor_position((
+                    rect_filter.x + 1 + self.filter.visual_cursor() as u16,
+                    rect_filter.y,
+                ));
+            }
+            block_list_inner = rect_rows;
+        }
+
+        let rows = self.rows();
+        let selected_position = self
+            .selection
+            .as_ref()
+            .and_then(|selection| rows.iter().position(|row| row == selection));
+        self.worker_list_state.select(selected_position);
+
+        let workers_name_list = rows
             .iter()
             .enumerate()
-            .map(|(i, w)| {
-                let name = w.fields_states[0].get();
-                let formated_name = match self.workers_info_state[i].worker {
-                    WorkerVariant::Worker(s) if !s => format!("<RUN> {name}"),
-                    WorkerVariant::Worker(s) if s => format!("<DONE> {name}"),
-                    WorkerVariant::Builder => format!("<WAIT> {name}"),
-                    _ => String::default(),
+            .map(|(i, row)| {
+                let formated_name = match row {
+                    Row::Group(group) => {
+                        let count = self
+                            .workers_info_state
+                            .iter()
+                            .filter(|w| w.group() == group)
+                            .count();
+                        let marker = if self.collapsed_groups.contains(group) {
+                            '▸'
+                        } else {
+                            '▾'
+                        };
+                        Text::from(format!("{marker} {group} ({count})"))
+                    }
+                    Row::Worker(ind) => {
+                        let w = &self.workers_info_state[*ind];
+                        let name = w.name();
+                        let indent = if w.group().is_empty() { "" } else { "  " };
+                        let mark = if w.marked { "+" } else { "" };
+                        let title = match w.worker {
+                            WorkerVariant::Worker(false) if self.is_paused(*ind) => {
+                                format!("{indent}{mark}<PAUSED> {name}")
+                            }
+                            WorkerVariant::Worker(s) if !s => match w.eta() {
+                                Some(eta) => format!("{indent}{mark}<RUN> {name} · {eta}"),
+                                None => format!("{indent}{mark}<RUN> {name}"),
+                            },
+                            WorkerVariant::Worker(s) if s => {
+                                format!("{indent}{mark}<DONE> {name}")
+                            }
+                            WorkerVariant::Failed => format!("{indent}{mark}<FAIL> {name}"),
+                            WorkerVariant::Builder => format!("{indent}{mark}<WAIT> {name}"),
+                            _ => String::default(),
+                        };
+                        let details = format!("{indent}  {} · {}", w.host(), w.mode());
+                        Text::from(vec![Line::from(title), Line::from(details).dim()])
+                    }
                 };
                 let mut item = ListItem::new(formated_name);
-                if let Some(selected_index) = self.worker_list_state.selected()
-                    && selected_index == i
-                {
+                match row {
+                    Row::Group(_) => item = item.bold(),
+                    Row::Worker(ind) => {
+                        let state = &self.workers_info_state[*ind];
+                        if let WorkerVariant::Failed = state.worker {
+                            item = item.red();
+                        } else if state.canary_alert.is_some() {
+                            item = item.yellow();
+                        }
+                    }
+                }
+                if selected_position == Some(i) {
                     item = item.reversed().blue();
                 }
                 item
@@ -230,13 +556,54 @@ impl App {
         let workers_list = List::new(workers_name_list);
         frame.render_stateful_widget(workers_list, block_list_inner, &mut self.worker_list_state);
 
-        if let Some(sel) = self.worker_list_state.selected() {
-            let worker_info = WorkerInfo {};
-            let state = &mut self.workers_info_state[sel];
-            frame.render_stateful_widget(worker_info, block_info_inner, state);
+        if let Some(((left, right), diff)) = &compared {
+            match diff.as_ref() {
+                Ok(diff) => {
+                    let [left_area, right_area] =
+                        Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)])
+                            .spacing(1)
+                            .areas(block_info_inner);
+                    for (&sel, area, is_left) in
+                        [(left, left_area, true), (right, right_area, false)]
+                    {
+                        let name = self.workers_info_state[sel].name();
+                        let table = Self::compare_table(name, diff, is_left, self.results_scroll);
+                        frame.render_widget(table, area);
+                    }
+                }
+                Err(err) => {
+                    frame.render_widget(Paragraph::new(err.to_string()).red(), block_info_inner);
+                }
+            }
+        } else {
+            match (all_hits.as_deref(), self.selection.clone()) {
+                (Some(Ok(hits)), _) => {
+                    // One line goes to the header.
+                    let visible = (block_info_inner.height as usize).saturating_sub(1).max(1);
+                    self.results_scroll = self.results_scroll.clamp(
+                        self.results_selected.saturating_sub(visible - 1),
+                        self.results_selected,
+                    );
+                    frame.render_widget(self.all_results_table(hits), block_info_inner);
+                }
+                (Some(Err(err)), _) => {
+                    frame.render_widget(Paragraph::new(err.to_string()).red(), block_info_inner);
+                }
+                (None, Some(Row::Worker(sel))) => {
+                    let worker_info = WorkerInfo {};
+                    let state = &mut self.workers_info_state[sel];
+                    frame.render_stateful_widget(worker_info, block_info_inner, state);
 
-            if self.input_mode == InputMode::Editing {
-                frame.set_cursor_position(state.get_cursor_position());
+                    if self.input_mode == InputMode::Editing
+                        && self.current_window == CurrentWindow::Info
+                    {
+                        frame.set_cursor_position(state.get_cursor_position());
+                    }
+                }
+                (None, Some(Row::Group(group))) => {
+                    frame.render_widget(self.group_summary(&group), block_info_inner);
+                }
+                (None, None) => {}
             }
         }
 
@@ -244,9 +611,74 @@ impl App {
             self.render_help_popup(frame);
         }
 
-        if let Some(err) = &self.builder_error {
+        if let Some(err) = &self.error {
             self.render_error_popup(frame, err.clone());
         }
+
+        if let Some(notice) = &self.notice {
+            let popup = Popup::new(
+                format!(" {} ", tr(Msg::InfoTitle)),
+                Text::from(notice.clone()),
+            );
+            frame.render_widget(popup, frame.area());
+        }
+
+        if let Some(picker) = &self.template_picker {
+            let lines = picker
+                .templates
+                .iter()
+                .enumerate()
+                .map(|(ind, template)| {
+                    let line = Line::from(template.name.clone());
+                    if ind == picker.selected {
+                        line.reversed()
+                    } else {
+                        line
+                    }
+                })
+                .collect::<Vec<_>>();
+            let popup = Popup::new(format!(" {} ", tr(Msg::TemplatesTitle)), Text::from(lines));
+            frame.render_widget(popup, frame.area());
+        }
+    }
+
+    fn group_summary(&self, group: &str) -> Paragraph<'static> {
+        let members = self
+            .workers_info_state
+            .iter()
+            .filter(|w| w.group() == group)
+            .collect::<Vec<_>>();
+        let count = |variant: fn(&WorkerVariant) -> bool| {
+            members.iter().filter(|w| variant(&w.worker)).count()
+        };
+
+        let line = |msg: Msg, value: &dyn Display| Line::from(format!("{}: {value}", tr(msg)));
+
+        Paragraph::new(Text::from(vec![
+            line(Msg::Group, &group).bold(),
+            line(Msg::Workers, &members.len()),
+            line(
+                Msg::Waiting,
+                &count(|w| matches!(w, WorkerVariant::Builder)),
+            ),
+            line(
+                Msg::Running,
+                &count(|w| matches!(w, WorkerVariant::Worker(false))),
+            ),
+            line(
+                Msg::Done,
+                &count(|w| matches!(w, WorkerVariant::Worker(true))),
+            ),
+            line(Msg::Failed, &count(|w| matches!(w, WorkerVariant::Failed))),
+            line(
+                Msg::Results,
+                &members.iter().map(|w| w.results.len()).sum::<usize>(),
+            ),
+            Line::default(),
+            help("<s>", Msg::HelpStartGroup),
+            help("<e>", Msg::HelpExportGroup),
+            help("<Enter>", Msg::HelpCollapse),
+        ]))
     }
 
     /// Reads the crossterm events and updates the state of [`App`].
@@ -256,13 +688,27 @@ impl App {
                 // it's important to check KeyEventKind::Press to avoid handling key release events
                 Event::Key(key) if key.kind == KeyEventKind::Press => self.on_key_event(key),
                 Event::Mouse(_) => {}
-                Event::Resize(_, _) => {}
+                Event::Resize(_, height) => self.on_resize(height),
                 _ => {}
             }
         }
         Ok(())
     }
 
+    /// Keeps lists and the scrolled results in view of the new terminal size;
+    /// the layouts themselves are recomputed on the next draw.
+    fn on_resize(&mut self, height: u16) {
+        // Scrolled down to the selection again on the next draw.
+        *self.worker_list_state.offset_mut() = 0;
+
+        if let Some(hits) = self.all_results.then(|| self.all_hits())
+            && let Ok(hits) = hits.as_ref()
+        {
+            let visible = height.saturating_sub(RESULTS_CHROME) as usize;
+            self.results_scroll = self.results_scroll.min(hits.len().saturating_sub(visible));
+        }
+    }
+
     /// Handles the key events and updates the state of [`App`].
     fn on_key_event(&mut self, key: KeyEvent) {
         if (key.modifiers, key.code) == (KeyModifiers::CONTROL, KeyCode::Char('c')) {
@@ -284,64 +730,726 @@ impl App {
     }
 
     fn handle_workers_list_keys(&mut self, key: KeyEvent) {
+        if let Some(picker) = &mut self.template_picker {
+            match key.code {
+                KeyCode::Down => picker.selected = (picker.selected + 1) % picker.templates.len(),
+                KeyCode::Up => {
+                    picker.selected =
+                        (picker.selected + picker.templates.len() - 1) % picker.templates.len()
+                }
+                KeyCode::Enter => {
+                    let template = picker.templates.swap_remove(picker.selected);
+                    self.template_picker = None;
+                    self.add_worker_from_template(&template);
+                }
+                KeyCode::Esc => self.template_picker = None,
+                _ => {}
+            }
+            return;
+        }
+
         match (key.modifiers, key.code) {
+            (_, KeyCode::Enter | KeyCode::Esc) if self.error.is_some() || self.notice.is_some() => {
+                self.close_all_popups();
+            }
             (_, KeyCode::Char('a')) => {
-                self.workers_info_state.push(WorkerState::default());
-                self.workers.push(WorkerRx::default());
-                if self.worker_list_state.selected().is_none() {
-                    self.worker_list_state.select(Some(0));
-                }
+                self.add_worker();
             }
-            (_, KeyCode::Down) => {
-                if self.workers_info_state.is_empty() {
-                    return;
-                }
-                if self.worker_list_state.selected() == Some(self.workers_info_state.len() - 1) {
-                    self.worker_list_state.select_first();
-                    return;
-                }
-                self.worker_list_state.select_next();
+            (_, KeyCode::Char('A')) => {
+                self.add_worker_from_clipboard();
+            }
+            (_, KeyCode::Char('t')) => self.open_template_picker(),
+            (_, KeyCode::Char('/')) => self.input_mode = InputMode::Editing,
+            (_, KeyCode::Char('c')) => self.toggle_compare(),
+            (_, KeyCode::Esc) if self.compare.is_some() => self.compare = None,
+            (_, KeyCode::Down) if self.compare.is_some() => self.results_scroll += 1,
+            (_, KeyCode::Up) if self.compare.is_some() => {
+                self.results_scroll = self.results_scroll.saturating_sub(1);
+            }
+            (_, KeyCode::PageDown) if self.compare.is_some() => self.results_scroll += 10,
+            (_, KeyCode::PageUp) if self.compare.is_some() => {
+                self.results_scroll = self.results_scroll.saturating_sub(10);
             }
-            (_, KeyCode::Up) => {
-                if self.workers_info_state.is_empty() {
-                    return;
+            (_, KeyCode::Down) if self.all_results => self.results_selected += 1,
+            (_, KeyCode::Up) if self.all_results => {
+                self.results_selected = self.results_selected.saturating_sub(1);
+            }
+            (_, KeyCode::Char('i')) if self.all_results => self.toggle_triage(Triage::Interesting),
+            (_, KeyCode::Char('f')) if self.all_results => {
+                self.toggle_triage(Triage::FalsePositive);
+            }
+            (_, KeyCode::Char('x')) if self.all_results => self.toggle_triage(Triage::Done),
+            (_, KeyCode::Down) => self.move_selection(true),
+            (_, KeyCode::Up) => self.move_selection(false),
+            (_, KeyCode::Char('d')) | (_, KeyCode::Delete) => {
+                if let Some(sel) = self.selected_worker() {
+                    self.delete_workers(&[sel]);
                 }
-                if self.worker_list_state.selected() == Some(0) {
-                    self.worker_list_state.select_last();
-                    return;
+            }
+            (_, KeyCode::Char(' ')) => self.toggle_mark(),
+            (_, KeyCode::Esc) => {
+                for state in &mut self.workers_info_state {
+                    state.marked = false;
                 }
-                self.worker_list_state.select_previous();
             }
-            (_, KeyCode::Char('d')) | (_, KeyCode::Delete) => {
-                if let Some(sel) = self.worker_list_state.selected() {
-                    self.workers_info_state.remove(sel);
-                    self.workers.remove(sel);
+            (_, KeyCode::Char('S')) => self.start_marked(),
+            (_, KeyCode::Char('X')) => {
+                for sel in self.marked_workers() {
+                    self.stop_worker(sel);
                 }
             }
+            (_, KeyCode::Char('D')) => {
+                let marked = self.marked_workers();
+                self.delete_workers(&marked);
+            }
             (_, KeyCode::Char('h')) => {
                 self.show_help_popup = !self.show_help_popup;
             }
-            (_, KeyCode::Right | KeyCode::Enter | KeyCode::Tab) => {
-                if !self.workers_info_state.is_empty() {
-                    self.switch_window()
+            (_, KeyCode::Enter) if matches!(self.selection, Some(Row::Group(_))) => {
+                if let Some(group) = self.selected_group()
+                    && !self.collapsed_groups.remove(&group)
+                {
+                    self.collapsed_groups.insert(group);
+                }
+            }
+            (_, KeyCode::Char('s')) if matches!(self.selection, Some(Row::Group(_))) => {
+                if let Some(group) = self.selected_group() {
+                    self.start_group(&group);
                 }
             }
+            (_, KeyCode::Char('r')) => {
+                self.all_results = !self.all_results;
+                self.compare = None;
+                self.results_scroll = 0;
+                self.results_selected = 0;
+            }
+            (_, KeyCode::PageDown) if self.all_results => self.results_selected += 10,
+            (_, KeyCode::PageUp) if self.all_results => {
+                self.results_selected = self.results_selected.saturating_sub(10);
+            }
+            (_, KeyCode::Char('e')) if self.all_results => self.export_all_results(),
+            (_, KeyCode::Char('e')) if matches!(self.selection, Some(Row::Group(_))) => {
+                if let Some(group) = self.selected_group() {
+                    self.export_group(&group);
+                }
+            }
+            (_, KeyCode::Char('w')) => self.dump_selection(),
+            (_, KeyCode::Enter | KeyCode::Char('s'))
+                if self
+                    .selected_worker()
+                    .is_some_and(|sel| self.workers_info_state[sel].is_waiting()) =>
+            {
+                if let Some(sel) = self.selected_worker()
+                    && let Err(err) = self.start_worker(sel)
+                {
+                    self.error = Some(err);
+                }
+            }
+            (_, KeyCode::Right | KeyCode::Enter | KeyCode::Tab)
+                if self.selected_worker().is_some() =>
+            {
+                self.switch_window()
+            }
             _ => {}
         }
     }
 
+    /// Adds a worker to the group of the current selection, clearing the
+    /// filter so that the new worker shows up.
+    fn add_worker(&mut self) {
+        self.filter.reset();
+        let group = self.selected_group().unwrap_or_default();
+        let mut state = WorkerState::new(self.retention);
+        state.fields_states[FieldName::Group.index()].set(&group);
+        state.set_auto_name(self.unique_name(default_names(), None));
+
+        self.workers_info_state.push(state);
+        self.workers.push(WorkerRx::default());
+        if self.selection.is_none() {
+            self.selection = Some(Row::Worker(self.workers_info_state.len() - 1));
+        }
+    }
+
+    /// The first of `candidates` no worker but `except` is called.
+    fn unique_name(
+        &self,
+        mut candidates: impl Iterator<Item = String>,
+        except: Option<usize>,
+    ) -> String {
+        candidates
+            .find(|name| {
+                !self
+                    .workers_info_state
+                    .iter()
+                    .enumerate()
+                    .any(|(ind, w)| Some(ind) != except && w.name().trim() == name)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Names a worker that wasn't renamed by hand after its target host.
+    fn follow_host(&mut self, sel: usize) {
+        let state = &self.workers_info_state[sel];
+        if !state.is_auto_named() {
+            return;
+        }
+
+        let host = state.host();
+        let name = if host.is_empty() {
+            self.unique_name(default_names(), Some(sel))
+        } else {
+            let numbered = (2..).map(|n| format!("{host}-{n}"));
+            self.unique_name(std::iter::once(host.clone()).chain(numbered), Some(sel))
+        };
+        self.workers_info_state[sel].set_auto_name(name);
+    }
+
+    /// Removes workers, stopping running ones, and keeps the selection on
+    /// the same row or the one taking its place.
+    fn delete_workers(&mut self, indices: &[usize]) {
+        let position = self
+            .selection
+            .as_ref()
+            .and_then(|selection| self.rows().iter().position(|row| row == selection));
+
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+        // Dropping the handles stops the scans without waiting for them.
+        for &sel in indices.iter().rev() {
+            self.workers_info_state.remove(sel);
+            self.workers.remove(sel);
+        }
+        let shifted = |sel: usize| sel - indices.iter().filter(|&&ind| ind < sel).count();
+        self.compare = self.compare.and_then(|(left, right)| {
+            (!indices.contains(&left) && !indices.contains(&right))
+                .then(|| (shifted(left), shifted(right)))
+        });
+
+        self.selection = match self.selection.take() {
+            Some(Row::Worker(sel)) if !indices.contains(&sel) => Some(Row::Worker(shifted(sel))),
+            Some(Row::Group(group))
+                if self.workers_info_state.iter().any(|w| w.group() == group) =>
+            {
+                Some(Row::Group(group))
+            }
+            _ => {
+                let rows = self.rows();
+                rows.get(
+                    position
+                        .unwrap_or_default()
+                        .min(rows.len().saturating_sub(1)),
+                )
+                .cloned()
+            }
+        };
+    }
+
+    /// Asks a running worker to finish; waiting and finished workers are left alone.
+    fn stop_worker(&self, sel: usize) {
+        if let WorkerType::Worker(handle) = &self.workers[sel].worker_type
+            && !handle.is_finished()
+        {
+            handle.control().stop();
+        }
+    }
+
+    /// Pauses the running worker at `sel`, or resumes it when paused.
+    fn toggle_pause(&self, sel: usize) {
+        if let WorkerType::Worker(handle) = &self.workers[sel].worker_type
+            && !handle.is_finished()
+        {
+            handle.control().toggle_pause();
+        }
+    }
+
+    fn is_paused(&self, sel: usize) -> bool {
+        matches!(&self.workers[sel].worker_type, WorkerType::Worker(handle) if handle.control().is_paused())
+    }
+
+    fn marked_workers(&self) -> Vec<usize> {
+        (0..self.workers_info_state.len())
+            .filter(|&sel| self.workers_info_state[sel].marked)
+            .collect()
+    }
+
+    /// Marks the selected worker, or every worker of the selected group.
+    fn toggle_mark(&mut self) {
+        match self.selection.clone() {
+            Some(Row::Worker(sel)) => {
+                let state = &mut self.workers_info_state[sel];
+                state.marked = !state.marked;
+            }
+            Some(Row::Group(group)) => {
+                let members = self
+                    .workers_info_state
+                    .iter_mut()
+                    .filter(|w| w.group() == group)
+                    .collect::<Vec<_>>();
+                let mark = members.iter().any(|w| !w.marked);
+                for state in members {
+                    state.marked = mark;
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Starts every marked waiting worker, stopping at the first failure.
+    fn start_marked(&mut self) {
+        for sel in self.marked_workers() {
+            if !self.workers_info_state[sel].is_waiting() {
+                continue;
+            }
+            if let Err(err) = self.start_worker(sel) {
+                let name = self.workers_info_state[sel].name();
+                self.error = Some(format!("{name}: {err}"));
+                return;
+            }
+        }
+    }
+
+    /// Starts every waiting worker of `group`, stopping at the first failure.
+    fn start_group(&mut self, group: &str) {
+        let waiting = (0..self.workers_info_state.len())
+            .filter(|&sel| {
+                let state = &self.workers_info_state[sel];
+                state.group() == group && state.is_waiting()
+            })
+            .collect::<Vec<_>>();
+
+        for sel in waiting {
+            if let Err(err) = self.start_worker(sel) {
+                let name = self.workers_info_state[sel].name();
+                self.error = Some(format!("{name}: {err}"));
+                return;
+            }
+        }
+    }
+
+    /// Writes the results of every worker in `group` to a JSON Lines file.
+    fn export_group(&mut self, group: &str) {
+        let path = PathBuf::from(format!("yadb-{}.jsonl", file_stem(group)));
+        let members = self
+            .workers_info_state
+            .iter()
+            .filter(|w| w.group() == group)
+            .collect::<Vec<_>>();
+        let result = self.write_export(&members, &path);
+        self.report_export(&path, result);
+    }
+
+    /// Opens the compare view on the two marked workers, or closes it.
+    fn toggle_compare(&mut self) {
+        if self.compare.take().is_some() {
+            return;
+        }
+        match self.marked_workers()[..] {
+            [left, right] => {
+                self.compare = Some((left, right));
+                self.all_results = false;
+                self.results_scroll = 0;
+            }
+            _ => self.error = Some(tr(Msg::CompareNeedsTwo).to_string()),
+        }
+    }
+
+    /// Hits of the workers being compared, lined up by path. Read again only
+    /// once either worker's hits change.
+    fn compare_hits(&mut self, (left, right): (usize, usize)) -> Rc<CompareView> {
+        let (left, right) = (
+            &self.workers_info_state[left].results,
+            &self.workers_info_state[right].results,
+        );
+        let key = (left.revision(), right.revision());
+        if let Some((cached, view)) = &self.compare_view
+            && *cached == key
+        {
+            return view.clone();
+        }
+
+        let diff = || {
+            Ok(report::diff_hits(
+                left.iter()?.collect(),
+                right.iter()?.collect(),
+            ))
+        };
+        let view = Rc::new(diff());
+        self.compare_view = Some((key, view.clone()));
+        view
+    }
+
+    /// One side of the compare view: the hits of a worker on the lines of
+    /// `diff`, blank where only the other worker found the path.
+    fn compare_table(name: &str, diff: &[DiffLine], left: bool, scroll: usize) -> Table<'static> {
+        let rows = diff.iter().skip(scroll).map(|line| {
+            let Some(hit) = (if left { &line.left } else { &line.right }) else {
+                return TableRow::new([String::new(), String::new(), String::new()]);
+            };
+            let row = TableRow::new([
+                hit.status.to_string(),
+                hit.size.map(|size| size.to_string()).unwrap_or_default(),
+                line.path.clone(),
+            ]);
+            if line.is_changed() {
+                row.yellow()
+            } else if line.is_common() {
+                row
+            } else {
+                row.green().bold()
+            }
+        });
+        let header = TableRow::new([
+            tr(Msg::ColumnStatus).to_string(),
+            tr(Msg::ColumnSize).to_string(),
+            name.to_string(),
+        ])
+        .bold();
+
+        Table::new(
+            rows,
+            [
+                Constraint::Length(6),
+                Constraint::Length(8),
+                Constraint::Min(0),
+            ],
+        )
+        .header(header)
+    }
+
+    /// Gives the selected line of the All results view the `triage` verdict,
+    /// or takes it back when it already has it.
+    fn toggle_triage(&mut self, triage: Triage) {
+        let Some((worker, hit)) = self
+            .all_hits()
+            .as_ref()
+            .as_ref()
+            .ok()
+            .and_then(|hits| hits.get(self.results_selected).cloned())
+        else {
+            return;
+        };
+
+        let triage = (hit.triage != Some(triage)).then_some(triage);
+        match triage {
+            Some(triage) => self.triage.insert(hit.url.clone(), triage),
+            None => self.triage.remove(&hit.url),
+        };
+        // Spilled hits aren't annotated, so their verdict only shows once the
+        // view is built again.
+        self.all_hits_view = None;
+        if let Some(state) = self
+            .workers_info_state
+            .iter_mut()
+            .find(|w| w.name() == worker)
+        {
+            state.results.annotate(&hit.url, |hit| hit.triage = triage);
+        }
+    }
+
+    /// Writes the results of a worker that just finished to a timestamped
+    /// file in its [auto-export directory](WorkerState::auto_export_dir).
+    fn auto_export(&mut self, sel: usize) {
+        let state = &self.workers_info_state[sel];
+        let Some(dir) = state.auto_export_dir(self.auto_export.as_deref()) else {
+            return;
+        };
+
+        let path = dir.join(format!(
+            "yadb-{}-{}.jsonl",
+            file_stem(state.name()),
+            Local::now().format("%Y%m%d-%H%M%S")
+        ));
+        let result =
+            std::fs::create_dir_all(&dir).and_then(|()| self.write_export(&[state], &path));
+        self.report_export(&path, result);
+    }
+
+    /// Writes what the All results view shows to a JSON Lines file.
+    fn export_all_results(&mut self) {
+        let path = PathBuf::from("yadb-all.jsonl");
+        let result = self.write_export(&self.listed_workers(), &path);
+        self.report_export(&path, result);
+    }
+
+    fn report_export(&mut self, path: &Path, result: std::io::Result<usize>) {
+        match result {
+            Ok(count) => {
+                self.notice = Some(tr_args(
+                    Msg::Exported,
+                    &[("count", &count), ("path", &path.display())],
+                ))
+            }
+            Err(err) => {
+                self.error = Some(tr_args(
+                    Msg::ExportFailed,
+                    &[("path", &path.display()), ("err", &err)],
+                ))
+            }
+        }
+    }
+
+    fn write_export(&self, members: &[&WorkerState], path: &Path) -> std::io::Result<usize> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        let mut count = 0;
+
+        let names = members.iter().map(|w| w.name()).collect::<BTreeSet<_>>();
+
+        for state in members {
+            if let Some(metadata) = &state.metadata {
+                let line = serde_json::to_string(&ExportedMetadata {
+                    kind: "metadata",
+                    worker: state.name(),
+                    metadata,
+                })?;
+                writeln!(writer, "{line}")?;
+            }
+
+            for mut hit in state.results.iter()? {
+                if found_first_by_other(&hit, &names) {
+                    continue;
+                }
+                hit.triage = self.triage.get(&hit.url).copied();
+
+                let line = serde_json::to_string(&ExportedHit {
+                    worker: state.name(),
+                    hit: &hit,
+                })?;
+                writeln!(writer, "{line}")?;
+                count += 1;
+            }
+        }
+
+        writer.flush()?;
+        Ok(count)
+    }
+
+    /// Workers the filter lets through, collapsed groups included.
+    fn listed_workers(&self) -> Vec<&WorkerState> {
+        let filter = self.filter.value().trim().to_lowercase();
+        self.workers_info_state
+            .iter()
+            .filter(|w| filter.is_empty() || w.matches(&filter))
+            .collect()
+    }
+
+    /// Hits of the [listed workers](Self::listed_workers) with the name of
+    /// the worker, each URL once. Read again only once the listed workers or
+    /// their hits change.
+    fn all_hits(&mut self) -> Rc<HitsView> {
+        let members = self.listed_workers();
+        let key = members
+            .iter()
+            .map(|w| (w.name().to_string(), w.results.revision()))
+            .collect::<Vec<_>>();
+        if let Some((cached, view)) = &self.all_hits_view
+            && *cached == key
+        {
+            return view.clone();
+        }
+
+        let names = members.iter().map(|w| w.name()).collect::<BTreeSet<_>>();
+        let collect = || {
+            let mut hits = Vec::new();
+            for state in &members {
+                for mut hit in state.results.iter()? {
+                    if !found_first_by_other(&hit, &names) {
+                        hit.triage = self.triage.get(&hit.url).copied();
+                        hits.push((state.name().to_string(), hit));
+                    }
+                }
+            }
+            Ok(hits)
+        };
+        let view = Rc::new(collect());
+        self.all_hits_view = Some((key, view.clone()));
+        view
+    }
+
+    fn all_results_table(&self, hits: &[(String, HitResult)]) -> Table<'static> {
+        let rows =
+            hits.iter()
+                .enumerate()
+                .skip(self.results_scroll)
+                .map(|(index, (worker, hit))| {
+                    let style = match hit.triage {
+                        Some(Triage::Interesting) => Style::new().yellow(),
+                        Some(Triage::FalsePositive | Triage::Done) => Style::new().dark_gray(),
+                        None => Style::new(),
+                    };
+                    let style = if index == self.results_selected {
+                        style.reversed()
+                    } else {
+                        style
+                    };
+                    TableRow::new([
+                        worker.clone(),
+                        hit.status.to_string(),
+                        hit.size.map(|size| size.to_string()).unwrap_or_default(),
+                        hit.triage.map(triage_label).unwrap_or_default().to_string(),
+                        match &hit.title {
+                            Some(title) => format!("{} [{title}]", hit.url),
+                            None => hit.url.clone(),
+                        },
+                    ])
+                    .style(style)
+                });
+        let header = TableRow::new([
+            tr(Msg::ColumnWorker),
+            tr(Msg::ColumnStatus),
+            tr(Msg::ColumnSize),
+            tr(Msg::ColumnTriage),
+            tr(Msg::ColumnUrl),
+        ])
+        .bold();
+
+        Table::new(
+            rows,
+            [
+                Constraint::Length(16),
+                Constraint::Length(6),
+                Constraint::Length(8),
+                Constraint::Length(14),
+                Constraint::Min(0),
+            ],
+        )
+        .header(header)
+    }
+
+    /// Plain-text state of every worker, see [`WorkerState::dump`].
+    pub fn dump(&self) -> String {
+        self.workers_info_state
+            .iter()
+            .map(WorkerState::dump)
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    /// Error met while restoring the session.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Writes the selected worker, or every worker of the selected group, as
+    /// plain text to a file.
+    fn dump_selection(&mut self) {
+        let (name, text) = match self.selection.clone() {
+            Some(Row::Worker(sel)) => {
+                let state = &self.workers_info_state[sel];
+                (state.name().to_string(), state.dump())
+            }
+            Some(Row::Group(group)) => {
+                let text = self
+                    .workers_info_state
+                    .iter()
+                    .filter(|w| w.group() == group)
+                    .map(WorkerState::dump)
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+                (group, text)
+            }
+            None => return,
+        };
+
+        let path = PathBuf::from(format!("yadb-{}.txt", file_stem(&name)));
+        match std::fs::write(&path, text + "\n") {
+            Ok(()) => {
+                self.notice = Some(tr_args(Msg::Dumped, &[("path", &path.display())]));
+            }
+            Err(err) => {
+                self.error = Some(tr_args(
+                    Msg::ExportFailed,
+                    &[("path", &path.display()), ("err", &err)],
+                ))
+            }
+        }
+    }
+
+    /// Adds a worker targeting the URL in the clipboard and focuses its wordlist field.
+    fn add_worker_from_clipboard(&mut self) {
+        let url = clipboard::read_text()
+            .map(|text| text.trim().to_string())
+            .and_then(|text| Url::parse(&text).ok())
+            .filter(|url| matches!(url.scheme(), "http" | "https"));
+
+        let Some(url) = url else {
+            self.error = Some(tr(Msg::NoUrlInClipboard).to_string());
+            return;
+        };
+
+        self.add_worker();
+        let sel = self.workers_info_state.len() - 1;
+        let state = &mut self.workers_info_state[sel];
+        state.fields_states[FieldName::Uri.index()].set(url.as_str());
+        state.select(Selection::Field(FieldName::WordlistPath));
+        self.follow_host(sel);
+
+        self.selection = Some(Row::Worker(sel));
+        self.current_window = CurrentWindow::Info;
+    }
+
+    /// Offers the readable templates, or explains why there are none.
+    fn open_template_picker(&mut self) {
+        let templates = match ScanTemplate::load_all() {
+            Ok(templates) => templates,
+            Err(err) => {
+                self.error = Some(format!("{err:#}"));
+                return;
+            }
+        };
+
+        let mut errors = Vec::new();
+        let templates = templates
+            .into_iter()
+            .filter_map(|(_, template)| template.map_err(|err| errors.push(err)).ok())
+            .collect::<Vec<_>>();
+
+        if let Some(err) = errors.first() {
+            self.error = Some(format!("{err:#}"));
+        }
+
+        if templates.is_empty() {
+            if self.error.is_none() {
+                let dir = ScanTemplate::dir().unwrap_or_default();
+                self.notice = Some(tr_args(Msg::NoTemplates, &[("dir", &dir.display())]));
+            }
+            return;
+        }
+
+        self.template_picker = Some(TemplatePicker {
+            templates,
+            selected: 0,
+        });
+    }
+
+    /// Adds a worker set up from `template` and focuses its URI field.
+    fn add_worker_from_template(&mut self, template: &ScanTemplate) {
+        self.add_worker();
+        let sel = self.workers_info_state.len() - 1;
+        let state = &mut self.workers_info_state[sel];
+        state.fields_states[FieldName::Template.index()].set(&template.name);
+        if let Some(wordlist) = &template.wordlist {
+            state.fields_states[FieldName::WordlistPath.index()].set(&wordlist.to_string_lossy());
+        }
+        state.select(Selection::Field(FieldName::Uri));
+
+        self.selection = Some(Row::Worker(sel));
+        self.current_window = CurrentWindow::Info;
+    }
+
     fn handle_worker_info_keys(&mut self, key: KeyEvent) {
-        if let Some(sel) = self.worker_list_state.selected() {
+        if let Some(sel) = self.selected_worker() {
             let worker_state = &mut self.workers_info_state[sel];
             match (key.modifiers, key.code) {
                 (_, KeyCode::Char('h')) => {
                     self.show_help_popup = !self.show_help_popup;
                 }
                 (_, KeyCode::Tab | KeyCode::Left) => self.switch_window(),
+                (_, KeyCode::Char('p')) => self.toggle_pause(sel),
                 (_, KeyCode::Down) => worker_state.set_next_selection(),
                 (_, KeyCode::Up) => worker_state.set_previous_selection(),
                 (_, KeyCode::Enter) => {
-                    if self.builder_error.is_some() || self.show_help_popup {
+                    if self.error.is_some() || self.show_help_popup {
                         self.close_all_popups();
                         return;
                     };
@@ -360,58 +1468,80 @@ impl App {
             };
 
             if self.workers_info_state[sel].do_build
-                && let WorkerType::Builder(builder) = &mut self.workers[sel].worker_type
+                && let Err(err) = self.start_worker(sel)
             {
-                let builder_clone = builder
-                    .clone()
-                    .recursive(
-                        self.workers_info_state[sel].fields_states[FieldName::Recursion.index()]
-                            .get()
-                            .parse()
-                            .unwrap(),
-                    )
-                    .threads(
-                        self.workers_info_state[sel].fields_states[FieldName::Threads.index()]
-                            .get()
-                            .parse()
-                            .unwrap(),
-                    )
-                    .timeout(
-                        self.workers_info_state[sel].fields_states[FieldName::Timeout.index()]
-                            .get()
-                            .parse()
-                            .unwrap(),
-                    )
-                    .uri(self.workers_info_state[sel].fields_states[FieldName::Uri.index()].get())
-                    .wordlist(
-                        self.workers_info_state[sel].fields_states[FieldName::WordlistPath.index()]
-                            .get(),
-                    )
-                    .proxy_url(
-                        self.workers_info_state[sel].fields_states[FieldName::ProxyUrl.index()]
-                            .get(),
-                    );
+                self.error = Some(err);
+                self.workers_info_state[sel].do_build = false;
+            }
+        }
+    }
 
-                let worker_result = builder_clone.build();
-                match worker_result {
-                    Ok(worker) => {
-                        self.workers[sel].worker_type = WorkerType::Worker;
-                        thread::spawn(move || worker.run());
-                        self.workers_info_state[sel].worker = WorkerVariant::Worker(false);
-                    }
-                    Err(err) => {
-                        self.builder_error = Some(err.clone());
-                        self.workers_info_state[sel].do_build = false;
-                    }
+    /// Builds the worker at `sel` from its form and spawns it.
+    fn start_worker(&mut self, sel: usize) -> std::result::Result<(), String> {
+        let WorkerType::Builder(builder) = &self.workers[sel].worker_type else {
+            return Ok(());
+        };
+        let state = &mut self.workers_info_state[sel];
+        for field_state in &mut state.fields_states {
+            field_state.is_invalid = false;
+        }
+
+        let template = state.fields_states[FieldName::Template.index()]
+            .get()
+            .trim();
+        let mut builder = if template.is_empty() {
+            builder.as_ref().clone()
+        } else {
+            ScanTemplate::find(template)
+                .map_err(|err| format!("{err:#}"))?
+                .apply(builder.as_ref().clone())
+        };
+        builder.validate().map_err(|err| err.to_string())?;
+
+        for field in [
+            FieldName::Recursion,
+            FieldName::Threads,
+            FieldName::Timeout,
+            FieldName::RateLimit,
+            FieldName::Method,
+            FieldName::StatusCodes,
+            FieldName::Uri,
+            FieldName::WordlistPath,
+            FieldName::ProxyUrl,
+            FieldName::ResumeFile,
+        ] {
+            builder = match state.apply(builder, field) {
+                Ok(builder) => builder,
+                Err(err) => {
+                    state.fields_states[field.index()].is_invalid = true;
+                    state.select(Selection::Field(field));
+                    return Err(err.to_string());
                 }
-            }
+            };
+        }
+
+        if self.usage_stats
+            && let Some(path) = UsageStats::path()
+        {
+            builder = builder.output_sink(StatsSink::new(path));
         }
+
+        let worker = builder
+            .max_stored_hits(self.retention.max_stored_hits)
+            .max_body_size(self.retention.max_body_size)
+            .build()
+            .map_err(|err| err.to_string())?;
+
+        self.workers[sel].worker_type = WorkerType::Worker(worker.spawn());
+        self.workers_info_state[sel].worker = WorkerVariant::Worker(false);
+        Ok(())
     }
+
     fn handle_editing_input(&mut self, key: KeyEvent) {
         match self.current_window {
-            CurrentWindow::Workers => todo!(),
+            CurrentWindow::Workers => self.handle_filter_input(key),
             CurrentWindow::Info => {
-                if let Some(sel) = self.worker_list_state.selected() {
+                if let Some(sel) = self.selected_worker() {
                     let state = &mut self.workers_info_state[sel];
                     if let Selection::Field(f) = state.selection {
                         let field_state = &mut state.fields_states[f.index()];
@@ -481,6 +1611,9 @@ impl App {
                             (_, KeyCode::Esc | KeyCode::Enter) => {
                                 state.switch_field_editing(f);
                                 self.switch_input_mode();
+                                if matches!(f, FieldName::Uri | FieldName::Name) {
+                                    self.follow_host(sel);
+                                }
                             }
                             _ => {}
                         };
@@ -490,9 +1623,33 @@ impl App {
         };
     }
 
+    /// Edits the Workers filter; Enter keeps it, Esc clears it.
+    fn handle_filter_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char(c) => {
+                self.filter.handle(InputRequest::InsertChar(c));
+            }
+            KeyCode::Backspace => {
+                self.filter.handle(InputRequest::DeletePrevChar);
+            }
+            KeyCode::Down => self.move_selection(true),
+            KeyCode::Up => self.move_selection(false),
+            KeyCode::Enter => self.input_mode = InputMode::Normal,
+            KeyCode::Esc => {
+                self.filter.reset();
+                self.input_mode = InputMode::Normal;
+            }
+            _ => {}
+        }
+        self.keep_selection_visible();
+    }
+
     fn switch_window(&mut self) {
         match self.current_window {
-            CurrentWindow::Workers => self.current_window = CurrentWindow::Info,
+            CurrentWindow::Workers => {
+                self.current_window = CurrentWindow::Info;
+                self.all_results = false;
+            }
             CurrentWindow::Info => self.current_window = CurrentWindow::Workers,
         }
     }
@@ -500,24 +1657,39 @@ impl App {
     fn render_help_popup(&mut self, frame: &mut Frame) {
         let help_message = match self.current_window {
             CurrentWindow::Workers => Text::from(vec![
-                "<TAB> / <LEFT> / <RIGHT>".bold().blue() + " - Switch Tabs".into(),
-                "<a>".bold().blue() + " - Add Worker".into(),
-                "<d>".bold().blue() + " - Delete Worker".into(),
-                "<Enter>".bold().blue() + " - Start/Stop worker".into(),
+                help("<TAB> / <LEFT> / <RIGHT>", Msg::HelpSwitchTabs),
+                help("<a>", Msg::HelpAddWorker),
+                help("<A>", Msg::HelpAddFromClipboard),
+                help("<t>", Msg::HelpTemplate),
+                help("</>", Msg::HelpFilter),
+                help("<d>", Msg::HelpDeleteWorker),
+                help("<Enter> / <s>", Msg::HelpStartStop),
+                help("<Enter>", Msg::HelpCollapse),
+                help("<s>", Msg::HelpStartGroup),
+                help("<e>", Msg::HelpExportGroup),
+                help("<w>", Msg::HelpDump),
+                help("<r>", Msg::HelpAllResults),
+                help("<UP> / <DOWN> / <PgUp> / <PgDn>", Msg::HelpScrollResults),
+                help("<i> / <f> / <x>", Msg::HelpTriage),
+                help("<c>", Msg::HelpCompare),
+                help("<Space>", Msg::HelpMark),
+                help("<Esc>", Msg::HelpClearMarks),
+                help("<S> / <X> / <D>", Msg::HelpMarked),
             ]),
             CurrentWindow::Info => Text::from(vec![
-                " <TAB> / <LEFT> / <RIGHT>".bold().blue() + " - Switch tabs".into(),
-                " <UP> / <DOWN>".bold().blue() + " - Move focus".into(),
-                " <Enter>".bold().blue() + " - Edit property or press button".into(),
+                help(" <TAB> / <LEFT> / <RIGHT>", Msg::HelpSwitchTabs),
+                help(" <UP> / <DOWN>", Msg::HelpMoveFocus),
+                help(" <Enter>", Msg::HelpEdit),
+                help(" <p>", Msg::HelpPause),
             ]),
         };
-        let popup = Popup::new(" Help ".to_string(), help_message);
+        let popup = Popup::new(format!(" {} ", tr(Msg::HelpTitle)), help_message);
         frame.render_widget(popup, frame.area());
     }
 
-    fn render_error_popup(&mut self, frame: &mut Frame, err: BuilderError) {
-        let error_message = Text::from(err.to_string());
-        let popup = Popup::new(" Error ".to_string(), error_message);
+    fn render_error_popup(&mut self, frame: &mut Frame, err: String) {
+        let error_message = Text::from(err);
+        let popup = Popup::new(format!(" {} ", tr(Msg::ErrorTitle)), error_message);
 
         frame.render_widget(popup, frame.area());
     }
@@ -530,7 +1702,8 @@ impl App {
     }
 
     fn close_all_popups(&mut self) {
-        self.builder_error = None;
+        self.error = None;
+        self.notice = None;
         self.show_help_popup = false;
     }
 
@@ -539,3 +1712,42 @@ impl App {
         self.running = false;
     }
 }
+
+/// Translated name of `triage`.
+fn triage_label(triage: Triage) -> &'static str {
+    tr(match triage {
+        Triage::Interesting => Msg::TriageInteresting,
+        Triage::FalsePositive => Msg::TriageFalsePositive,
+        Triage::Done => Msg::TriageDone,
+    })
+}
+
+/// `hit` was reported earlier by another of the workers called `names`.
+fn found_first_by_other(hit: &HitResult, names: &BTreeSet<&str>) -> bool {
+    hit.first_found_by
+        .as_deref()
+        .is_some_and(|first| names.contains(first))
+}
+
+/// `worker-1`, `worker-2`, … for workers without a target host to be named after.
+fn default_names() -> impl Iterator<Item = String> {
+    (1..).map(|n| format!("worker-{n}"))
+}
+
+/// A line of a help popup: the key and what it does.
+fn help(key: &'static str, msg: Msg) -> Line<'static> {
+    key.bold().blue() + format!(" - {}", tr(msg)).into()
+}
+
+/// `name` with everything but letters, digits, `-` and `_` replaced, for file names.
+fn file_stem(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,27 @@
+use std::process::Command;
+
+/// Programs printing the clipboard contents, tried in order.
+#[cfg(target_os = "macos")]
+const PASTE_COMMANDS: &[(&str, &[&str])] = &[("pbpaste", &[])];
+
+#[cfg(target_os = "windows")]
+const PASTE_COMMANDS: &[(&str, &[&str])] =
+    &[("powershell", &["-NoProfile", "-Command", "Get-Clipboard"])];
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const PASTE_COMMANDS: &[(&str, &[&str])] = &[
+    ("wl-paste", &["--no-newline"]),
+    ("xclip", &["-selection", "clipboard", "-o"]),
+    ("xsel", &["--clipboard", "--output"]),
+];
+
+/// Text currently in the system clipboard, if any tool could read it.
+pub fn read_text() -> Option<String> {
+    PASTE_COMMANDS.iter().find_map(|(program, args)| {
+        let output = Command::new(program).args(*args).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        String::from_utf8(output.stdout).ok()
+    })
+}
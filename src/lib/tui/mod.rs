@@ -1,2 +1,5 @@
 pub mod app;
+mod clipboard;
+mod session;
+pub mod settings;
 mod widgets;
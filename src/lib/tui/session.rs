@@ -0,0 +1,142 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::lib::{
+    i18n::Lang,
+    util,
+    worker::{hit::Triage, store::DEFAULT_MAX_STORED_HITS, unit::DEFAULT_MAX_BODY_SIZE},
+};
+
+const SESSION_FILE: &str = "tui.json";
+
+pub const DEFAULT_LOG_LINES: usize = 5;
+pub const DEFAULT_MESSAGES: usize = 20;
+
+/// How much of every scan the TUI keeps around.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Retention {
+    /// Log lines shown per worker.
+    pub log_lines: usize,
+    /// Recent requests and hits shown per worker.
+    pub messages: usize,
+    /// Hits kept in memory per worker; the rest are spilled to disk.
+    pub max_stored_hits: usize,
+    /// Bytes read of each response body.
+    pub max_body_size: u64,
+}
+
+impl Default for Retention {
+    fn default() -> Self {
+        Self {
+            log_lines: DEFAULT_LOG_LINES,
+            messages: DEFAULT_MESSAGES,
+            max_stored_hits: DEFAULT_MAX_STORED_HITS,
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+        }
+    }
+}
+
+/// A line of the Workers list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Row {
+    Group(String),
+    Worker(usize),
+}
+
+/// Lays out the Workers list: ungrouped workers first, then every group in
+/// order of its first worker. Workers of collapsed groups are left out, and
+/// so are workers `visible` rejects along with groups left without any.
+pub fn rows<'a>(
+    groups: impl Iterator<Item = &'a str>,
+    collapsed: &BTreeSet<String>,
+    visible: impl Fn(usize) -> bool,
+) -> Vec<Row> {
+    let mut ungrouped = Vec::new();
+    let mut grouped: Vec<(&str, Vec<usize>)> = Vec::new();
+
+    for (ind, group) in groups.enumerate() {
+        if !visible(ind) {
+            continue;
+        }
+
+        if group.is_empty() {
+            ungrouped.push(Row::Worker(ind));
+        } else if let Some((_, workers)) = grouped.iter_mut().find(|(name, _)| *name == group) {
+            workers.push(ind);
+        } else {
+            grouped.push((group, vec![ind]));
+        }
+    }
+
+    let mut rows = ungrouped;
+    for (group, workers) in grouped {
+        rows.push(Row::Group(group.to_string()));
+        if !collapsed.contains(group) {
+            rows.extend(workers.into_iter().map(Row::Worker));
+        }
+    }
+    rows
+}
+
+/// What the TUI remembers between runs: worker forms, collapsed groups, the
+/// triage of hits and the interface language.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Session {
+    /// Interface language; the environment decides when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lang: Option<Lang>,
+    #[serde(default)]
+    pub collapsed: BTreeSet<String>,
+    /// Report a URL found by several workers only for the first of them.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dedupe_hits: bool,
+    /// Add every scan to the local [usage statistics](crate::lib::output::stats::UsageStats).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub usage_stats: bool,
+    #[serde(default)]
+    pub retention: Retention,
+    /// Directory every finished worker writes its results to, unless its
+    /// form names another.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_export: Option<PathBuf>,
+    /// Form values of every worker, keyed by field.
+    #[serde(default)]
+    pub workers: Vec<BTreeMap<String, String>>,
+    /// Verdicts given to hits, keyed by URL; applied again when a scan finds
+    /// the URL once more.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub triage: BTreeMap<String, Triage>,
+}
+
+impl Session {
+    /// `tui.json` in the [config directory](util::config_dir).
+    pub fn path() -> Option<PathBuf> {
+        Some(util::config_dir()?.join(SESSION_FILE))
+    }
+
+    /// Reads a saved session; a missing file is an empty session.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(data) => serde_json::from_str(&data)
+                .with_context(|| format!("Invalid session file {}", path.display())),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err).with_context(|| format!("Can't read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("Can't create {}", dir.display()))?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Can't write {}", path.display()))
+    }
+}
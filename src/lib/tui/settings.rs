@@ -0,0 +1,133 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::lib::{
+    i18n::Lang,
+    output::webhook::PayloadTemplates,
+    template::ScanTemplate,
+    tui::session::{Retention, Session},
+};
+
+/// A tuned setup in one TOML file, to move between machines or keep in a
+/// dotfiles repository: the TUI's options, the scan templates and the
+/// webhook payload templates. Worker forms and triage stay behind.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub lang: Option<Lang>,
+    pub dedupe_hits: bool,
+    pub usage_stats: bool,
+    pub auto_export: Option<PathBuf>,
+    pub retention: Retention,
+    /// Source of every scan template, keyed by file name.
+    pub templates: BTreeMap<String, String>,
+    /// Source of `webhooks.toml`.
+    pub webhooks: Option<String>,
+}
+
+impl Settings {
+    /// Collects the settings in use.
+    pub fn current() -> Result<Self> {
+        let session = match Session::path() {
+            Some(path) => Session::load(&path)?,
+            None => Session::default(),
+        };
+
+        let mut templates = BTreeMap::new();
+        for (path, _) in ScanTemplate::load_all()? {
+            let Some(name) = path.file_name() else {
+                continue;
+            };
+            let source = fs::read_to_string(&path)
+                .with_context(|| format!("Can't read {}", path.display()))?;
+            templates.insert(name.to_string_lossy().into_owned(), source);
+        }
+
+        let webhooks = match PayloadTemplates::path() {
+            Some(path) => read_optional(&path)?,
+            None => None,
+        };
+
+        Ok(Self {
+            lang: session.lang,
+            dedupe_hits: session.dedupe_hits,
+            usage_stats: session.usage_stats,
+            auto_export: session.auto_export,
+            retention: session.retention,
+            templates,
+            webhooks,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let data =
+            fs::read_to_string(path).with_context(|| format!("Can't read {}", path.display()))?;
+        toml::from_str(&data).with_context(|| format!("Invalid settings file {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, toml::to_string_pretty(self)?)
+            .with_context(|| format!("Can't write {}", path.display()))
+    }
+
+    /// Makes these the settings in use. Options are replaced in the saved
+    /// session, which keeps its workers; templates are written over the ones
+    /// of the same name and others are left alone.
+    pub fn apply(&self) -> Result<()> {
+        for (name, source) in &self.templates {
+            if Path::new(name).file_name() != Some(name.as_ref()) || !name.ends_with(".toml") {
+                bail!("Invalid template file name {name}");
+            }
+            toml::from_str::<ScanTemplate>(source)
+                .with_context(|| format!("Invalid template {name}"))?;
+        }
+        if let Some(webhooks) = &self.webhooks {
+            toml::from_str::<PayloadTemplates>(webhooks).context("Invalid webhook templates")?;
+        }
+
+        let no_config_dir = || anyhow!("No config directory");
+
+        let session_path = Session::path().ok_or_else(no_config_dir)?;
+        let mut session = Session::load(&session_path)?;
+        session.lang = self.lang;
+        session.dedupe_hits = self.dedupe_hits;
+        session.usage_stats = self.usage_stats;
+        session.auto_export = self.auto_export.clone();
+        session.retention = self.retention;
+        session.save(&session_path)?;
+
+        if !self.templates.is_empty() {
+            let dir = ScanTemplate::dir().ok_or_else(no_config_dir)?;
+            fs::create_dir_all(&dir).with_context(|| format!("Can't create {}", dir.display()))?;
+            for (name, source) in &self.templates {
+                let path = dir.join(name);
+                fs::write(&path, source)
+                    .with_context(|| format!("Can't write {}", path.display()))?;
+            }
+        }
+
+        if let Some(webhooks) = &self.webhooks {
+            let path = PayloadTemplates::path().ok_or_else(no_config_dir)?;
+            fs::write(&path, webhooks)
+                .with_context(|| format!("Can't write {}", path.display()))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Contents of a file, `None` when it doesn't exist.
+fn read_optional(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(data) => Ok(Some(data)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err).with_context(|| format!("Can't read {}", path.display())),
+    }
+}
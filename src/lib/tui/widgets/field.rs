@@ -20,6 +20,8 @@ pub struct FieldState {
     pub is_selected: bool,
     pub is_editing: bool,
     pub is_only_numbers: bool,
+    /// The value was rejected the last time the worker was started.
+    pub is_invalid: bool,
     pub field_type: FieldType,
 }
 
@@ -35,6 +37,7 @@ impl FieldState {
             is_selected,
             is_editing: false,
             is_only_numbers,
+            is_invalid: false,
             field_type,
         }
     }
@@ -42,6 +45,10 @@ impl FieldState {
     pub fn get(&self) -> &str {
         self.input.value()
     }
+
+    pub fn set(&mut self, value: &str) {
+        self.input = Input::new(value.to_string());
+    }
 }
 
 pub struct Field<'a> {
@@ -62,17 +69,15 @@ impl StatefulWidget for Field<'_> {
 
         let scroll = state.input.visual_scroll(layout[0].width as usize);
         let mut input = Paragraph::new(state.input.value())
-            .block(
-                Block::bordered()
-                    .title(self.title)
-                    .border_style(if state.is_editing {
-                        Style::default().red()
-                    } else if state.is_selected {
-                        Style::default().blue()
-                    } else {
-                        Style::default()
-                    }),
-            )
+            .block(Block::bordered().title(self.title).border_style(
+                if state.is_editing || state.is_invalid {
+                    Style::default().red()
+                } else if state.is_selected {
+                    Style::default().blue()
+                } else {
+                    Style::default()
+                },
+            ))
             .scroll((0, scroll as u16));
 
         if state.is_editing {
@@ -1,26 +1,55 @@
-use std::collections::VecDeque;
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
+use clap::ValueEnum;
 use ratatui::{
     layout::{self, Constraint, Flex, Layout, Rect},
     style::{Style, Stylize},
     text::{Line, Text},
     widgets::{Block, Gauge, Paragraph, StatefulWidget, Widget},
 };
+use url::Url;
 
 use crate::lib::{
+    i18n::{Msg, tr, tr_args},
+    logger::traits::LogLevel,
+    progress_handler::{
+        rate::RateWindow,
+        traits::{ProgressHandler, ProgressKind},
+    },
+    report::{ScanMetadata, ScanSummary},
     tui::{
-        app::{LOG_MAX, MESSAGES_MAX},
+        session::Retention,
         widgets::{
             field::{Field, FieldState, FieldType},
             path_hint::PathHintState,
         },
     },
-    worker::builder::{DEFAULT_RECURSIVE_MODE, DEFAULT_THREADS_NUMBER, DEFAULT_TIMEOUT},
+    util,
+    worker::{
+        builder::{
+            BuilderError, DEFAULT_RECURSIVE_MODE, DEFAULT_THREADS_NUMBER, DEFAULT_TIMEOUT,
+            WorkerBuilder,
+        },
+        canary::{CanaryAlert, CanaryChange},
+        config::HttpMethod,
+        headers::HeaderFinding,
+        hit::{HitResult, Verification},
+        scheduler::{SchedulerSnapshot, SchedulerState},
+        store::{ResultStore, StoreLimits},
+        tls::TlsInfo,
+        waf::WafVendor,
+    },
 };
 
 #[derive(Debug, Default, Clone)]
 pub enum WorkerVariant {
     Worker(bool),
+    /// The scan ended with an error or a panic.
+    Failed,
     #[default]
     Builder,
 }
@@ -29,70 +58,164 @@ pub enum WorkerVariant {
 pub enum FieldName {
     #[default]
     Name = 0,
-    Uri = 1,
-    Threads = 2,
-    Recursion = 3,
-    Timeout = 4,
-    WordlistPath = 5,
-    ProxyUrl = 6,
+    Group = 1,
+    Template = 2,
+    Uri = 3,
+    Threads = 4,
+    Recursion = 5,
+    Timeout = 6,
+    RateLimit = 7,
+    Method = 8,
+    StatusCodes = 9,
+    WordlistPath = 10,
+    ProxyUrl = 11,
+    ResumeFile = 12,
+    AutoExport = 13,
 }
 
 impl FieldName {
     pub fn index(self) -> usize {
         match self {
             FieldName::Name => 0,
-            FieldName::Uri => 1,
-            FieldName::Threads => 2,
-            FieldName::Recursion => 3,
-            FieldName::Timeout => 4,
-            FieldName::WordlistPath => 5,
-            FieldName::ProxyUrl => 6,
+            FieldName::Group => 1,
+            FieldName::Template => 2,
+            FieldName::Uri => 3,
+            FieldName::Threads => 4,
+            FieldName::Recursion => 5,
+            FieldName::Timeout => 6,
+            FieldName::RateLimit => 7,
+            FieldName::Method => 8,
+            FieldName::StatusCodes => 9,
+            FieldName::WordlistPath => 10,
+            FieldName::ProxyUrl => 11,
+            FieldName::ResumeFile => 12,
+            FieldName::AutoExport => 13,
+        }
+    }
+
+    /// Stable name used when the field is saved to disk.
+    pub fn key(self) -> &'static str {
+        match self {
+            FieldName::Name => "name",
+            FieldName::Group => "group",
+            FieldName::Template => "template",
+            FieldName::Uri => "uri",
+            FieldName::Threads => "threads",
+            FieldName::Recursion => "recursion",
+            FieldName::Timeout => "timeout",
+            FieldName::RateLimit => "rate_limit",
+            FieldName::Method => "method",
+            FieldName::StatusCodes => "status_codes",
+            FieldName::WordlistPath => "wordlist_path",
+            FieldName::ProxyUrl => "proxy_url",
+            FieldName::ResumeFile => "resume_file",
+            FieldName::AutoExport => "auto_export",
         }
     }
 
+    /// Translated name shown to the user.
+    pub fn label(self) -> &'static str {
+        tr(match self {
+            FieldName::Name => Msg::FieldName,
+            FieldName::Group => Msg::FieldGroup,
+            FieldName::Template => Msg::FieldTemplate,
+            FieldName::Uri => Msg::FieldUri,
+            FieldName::Threads => Msg::FieldThreads,
+            FieldName::Recursion => Msg::FieldRecursion,
+            FieldName::Timeout => Msg::FieldTimeout,
+            FieldName::RateLimit => Msg::FieldRateLimit,
+            FieldName::Method => Msg::FieldMethod,
+            FieldName::StatusCodes => Msg::FieldStatusCodes,
+            FieldName::WordlistPath => Msg::FieldWordlist,
+            FieldName::ProxyUrl => Msg::FieldProxy,
+            FieldName::ResumeFile => Msg::FieldResumeFile,
+            FieldName::AutoExport => Msg::FieldAutoExport,
+        })
+    }
+
     pub fn next(self) -> FieldName {
         match self {
-            FieldName::Name => FieldName::Uri,
+            FieldName::Name => FieldName::Group,
+            FieldName::Group => FieldName::Template,
+            FieldName::Template => FieldName::Uri,
             FieldName::Uri => FieldName::Threads,
             FieldName::Threads => FieldName::Recursion,
             FieldName::Recursion => FieldName::Timeout,
-            FieldName::Timeout => FieldName::WordlistPath,
+            FieldName::Timeout => FieldName::RateLimit,
+            FieldName::RateLimit => FieldName::Method,
+            FieldName::Method => FieldName::StatusCodes,
+            FieldName::StatusCodes => FieldName::WordlistPath,
             FieldName::WordlistPath => FieldName::ProxyUrl,
-            FieldName::ProxyUrl => FieldName::Name,
+            FieldName::ProxyUrl => FieldName::ResumeFile,
+            FieldName::ResumeFile => FieldName::AutoExport,
+            FieldName::AutoExport => FieldName::Name,
         }
     }
 
     pub fn previous(self) -> FieldName {
         match self {
-            FieldName::Name => FieldName::ProxyUrl,
-            FieldName::Uri => FieldName::Name,
+            FieldName::Name => FieldName::AutoExport,
+            FieldName::Group => FieldName::Name,
+            FieldName::Template => FieldName::Group,
+            FieldName::Uri => FieldName::Template,
             FieldName::Threads => FieldName::Uri,
             FieldName::Recursion => FieldName::Threads,
             FieldName::Timeout => FieldName::Recursion,
-            FieldName::WordlistPath => FieldName::Timeout,
+            FieldName::RateLimit => FieldName::Timeout,
+            FieldName::Method => FieldName::RateLimit,
+            FieldName::StatusCodes => FieldName::Method,
+            FieldName::WordlistPath => FieldName::StatusCodes,
             FieldName::ProxyUrl => FieldName::WordlistPath,
+            FieldName::ResumeFile => FieldName::ProxyUrl,
+            FieldName::AutoExport => FieldName::ResumeFile,
         }
     }
 
     pub fn is_first(self) -> bool {
-        self == FieldName::Name
+        self == FIELDS[0]
     }
 
     pub fn is_last(self) -> bool {
-        self == FieldName::ProxyUrl
+        self == FIELDS[FIELDS_NUMBER - 1]
     }
 }
 
-const FIELDS_NUMBER: usize = 7;
+const FIELDS_NUMBER: usize = 14;
+
+/// Rows of a form field, and of the wordlist field while its hint is open.
+const FIELD_HEIGHT: u16 = 3;
+const HINT_FIELD_HEIGHT: u16 = 7;
+/// Rows of the Arguments pane: five arguments and the borders.
+const ARGUMENTS_HEIGHT: u16 = 7;
+/// Rows of the bars under the results of a running worker.
+const BAR_HEIGHT: u16 = 3;
+/// A running worker shows the Arguments pane, at least one line of results
+/// and three bars.
+const RUNNING_HEIGHT: u16 = ARGUMENTS_HEIGHT + 3 + 3 * BAR_HEIGHT;
 
-const NAMES: [&str; FIELDS_NUMBER] = [
-    " Name ",
-    " URI ",
-    " Threads ",
-    " Recursion depth ",
-    " Max timeout ",
-    " Wordlist path ",
-    " Proxy URL ",
+/// Smallest area a worker is drawn in without squeezing anything. The form
+/// scrolls to the selected field, so it only needs room for the tallest one.
+pub const MIN_HEIGHT: u16 = if RUNNING_HEIGHT > HINT_FIELD_HEIGHT {
+    RUNNING_HEIGHT
+} else {
+    HINT_FIELD_HEIGHT
+};
+
+pub const FIELDS: [FieldName; FIELDS_NUMBER] = [
+    FieldName::Name,
+    FieldName::Group,
+    FieldName::Template,
+    FieldName::Uri,
+    FieldName::Threads,
+    FieldName::Recursion,
+    FieldName::Timeout,
+    FieldName::RateLimit,
+    FieldName::Method,
+    FieldName::StatusCodes,
+    FieldName::WordlistPath,
+    FieldName::ProxyUrl,
+    FieldName::ResumeFile,
+    FieldName::AutoExport,
 ];
 
 #[derive(Debug, PartialEq)]
@@ -117,7 +240,15 @@ impl Selection {
                 };
                 *self = Selection::Field(field.next());
             }
-            Selection::RunButton => *self = Selection::Field(FieldName::Name),
+            Selection::RunButton => *self = Selection::Field(FIELDS[0]),
+        }
+    }
+
+    /// Row of the form, the Run button being the last one.
+    fn index(&self) -> usize {
+        match self {
+            Selection::Field(field) => field.index(),
+            Selection::RunButton => FIELDS_NUMBER,
         }
     }
 
@@ -130,7 +261,7 @@ impl Selection {
                 }
                 *self = Selection::Field(field.previous());
             }
-            Selection::RunButton => *self = Selection::Field(FieldName::WordlistPath),
+            Selection::RunButton => *self = Selection::Field(FIELDS[FIELDS_NUMBER - 1]),
         }
     }
 }
@@ -140,15 +271,39 @@ pub struct WorkerState {
     pub worker: WorkerVariant,
     pub selection: Selection,
     pub current_parsing: String,
+    /// Pipeline stage being run, for multi-stage scans.
+    pub stage: Option<String>,
     pub log: VecDeque<String>,
     pub messages: VecDeque<String>,
     pub progress_current_total: usize,
     pub progress_current_now: usize,
     pub progress_all_total: usize,
     pub progress_all_now: usize,
+    /// Rate of the total progress, for the ETA.
+    pub rate: RateWindow,
+    pub hidden_static: usize,
+    pub scheduler_state: Option<SchedulerState>,
+    /// Threads sending requests, with adaptive concurrency.
+    pub threads: Option<usize>,
+    /// Latest canary alert, until the canary recovers.
+    pub canary_alert: Option<String>,
+    /// Why the last run gave up early, if it did.
+    pub aborted: Option<String>,
+    /// Every reported hit, kept for exports; spills to disk past the default limits.
+    pub results: ResultStore,
+    /// What the last run of the worker was started with, for exports.
+    pub metadata: Option<ScanMetadata>,
+    /// Picked for a bulk action in the Workers list.
+    pub marked: bool,
+    pub retention: Retention,
+    /// Name the interface gave the worker; while it is still called that,
+    /// the name follows the target host.
+    pub auto_name: Option<String>,
     pub do_build: bool,
     pub fields_states: [FieldState; FIELDS_NUMBER],
     cursor_position: (u16, u16),
+    /// First row of the form shown when it doesn't fit.
+    form_scroll: usize,
 }
 
 impl Default for WorkerState {
@@ -156,8 +311,10 @@ impl Default for WorkerState {
         Self {
             worker: Default::default(),
             cursor_position: Default::default(),
+            form_scroll: 0,
             selection: Default::default(),
             current_parsing: Default::default(),
+            stage: None,
             log: Default::default(),
             messages: Default::default(),
             do_build: Default::default(),
@@ -165,8 +322,21 @@ impl Default for WorkerState {
             progress_current_now: Default::default(),
             progress_all_total: Default::default(),
             progress_all_now: Default::default(),
+            rate: Default::default(),
+            hidden_static: Default::default(),
+            scheduler_state: Default::default(),
+            threads: None,
+            canary_alert: None,
+            aborted: None,
+            results: Default::default(),
+            metadata: Default::default(),
+            marked: Default::default(),
+            auto_name: Default::default(),
+            retention: Default::default(),
             fields_states: [
                 FieldState::new("Unnamed", true, false, FieldType::Normal),
+                FieldState::new("", false, false, FieldType::Normal),
+                FieldState::new("", false, false, FieldType::Normal),
                 FieldState::new("http://localhost", false, false, FieldType::Normal),
                 FieldState::new(
                     DEFAULT_THREADS_NUMBER.to_string().as_str(),
@@ -181,11 +351,19 @@ impl Default for WorkerState {
                     FieldType::Normal,
                 ),
                 FieldState::new(
-                    DEFAULT_TIMEOUT.to_string().as_str(),
+                    util::format_duration(DEFAULT_TIMEOUT).as_str(),
+                    false,
                     false,
-                    true,
                     FieldType::Normal,
                 ),
+                FieldState::new("", false, true, FieldType::Normal),
+                FieldState::new(
+                    HttpMethod::default().as_str(),
+                    false,
+                    false,
+                    FieldType::Normal,
+                ),
+                FieldState::new("", false, false, FieldType::Normal),
                 FieldState::new(
                     "/usr/share",
                     false,
@@ -193,12 +371,25 @@ impl Default for WorkerState {
                     FieldType::Path(PathHintState::default()),
                 ),
                 FieldState::new("", false, false, FieldType::Normal),
+                FieldState::new("", false, false, FieldType::Normal),
+                FieldState::new("", false, false, FieldType::Normal),
             ],
         }
     }
 }
 
 impl WorkerState {
+    pub fn new(retention: Retention) -> Self {
+        Self {
+            retention,
+            results: ResultStore::new(StoreLimits {
+                max_hits: retention.max_stored_hits,
+                ..StoreLimits::default()
+            }),
+            ..Self::default()
+        }
+    }
+
     pub fn set_next_selection(&mut self) {
         if let Selection::Field(f) = self.selection {
             self.fields_states[f.index()].is_selected = false;
@@ -219,14 +410,407 @@ impl WorkerState {
         }
     }
 
+    pub fn select(&mut self, selection: Selection) {
+        if let Selection::Field(f) = self.selection {
+            self.fields_states[f.index()].is_selected = false;
+        }
+        self.selection = selection;
+        if let Selection::Field(f) = self.selection {
+            self.fields_states[f.index()].is_selected = true;
+        }
+    }
+
     pub fn switch_field_editing(&mut self, field: FieldName) {
         let ind = field.index();
         self.fields_states[ind].is_editing = !self.fields_states[ind].is_editing;
+        self.fields_states[ind].is_invalid = false;
     }
 
     pub fn get_cursor_position(&self) -> (u16, u16) {
         self.cursor_position
     }
+
+    pub fn name(&self) -> &str {
+        self.fields_states[FieldName::Name.index()].get()
+    }
+
+    /// Value of a numeric field.
+    pub fn number(&self, field: FieldName) -> Result<usize, BuilderError> {
+        let value = self.fields_states[field.index()].get().trim();
+        value.parse().map_err(|_| BuilderError::InvalidNumber {
+            field: field.label().to_string(),
+            value: value.to_string(),
+        })
+    }
+
+    /// Sets `field` on `builder`, failing with what the field got wrong.
+    pub fn apply(
+        &self,
+        builder: WorkerBuilder,
+        field: FieldName,
+    ) -> Result<WorkerBuilder, BuilderError> {
+        let value = self.fields_states[field.index()].get();
+        let builder = match field {
+            FieldName::Recursion => builder.recursive(self.number(field)?),
+            FieldName::Threads => builder.threads(self.number(field)?),
+            FieldName::Timeout => {
+                builder.timeout(util::parse_duration(value).map_err(BuilderError::InvalidDuration)?)
+            }
+            FieldName::RateLimit => match value.trim() {
+                "" => builder,
+                rate => {
+                    builder.rate_limit(rate.parse().map_err(|_| BuilderError::InvalidNumber {
+                        field: field.label().to_string(),
+                        value: rate.to_string(),
+                    })?)
+                }
+            },
+            FieldName::Method => builder.method(
+                HttpMethod::from_str(value.trim(), true)
+                    .map_err(|_| BuilderError::InvalidMethod(value.to_string()))?,
+            ),
+            FieldName::StatusCodes => {
+                let (included, excluded) =
+                    util::parse_status_filter(value).map_err(BuilderError::InvalidStatus)?;
+                let builder = included
+                    .into_iter()
+                    .fold(builder, WorkerBuilder::status_code);
+                excluded
+                    .into_iter()
+                    .fold(builder, WorkerBuilder::exclude_status)
+            }
+            FieldName::Uri => builder.uri(value),
+            FieldName::WordlistPath => builder.wordlist(value),
+            FieldName::ProxyUrl => builder.proxy_url(value),
+            FieldName::ResumeFile => match value.trim() {
+                "" => builder,
+                path => builder.checkpoint(path),
+            },
+            FieldName::Name | FieldName::Group | FieldName::Template | FieldName::AutoExport => {
+                builder
+            }
+        };
+        builder.validate()?;
+        Ok(builder)
+    }
+
+    /// Gives the worker a generated name, replaced when the target changes.
+    pub fn set_auto_name(&mut self, name: String) {
+        self.fields_states[FieldName::Name.index()].set(&name);
+        self.auto_name = Some(name);
+    }
+
+    /// Still called by its generated name, or not called anything.
+    pub fn is_auto_named(&self) -> bool {
+        let name = self.name().trim();
+        name.is_empty() || self.auto_name.as_deref() == Some(name)
+    }
+
+    /// Group label of the worker; empty when ungrouped.
+    pub fn group(&self) -> &str {
+        self.fields_states[FieldName::Group.index()].get().trim()
+    }
+
+    /// Host of the target, or the URI as typed when it isn't a valid URL.
+    pub fn host(&self) -> String {
+        let uri = self.fields_states[FieldName::Uri.index()].get().trim();
+        Url::parse(uri)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| uri.to_string())
+    }
+
+    /// How the worker scans: recursion depth and the template, if any.
+    pub fn mode(&self) -> String {
+        let depth = self.fields_states[FieldName::Recursion.index()]
+            .get()
+            .trim();
+        let mut mode = match depth.parse::<usize>() {
+            Ok(0) | Err(_) => tr(Msg::ModeFlat).to_string(),
+            Ok(depth) => tr_args(Msg::ModeRecursive, &[("depth", &depth)]),
+        };
+
+        let template = self.fields_states[FieldName::Template.index()].get().trim();
+        if !template.is_empty() {
+            mode = format!("{mode} · {template}");
+        }
+        if let Some(stage) = &self.stage {
+            mode = format!("{mode} · {stage}");
+        }
+        mode
+    }
+
+    /// Directory the results are written to when the worker finishes: the
+    /// one of its form, or `default` when the field is empty.
+    pub fn auto_export_dir(&self, default: Option<&Path>) -> Option<PathBuf> {
+        match self.fields_states[FieldName::AutoExport.index()]
+            .get()
+            .trim()
+        {
+            "" => default.map(Path::to_path_buf),
+            dir => Some(PathBuf::from(dir)),
+        }
+    }
+
+    /// Name or target host contains the lowercase `filter`.
+    pub fn matches(&self, filter: &str) -> bool {
+        self.name().to_lowercase().contains(filter) || self.host().to_lowercase().contains(filter)
+    }
+
+    pub fn is_waiting(&self) -> bool {
+        matches!(self.worker, WorkerVariant::Builder)
+    }
+
+    /// Time left of a running worker at its recent rate, once it is known.
+    pub fn eta(&self) -> Option<String> {
+        if !matches!(self.worker, WorkerVariant::Worker(false)) {
+            return None;
+        }
+        let eta = self
+            .rate
+            .eta(self.progress_all_now as u64, self.progress_all_total as u64)?;
+        Some(tr_args(Msg::Eta, &[("eta", &util::format_duration(eta))]))
+    }
+
+    /// The worker as plain indented text, one fact per line, for screen
+    /// readers and diffs.
+    pub fn dump(&self) -> String {
+        let status = match self.worker {
+            WorkerVariant::Builder => "waiting",
+            WorkerVariant::Worker(false) => "running",
+            WorkerVariant::Worker(true) => "done",
+            WorkerVariant::Failed => "failed",
+        };
+
+        let mut lines = vec![
+            format!("{}: {}", tr(Msg::FieldName), self.name()),
+            format!("  status: {status}"),
+        ];
+        for field in FIELDS.into_iter().skip(1) {
+            let line = format!(
+                "  {}: {}",
+                field.label(),
+                self.fields_states[field.index()].get()
+            );
+            lines.push(line.trim_end().to_string());
+        }
+
+        if !self.is_waiting() {
+            lines.push(format!(
+                "  {}: {}/{}",
+                tr(Msg::TotalProgress),
+                self.progress_all_now,
+                self.progress_all_total
+            ));
+            if !self.current_parsing.is_empty() {
+                lines.push(format!(
+                    "  {}: {}",
+                    tr(Msg::CurrentlyRequesting),
+                    self.current_parsing
+                ));
+            }
+        }
+
+        if !self.log.is_empty() {
+            lines.push(format!("  {}:", tr(Msg::Logs)));
+            lines.extend(self.log.iter().map(|line| format!("    {line}")));
+        }
+
+        lines.push(format!("  {}: {}", tr(Msg::Results), self.results.len()));
+        match self.results.iter() {
+            Ok(hits) => lines.extend(hits.map(|hit| format!("    {hit}"))),
+            Err(err) => lines.push(format!("    {err}")),
+        }
+        if self.hidden_static > 0 {
+            lines.push(format!(
+                "  {}",
+                tr_args(Msg::StaticHidden, &[("count", &self.hidden_static)])
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl ProgressHandler for WorkerState {
+    fn set_message(&mut self, kind: ProgressKind, msg: String) {
+        if kind == ProgressKind::Current {
+            self.current_parsing = msg;
+        }
+    }
+
+    fn set_size(&mut self, kind: ProgressKind, size: usize) {
+        match kind {
+            ProgressKind::Total => self.progress_all_total = size,
+            ProgressKind::Current => {
+                self.progress_current_now = 0;
+                self.progress_current_total = size;
+            }
+        }
+    }
+
+    fn advance(&mut self, kind: ProgressKind) {
+        self.advance_by(kind, 1);
+    }
+
+    fn advance_by(&mut self, kind: ProgressKind, steps: usize) {
+        match kind {
+            ProgressKind::Total => {
+                self.progress_all_now += steps;
+                self.rate.record(self.progress_all_now as u64);
+            }
+            ProgressKind::Current => self.progress_current_now += steps,
+        }
+    }
+
+    fn print(&mut self, kind: ProgressKind, msg: String) {
+        if kind == ProgressKind::Current {
+            self.messages.push_back(msg);
+            if self.messages.len() > self.retention.messages {
+                self.messages.pop_front();
+            }
+        }
+    }
+
+    fn started(&mut self, metadata: ScanMetadata) {
+        self.metadata = Some(metadata);
+        self.canary_alert = None;
+        self.aborted = None;
+        self.threads = None;
+        self.rate.reset();
+    }
+
+    fn stage(&mut self, index: usize, count: usize, name: String) {
+        self.progress_all_now = 0;
+        self.rate.reset();
+        self.stage = Some(tr_args(
+            Msg::Stage,
+            &[("number", &(index + 1)), ("count", &count), ("name", &name)],
+        ));
+    }
+
+    fn hit(&mut self, hit: HitResult) {
+        if hit.static_asset {
+            self.hidden_static += 1;
+            return;
+        }
+
+        let method = self
+            .metadata
+            .as_ref()
+            .map_or(HttpMethod::default(), |metadata| metadata.method);
+        self.print(ProgressKind::Current, format!("{method} {hit}"));
+        if let Err(err) = self.results.push(hit) {
+            self.log(
+                LogLevel::WARN,
+                format!("Can't spill results to disk: {err}"),
+            );
+        }
+    }
+
+    fn variant(&mut self, url: String, variant: String) {
+        self.results
+            .annotate(&url, |hit| hit.variants.push(variant));
+    }
+
+    fn scheduler(&mut self, snapshot: SchedulerSnapshot) {
+        self.scheduler_state = Some(snapshot.state);
+    }
+
+    fn concurrency(&mut self, threads: usize) {
+        self.threads = Some(threads);
+    }
+
+    fn waf_detected(&mut self, vendor: WafVendor) {
+        self.log(LogLevel::WARN, format!("WAF detected: {vendor}"));
+    }
+
+    fn tls(&mut self, tls: TlsInfo) {
+        if tls.problem.is_some() {
+            self.log(LogLevel::WARN, format!("TLS {tls}"));
+        }
+        if let Some(metadata) = self.metadata.as_mut() {
+            metadata.add_tls(tls);
+        }
+    }
+
+    fn header_finding(&mut self, finding: HeaderFinding) {
+        self.print(ProgressKind::Current, format!("HEADER {finding}"));
+    }
+
+    fn canary(&mut self, alert: CanaryAlert) {
+        self.log(LogLevel::WARN, format!("Canary {alert}"));
+        self.canary_alert = match alert.change {
+            CanaryChange::Recovered => None,
+            _ => Some(alert.to_string()),
+        };
+    }
+
+    fn verified(&mut self, hit: HitResult, verification: Verification) {
+        if let Verification::Flaky { .. } = verification {
+            self.log(LogLevel::WARN, format!("{hit} ({verification})"));
+        }
+    }
+
+    fn finish(&mut self, kind: ProgressKind) {
+        if kind == ProgressKind::Total {
+            self.current_parsing = "Done!".to_string();
+            self.worker = WorkerVariant::Worker(true);
+        }
+    }
+
+    fn finished(&mut self, summary: ScanSummary) {
+        let duration =
+            util::format_duration(Duration::from_millis(summary.elapsed.as_millis() as u64));
+        let finished = tr_args(
+            Msg::Finished,
+            &[
+                ("duration", &duration),
+                ("requests", &summary.requests),
+                ("errors", &summary.errors),
+                ("hits", &summary.hits),
+            ],
+        );
+        let finished = match &self.aborted {
+            Some(reason) => format!(
+                "{} · {finished}",
+                tr_args(Msg::Aborted, &[("reason", reason)])
+            ),
+            None => finished,
+        };
+        self.current_parsing = match summary.average_latency {
+            Some(latency) => format!(
+                "{finished} · {}",
+                tr_args(
+                    Msg::AverageLatency,
+                    &[(
+                        "latency",
+                        &format!("{:.1}ms", latency.as_secs_f64() * 1000.0)
+                    )],
+                )
+            ),
+            None => finished,
+        };
+    }
+
+    fn aborted(&mut self, reason: String) {
+        self.aborted = Some(reason);
+    }
+
+    fn failed(&mut self, reason: String) {
+        self.current_parsing = format!("Failed: {reason}");
+        self.worker = WorkerVariant::Failed;
+    }
+
+    fn log(&mut self, level: LogLevel, msg: String) {
+        if level == LogLevel::INFO {
+            return;
+        }
+        self.log.push_front(format!("[{level}] {msg}"));
+        if self.log.len() > self.retention.log_lines {
+            self.log.pop_back();
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -242,15 +826,22 @@ impl StatefulWidget for WorkerInfo {
         state: &mut Self::State,
     ) {
         match &state.worker {
-            WorkerVariant::Worker(_) => {
+            WorkerVariant::Worker(_) | WorkerVariant::Failed => {
+                // Fewer log lines rather than a squeezed pane when short of room.
+                let log_height = (state.retention.log_lines as u16 + 2)
+                    .min(
+                        area.height
+                            .saturating_sub(RUNNING_HEIGHT - ARGUMENTS_HEIGHT),
+                    )
+                    .max(ARGUMENTS_HEIGHT);
                 let layout: [Rect; 5] = Layout::new(
                     layout::Direction::Vertical,
                     [
-                        Constraint::Length((LOG_MAX + 2).try_into().unwrap()),
-                        Constraint::Min((MESSAGES_MAX + 2).try_into().unwrap()),
-                        Constraint::Length(3),
-                        Constraint::Length(3),
-                        Constraint::Length(3),
+                        Constraint::Length(log_height),
+                        Constraint::Min(3),
+                        Constraint::Length(BAR_HEIGHT),
+                        Constraint::Length(BAR_HEIGHT),
+                        Constraint::Length(BAR_HEIGHT),
                     ],
                 )
                 .areas(area);
@@ -261,44 +852,84 @@ impl StatefulWidget for WorkerInfo {
                 )
                 .areas(layout[0]);
 
-                let names: [&str; 4] = [
-                    " Logs ",
-                    " Results ",
-                    " Currently requesting ",
-                    " Arguments ",
+                let total_title = match state.scheduler_state {
+                    Some(SchedulerState::Limited) => {
+                        format!(" {} ({}) ", tr(Msg::TotalProgress), tr(Msg::RateLimited))
+                    }
+                    Some(SchedulerState::Throttled) => {
+                        format!(" {} ({}) ", tr(Msg::TotalProgress), tr(Msg::Throttled))
+                    }
+                    Some(SchedulerState::Paused) => {
+                        format!(" {} ({}) ", tr(Msg::TotalProgress), tr(Msg::Paused))
+                    }
+                    Some(SchedulerState::WarmingUp) => {
+                        format!(" {} ({}) ", tr(Msg::TotalProgress), tr(Msg::WarmingUp))
+                    }
+                    _ => format!(" {} ", tr(Msg::TotalProgress)),
+                };
+                let total_title = match &state.stage {
+                    Some(stage) => format!("{total_title}· {stage} "),
+                    None => total_title,
+                };
+                let total_title = match state.threads {
+                    Some(threads) => format!(
+                        "{total_title}· {} ",
+                        tr_args(Msg::ActiveThreads, &[("threads", &threads)])
+                    ),
+                    None => total_title,
+                };
+                let total_title = match state.eta() {
+                    Some(eta) => format!("{total_title}· {eta} "),
+                    None => total_title,
+                };
+                let results_title = match state.hidden_static {
+                    0 => format!(" {} ", tr(Msg::Results)),
+                    hidden => format!(
+                        " {} ",
+                        tr_args(Msg::ResultsStaticHidden, &[("hidden", &hidden)])
+                    ),
+                };
+                let names: [String; 4] = [
+                    format!(" {} ", tr(Msg::Logs)),
+                    results_title,
+                    format!(" {} ", tr(Msg::CurrentlyRequesting)),
+                    format!(" {} ", tr(Msg::Arguments)),
                 ];
 
+                let argument = |field: FieldName| {
+                    Line::from(format!("{}: ", field.label()))
+                        + state.fields_states[field.index()].get().blue()
+                };
+
                 Paragraph::new(Text::from_iter::<[Line; 5]>([
-                    Line::from("URI: ") + state.fields_states[FieldName::Uri.index()].get().blue(),
-                    Line::from("Threads: ")
-                        + state.fields_states[FieldName::Threads.index()].get().blue(),
-                    Line::from("Recursion depth: ")
-                        + state.fields_states[FieldName::Recursion.index()]
-                            .get()
-                            .blue(),
-                    Line::from("Timeout: ")
-                        + state.fields_states[FieldName::Timeout.index()].get().blue(),
-                    Line::from("Wordlist: ")
-                        + state.fields_states[FieldName::WordlistPath.index()]
-                            .get()
-                            .blue(),
+                    argument(FieldName::Uri),
+                    argument(FieldName::Threads),
+                    argument(FieldName::Recursion),
+                    argument(FieldName::Timeout),
+                    argument(FieldName::WordlistPath),
                 ]))
-                .block(Block::bordered().title(names[3]))
+                .block(Block::bordered().title(names[3].as_str()))
                 .render(args_and_log_layout[0], buf);
 
                 let log_lines = state.log.iter().map(|s| Line::from(s.as_str()));
                 let message_lines = state.messages.iter().map(|s| Line::from(s.as_str()));
 
                 Paragraph::new(Text::from_iter(log_lines))
-                    .block(Block::bordered().title(names[0]))
+                    .block(Block::bordered().title(names[0].as_str()))
                     .render(args_and_log_layout[1], buf);
 
+                // The latest messages when they don't all fit.
+                let hidden = state
+                    .messages
+                    .len()
+                    .saturating_sub(layout[1].height.saturating_sub(2) as usize);
                 Paragraph::new(Text::from_iter(message_lines))
-                    .block(Block::bordered().title(names[1]))
+                    .block(Block::bordered().title(names[1].as_str()))
+                    .scroll((hidden as u16, 0))
                     .render(layout[1], buf);
 
                 Paragraph::new(Line::from(state.current_parsing.as_str()))
-                    .block(Block::bordered().title(names[2]))
+                    .block(Block::bordered().title(names[2].as_str()))
                     .render(layout[2], buf);
 
                 if !state.fields_states[FieldName::Recursion.index()]
@@ -306,7 +937,7 @@ impl StatefulWidget for WorkerInfo {
                     .starts_with('0')
                 {
                     Gauge::default()
-                        .block(Block::bordered().title(" Current recursion progress "))
+                        .block(Block::bordered().title(format!(" {} ", tr(Msg::RecursionProgress))))
                         .gauge_style(Style::new().white().on_black().italic())
                         .ratio(checked_ratio(
                             state.progress_current_now,
@@ -316,7 +947,7 @@ impl StatefulWidget for WorkerInfo {
                 }
 
                 Gauge::default()
-                    .block(Block::bordered().title(" Total progress "))
+                    .block(Block::bordered().title(total_title.as_str()))
                     .gauge_style(Style::new().blue().on_black().italic())
                     .ratio(checked_ratio(
                         state.progress_all_now,
@@ -325,43 +956,69 @@ impl StatefulWidget for WorkerInfo {
                     .render(layout[4], buf);
             }
             WorkerVariant::Builder => {
-                let constraints: [Constraint; FIELDS_NUMBER + 1] = std::array::from_fn(|i| {
+                let heights: [u16; FIELDS_NUMBER + 1] = std::array::from_fn(|i| {
                     if i == FieldName::WordlistPath.index() && state.fields_states[i].is_editing {
-                        return Constraint::Length(7);
+                        return HINT_FIELD_HEIGHT;
                     }
-                    Constraint::Length(3)
+                    FIELD_HEIGHT
                 });
 
-                let layout: [Rect; FIELDS_NUMBER + 1] =
-                    Layout::new(layout::Direction::Vertical, constraints).areas(area);
-
-                Paragraph::new("Run")
-                    .centered()
-                    .block(
-                        Block::bordered().style(if state.selection == Selection::RunButton {
-                            Style::default().green()
-                        } else {
-                            Style::default()
-                        }),
-                    )
-                    .alignment(layout::Alignment::Center)
-                    .render(
-                        Self::center(
-                            layout[FIELDS_NUMBER],
-                            Constraint::Max(40),
-                            Constraint::Length(3),
-                        ),
-                        buf,
-                    );
-
-                for (ind, field_state) in state.fields_states.iter_mut().enumerate() {
-                    if field_state.is_editing {
-                        state.cursor_position = (
-                            layout[ind].x + 1 + field_state.input.cursor() as u16,
-                            layout[ind].y + 1,
+                // Scrolls as little as needed to keep the selected row in view.
+                let selected = state.selection.index();
+                let fits = |rows: &[u16]| rows.iter().sum::<u16>() <= area.height;
+                state.form_scroll = state.form_scroll.min(selected);
+                while state.form_scroll < selected && !fits(&heights[state.form_scroll..=selected])
+                {
+                    state.form_scroll += 1;
+                }
+                while state.form_scroll > 0 && fits(&heights[state.form_scroll - 1..]) {
+                    state.form_scroll -= 1;
+                }
+
+                let first = state.form_scroll;
+                let mut shown = first..first;
+                while shown.end <= FIELDS_NUMBER && fits(&heights[first..=shown.end]) {
+                    shown.end += 1;
+                }
+                let layout = Layout::new(
+                    layout::Direction::Vertical,
+                    heights[shown.clone()]
+                        .iter()
+                        .map(|&h| Constraint::Length(h)),
+                )
+                .split(area);
+                let area_of = |ind: usize| layout[ind - first];
+
+                if shown.contains(&FIELDS_NUMBER) {
+                    Paragraph::new(tr(Msg::Run))
+                        .centered()
+                        .block(Block::bordered().style(
+                            if state.selection == Selection::RunButton {
+                                Style::default().green()
+                            } else {
+                                Style::default()
+                            },
+                        ))
+                        .alignment(layout::Alignment::Center)
+                        .render(
+                            Self::center(
+                                area_of(FIELDS_NUMBER),
+                                Constraint::Max(40),
+                                Constraint::Length(FIELD_HEIGHT),
+                            ),
+                            buf,
                         );
+                }
+
+                for ind in shown.take_while(|&ind| ind < FIELDS_NUMBER) {
+                    let field_state = &mut state.fields_states[ind];
+                    let area = area_of(ind);
+                    if field_state.is_editing {
+                        state.cursor_position =
+                            (area.x + 1 + field_state.input.cursor() as u16, area.y + 1);
                     }
-                    Field::new(NAMES[ind]).render(layout[ind], buf, field_state);
+                    let title = format!(" {} ", FIELDS[ind].label());
+                    Field::new(&title).render(area, buf, field_state);
                 }
             }
         }
@@ -1,3 +1,13 @@
+use std::{
+    env,
+    fs::File,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use sha2::{Digest, Sha256};
+
 pub fn print_logo() {
     println!(
         "
@@ -14,3 +24,164 @@ pub fn print_logo() {
  "
     )
 }
+
+/// `$XDG_CONFIG_HOME/yadb`, falling back to `~/.config/yadb` and `%APPDATA%\yadb`.
+pub fn config_dir() -> Option<PathBuf> {
+    let dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))?;
+    Some(dir.join("yadb"))
+}
+
+/// `$XDG_DATA_HOME/yadb`, falling back to `~/.local/share/yadb` and `%LOCALAPPDATA%\yadb`.
+pub fn data_dir() -> Option<PathBuf> {
+    let dir = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .or_else(|| env::var_os("LOCALAPPDATA").map(PathBuf::from))?;
+    Some(dir.join("yadb"))
+}
+
+/// Hex SHA-256 of a file's contents.
+pub fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0; 64 * 1024];
+    loop {
+        let read = file.read(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buf[..read]);
+    }
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+/// Replaces every `${NAME}` in `value` with the environment variable `NAME`,
+/// failing with the name of the first one that isn't set. `$${` stands for a
+/// literal `${`.
+pub fn interpolate_env(value: &str) -> Result<String, String> {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find('$') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(escaped) = rest.strip_prefix("$${") {
+            result.push_str("${");
+            rest = escaped;
+        } else if let Some((name, after)) = rest
+            .strip_prefix("${")
+            .and_then(|tail| tail.split_once('}'))
+        {
+            result.push_str(&env::var(name).map_err(|_| name.to_string())?);
+            rest = after;
+        } else {
+            result.push('$');
+            rest = &rest[1..];
+        }
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Parses a human-friendly duration like `2s500ms` or `1h30m`.
+///
+/// A bare number is read as seconds to keep older invocations working.
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Ok(Duration::from_secs(secs));
+    }
+    humantime::parse_duration(value).map_err(|err| format!("{value:?}: {err}"))
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    humantime::format_duration(duration).to_string()
+}
+
+/// Parses a header given as `Name: value`.
+pub fn parse_header(value: &str) -> Result<(String, String), String> {
+    let (name, header_value) = value
+        .split_once(':')
+        .ok_or_else(|| format!("{value:?}: expected NAME: VALUE"))?;
+    Ok((name.trim().to_string(), header_value.trim().to_string()))
+}
+
+/// Parses an HTTP status code, between 100 and 599.
+pub fn parse_status(value: &str) -> Result<u16, String> {
+    let value = value.trim();
+    value
+        .parse::<u16>()
+        .ok()
+        .filter(|status| (100..600).contains(status))
+        .ok_or_else(|| format!("{value:?}: not an HTTP status code"))
+}
+
+/// Parses status codes separated by commas or spaces, such as
+/// `200, 301, !500`, into the ones to report and the ones, prefixed with
+/// `!`, to drop.
+pub fn parse_status_filter(value: &str) -> Result<(Vec<u16>, Vec<u16>), String> {
+    let mut included = Vec::new();
+    let mut excluded = Vec::new();
+    for code in value
+        .split([',', ' '])
+        .map(str::trim)
+        .filter(|code| !code.is_empty())
+    {
+        match code.strip_prefix('!') {
+            Some(code) => excluded.push(parse_status(code)?),
+            None => included.push(parse_status(code)?),
+        }
+    }
+    Ok((included, excluded))
+}
+
+/// Parses a byte size like `512`, `64K` or `1.5GiB`, using binary units.
+pub fn parse_size(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+
+    let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        _ => return Err(format!("{value:?}: unknown unit {unit:?}")),
+    };
+
+    number
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite() && *number >= 0.0)
+        .map(|number| (number * multiplier as f64) as u64)
+        .ok_or_else(|| format!("{value:?}: invalid size"))
+}
+
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
@@ -0,0 +1,253 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Wordlists larger than this many bytes are streamed from disk rather than
+/// loaded into memory.
+pub const STREAM_THRESHOLD: u64 = 64 * 1024 * 1024;
+
+/// Size of the chunks a streamed wordlist is read in.
+const STREAM_BUFFER: usize = 1024 * 1024;
+
+/// Lines of a wordlist file.
+#[derive(Debug, Default, Clone)]
+pub struct Wordlist {
+    pub lines: Vec<String>,
+    /// Lines that weren't valid UTF-8 and had bytes replaced.
+    pub invalid_utf8: usize,
+}
+
+impl Wordlist {
+    /// Reads every line of a wordlist, replacing invalid UTF-8.
+    pub fn read(path: &Path) -> std::io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut wordlist = Self::default();
+        let mut buf = Vec::new();
+
+        while reader.read_until(b'\n', &mut buf)? > 0 {
+            let line = String::from_utf8_lossy(&buf);
+            if std::str::from_utf8(&buf).is_err() {
+                wordlist.invalid_utf8 += 1;
+            }
+            wordlist
+                .lines
+                .push(line.trim_end_matches(['\n', '\r']).to_string());
+            buf.clear();
+        }
+
+        Ok(wordlist)
+    }
+
+    /// Words of a frequency-annotated wordlist, such as the `word,count`
+    /// files of commonspeak or `uniq -c` output, the most frequent first.
+    ///
+    /// Counts of repeated words add up; words without a count come last, and
+    /// words with equal counts keep the order of the file.
+    pub fn ranked(&self) -> Vec<String> {
+        let mut counts: HashMap<&str, u64> = HashMap::new();
+        let mut words = Vec::new();
+        for line in &self.lines {
+            let (word, count) = parse_frequency(line);
+            if word.is_empty() {
+                continue;
+            }
+            match counts.get_mut(word) {
+                Some(total) => *total = total.saturating_add(count.unwrap_or_default()),
+                None => {
+                    counts.insert(word, count.unwrap_or_default());
+                    words.push(word);
+                }
+            }
+        }
+
+        words.sort_by_key(|word| std::cmp::Reverse(counts[word]));
+        words.into_iter().map(str::to_string).collect()
+    }
+
+    /// Appends the lines of `other`.
+    pub fn merge(&mut self, other: Wordlist) {
+        self.lines.extend(other.lines);
+        self.invalid_utf8 += other.invalid_utf8;
+    }
+}
+
+/// A wordlist read from disk on every pass instead of held in memory, for
+/// lists like rockyou that take gigabytes.
+///
+/// Opening it reads the file once to count the words, so progress totals are
+/// known before the scan starts.
+#[derive(Clone)]
+pub struct WordStream {
+    path: PathBuf,
+    keep: Arc<dyn Fn(&str) -> bool + Send + Sync>,
+    /// Words requested after the lines of the file.
+    extra: Vec<String>,
+    len: usize,
+    /// Lines `keep` rejected.
+    pub skipped: usize,
+}
+
+impl fmt::Debug for WordStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WordStream")
+            .field("path", &self.path)
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WordStream {
+    /// Streams the lines of `path` that `keep` lets through, followed by the
+    /// `extra` words the file doesn't have.
+    pub fn open(
+        path: &Path,
+        keep: impl Fn(&str) -> bool + Send + Sync + 'static,
+        extra: &[String],
+    ) -> io::Result<Self> {
+        let mut stream = Self {
+            path: path.to_path_buf(),
+            keep: Arc::new(keep),
+            extra: extra.to_vec(),
+            len: 0,
+            skipped: 0,
+        };
+
+        let mut missing: HashSet<&str> = extra.iter().map(String::as_str).collect();
+        for line in read_lines(File::open(path)?) {
+            let line = line?;
+            if !(stream.keep)(&line) {
+                stream.skipped += 1;
+                continue;
+            }
+            missing.remove(line.as_str());
+            stream.len += 1;
+        }
+        stream.extra.retain(|word| missing.contains(word.as_str()));
+        stream.len += stream.extra.len();
+
+        Ok(stream)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Words in file order, read lazily. A file that can't be read anymore
+    /// ends the words early.
+    pub fn words(&self) -> impl Iterator<Item = String> + Send + '_ {
+        File::open(&self.path)
+            .into_iter()
+            .flat_map(read_lines)
+            .map_while(Result::ok)
+            .filter(|line| (self.keep)(line))
+            .chain(self.extra.iter().cloned())
+    }
+}
+
+/// Lines of `file` without line endings, invalid UTF-8 replaced.
+fn read_lines(file: File) -> impl Iterator<Item = io::Result<String>> + Send {
+    BufReader::with_capacity(STREAM_BUFFER, file)
+        .split(b'\n')
+        .map(|line| {
+            line.map(|bytes| {
+                String::from_utf8_lossy(&bytes)
+                    .trim_end_matches(['\n', '\r'])
+                    .to_string()
+            })
+        })
+}
+
+/// Splits a line of a frequency-annotated wordlist into its word and count.
+///
+/// The count may follow the word after a tab, comma or space (`admin\t1520`,
+/// `admin,1520`), or precede it as in `uniq -c` output (`   1520 admin`).
+pub fn parse_frequency(line: &str) -> (&str, Option<u64>) {
+    let line = line.trim();
+    if let Some((word, count)) = line.rsplit_once(['\t', ',', ' '])
+        && let Ok(count) = count.parse()
+    {
+        return (word.trim_end(), Some(count));
+    }
+    if let Some((count, word)) = line.split_once(['\t', ' '])
+        && let Ok(count) = count.parse()
+    {
+        return (word.trim_start(), Some(count));
+    }
+    (line, None)
+}
+
+/// Counters describing a wordlist and what a [`Cleanup`] removed from it.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WordlistStats {
+    pub lines: usize,
+    pub blank: usize,
+    pub duplicates: usize,
+    /// Words dropped for being shorter or longer than allowed.
+    pub out_of_range: usize,
+    pub invalid_utf8: usize,
+    /// Words left after the cleanup.
+    pub words: usize,
+    pub longest: usize,
+}
+
+/// How to clean up a wordlist; blank lines and duplicates are always removed.
+#[derive(Debug, Default, Clone)]
+pub struct Cleanup {
+    pub sort: bool,
+    /// Minimal word length in characters.
+    pub min_len: Option<usize>,
+    /// Maximal word length in characters.
+    pub max_len: Option<usize>,
+}
+
+impl Cleanup {
+    /// Cleaned words in their original order (or sorted), with statistics.
+    pub fn apply(&self, wordlist: Wordlist) -> (Vec<String>, WordlistStats) {
+        let mut stats = WordlistStats {
+            lines: wordlist.lines.len(),
+            invalid_utf8: wordlist.invalid_utf8,
+            ..Default::default()
+        };
+        let mut seen = HashSet::new();
+        let mut words = Vec::new();
+
+        for line in wordlist.lines {
+            if line.trim().is_empty() {
+                stats.blank += 1;
+                continue;
+            }
+
+            let len = line.chars().count();
+            if self.min_len.is_some_and(|min| len < min)
+                || self.max_len.is_some_and(|max| len > max)
+            {
+                stats.out_of_range += 1;
+                continue;
+            }
+
+            if !seen.insert(line.clone()) {
+                stats.duplicates += 1;
+                continue;
+            }
+
+            stats.longest = stats.longest.max(len);
+            words.push(line);
+        }
+
+        if self.sort {
+            words.sort_unstable();
+        }
+
+        stats.words = words.len();
+        (words, stats)
+    }
+}
@@ -0,0 +1,191 @@
+use std::{
+    fmt, io,
+    io::{Read, Write},
+    net::{IpAddr, SocketAddr, TcpStream},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use socket2::{Domain, Protocol, Socket, Type};
+use ureq::{
+    Error,
+    unversioned::transport::{
+        Buffers, ConnectionDetails, Connector, Either, LazyBuffers, NextTimeout, Transport,
+    },
+};
+
+/// Opens TCP connections from one of the given local addresses, rotating between them.
+pub struct BindConnector {
+    addresses: Vec<IpAddr>,
+    next: AtomicUsize,
+}
+
+impl BindConnector {
+    pub fn new(addresses: Vec<IpAddr>) -> Self {
+        Self {
+            addresses,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Next local address of the same family as `remote`.
+    fn local_for(&self, remote: &SocketAddr) -> Option<IpAddr> {
+        let candidates: Vec<&IpAddr> = self
+            .addresses
+            .iter()
+            .filter(|local| local.is_ipv4() == remote.is_ipv4())
+            .collect();
+
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % candidates.len();
+        Some(*candidates[index])
+    }
+}
+
+/// Connects to `remote` from the local address `local`.
+pub fn connect_from(
+    local: IpAddr,
+    remote: SocketAddr,
+    timeout: Option<Duration>,
+) -> io::Result<TcpStream> {
+    let socket = Socket::new(
+        Domain::for_address(remote),
+        Type::STREAM,
+        Some(Protocol::TCP),
+    )?;
+    socket.bind(&SocketAddr::new(local, 0).into())?;
+
+    match timeout {
+        Some(timeout) => socket.connect_timeout(&remote.into(), timeout)?,
+        None => socket.connect(&remote.into())?,
+    }
+
+    Ok(socket.into())
+}
+
+impl fmt::Debug for BindConnector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BindConnector")
+            .field("addresses", &self.addresses)
+            .finish()
+    }
+}
+
+impl<In: Transport> Connector<In> for BindConnector {
+    type Out = Either<In, BoundTcpTransport>;
+
+    fn connect(
+        &self,
+        details: &ConnectionDetails,
+        chained: Option<In>,
+    ) -> Result<Option<Self::Out>, Error> {
+        if chained.is_some() {
+            return Ok(chained.map(Either::A));
+        }
+
+        let mut last_err = None;
+        for remote in details.addrs.iter() {
+            let Some(local) = self.local_for(remote) else {
+                continue;
+            };
+
+            match connect_from(local, *remote, details.timeout.not_zero().map(|t| *t)) {
+                Ok(stream) => {
+                    if details.config.no_delay() {
+                        stream.set_nodelay(true)?;
+                    }
+                    let buffers = LazyBuffers::new(
+                        details.config.input_buffer_size(),
+                        details.config.output_buffer_size(),
+                    );
+                    return Ok(Some(Either::B(BoundTcpTransport::new(stream, buffers))));
+                }
+                Err(err) => last_err = Some(err),
+            }
+        }
+
+        Err(last_err
+            .unwrap_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::AddrNotAvailable,
+                    "No bind address matches the target address family",
+                )
+            })
+            .into())
+    }
+}
+
+/// Plain TCP transport over a socket created by [`BindConnector`].
+pub struct BoundTcpTransport {
+    stream: TcpStream,
+    buffers: LazyBuffers,
+}
+
+impl BoundTcpTransport {
+    fn new(stream: TcpStream, buffers: LazyBuffers) -> Self {
+        Self { stream, buffers }
+    }
+}
+
+fn map_timeout(err: io::Error, timeout: NextTimeout) -> Error {
+    match err.kind() {
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => Error::Timeout(timeout.reason),
+        _ => err.into(),
+    }
+}
+
+impl Transport for BoundTcpTransport {
+    fn buffers(&mut self) -> &mut dyn Buffers {
+        &mut self.buffers
+    }
+
+    fn transmit_output(&mut self, amount: usize, timeout: NextTimeout) -> Result<(), Error> {
+        self.stream
+            .set_write_timeout(timeout.not_zero().map(|t| *t))?;
+
+        let output = &self.buffers.output()[..amount];
+        self.stream
+            .write_all(output)
+            .map_err(|err| map_timeout(err, timeout))
+    }
+
+    fn await_input(&mut self, timeout: NextTimeout) -> Result<bool, Error> {
+        self.stream
+            .set_read_timeout(timeout.not_zero().map(|t| *t))?;
+
+        let input = self.buffers.input_append_buf();
+        let amount = self
+            .stream
+            .read(input)
+            .map_err(|err| map_timeout(err, timeout))?;
+        self.buffers.input_appended(amount);
+
+        Ok(amount > 0)
+    }
+
+    fn is_open(&mut self) -> bool {
+        if self.stream.set_nonblocking(true).is_err() {
+            return false;
+        }
+
+        let mut buf = [0];
+        let open = matches!(
+            self.stream.read(&mut buf),
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock
+        );
+
+        open && self.stream.set_nonblocking(false).is_ok()
+    }
+}
+
+impl fmt::Debug for BoundTcpTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoundTcpTransport")
+            .field("local", &self.stream.local_addr().ok())
+            .field("peer", &self.stream.peer_addr().ok())
+            .finish()
+    }
+}
@@ -1,17 +1,43 @@
 use std::{
-    path::PathBuf,
+    net::IpAddr,
+    path::{Path, PathBuf},
     sync::{Arc, mpsc::Sender},
+    time::Duration,
 };
 
 use anyhow::Result;
+use regex::Regex;
 use thiserror::Error;
 use url::{ParseError, Url};
 
-use crate::lib::worker::{messages::WorkerMessage, unit::Worker};
+use crate::lib::output::{self, OutputSink, OutputSinks};
+use crate::lib::util;
+use crate::lib::worker::{
+    canary,
+    candidates::{CustomTransforms, Transform, WordTransform},
+    capture::DEFAULT_SAMPLE_SIZE,
+    checkpoint::Checkpoint,
+    config::{Engine, HttpMethod, ScanMode, WorkerConfig},
+    danger::DANGEROUS_WORDS,
+    discovery,
+    filter::{
+        BodyCount, CalibrationFilter, CaptureFilter, CountFilter, FilterPipeline, HashDedupeFilter,
+        NumberRange, RegexFilter, ResponseFilter, SizeFilter, StatusFilter,
+    },
+    known::KnownHits,
+    messages::WorkerMessage,
+    pipeline::Stage,
+    plugin::{Plugin, PluginRegistry},
+    politeness::Politeness,
+    profile::BrowserProfile,
+    proxy_pool, rng,
+    store::StoreLimits,
+    unit::{DEFAULT_MAX_BODY_SIZE, Worker},
+};
 
 pub const DEFAULT_THREADS_NUMBER: usize = 50;
 pub const DEFAULT_RECURSIVE_MODE: usize = 0;
-pub const DEFAULT_TIMEOUT: usize = 5;
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
 
 #[derive(Error, Debug, Clone)]
 pub enum BuilderError {
@@ -33,29 +59,189 @@ pub enum BuilderError {
     #[error("Not a file: {0}")]
     NotAFile(String),
 
+    #[error("Invalid duration: {0}")]
+    InvalidDuration(String),
+
+    #[error("{field}: not a number: {value:?}")]
+    InvalidNumber { field: String, value: String },
+
+    #[error("Invalid bind address: {0}")]
+    InvalidBindAddress(String),
+
+    #[error("Invalid regex: {0}")]
+    InvalidRegex(String),
+
+    #[error("Invalid output: {0}")]
+    InvalidOutput(String),
+
+    #[error("Invalid header: {0}")]
+    InvalidHeader(String),
+
+    #[error("Invalid HTTP method: {0}")]
+    InvalidMethod(String),
+
+    #[error("Invalid status filter: {0}")]
+    InvalidStatus(String),
+
+    #[error("Plugin {name}: {reason}")]
+    Plugin { name: String, reason: String },
+
+    #[error("Can't load previous results: {0}")]
+    InvalidPreviousResults(String),
+
+    #[error("Can't resume from checkpoint: {0}")]
+    InvalidCheckpoint(String),
+
+    #[error("Can't load proxy list: {0}")]
+    InvalidProxyList(String),
+
+    #[error("Wordlist {path} has SHA-256 {actual}, expected {expected}")]
+    WordlistHashMismatch {
+        path: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Environment variable {0} is not set")]
+    UndefinedVariable(String),
+
+    #[error("The async engine needs yadb built with the async feature")]
+    AsyncEngineUnavailable,
+
+    #[error("The async engine doesn't support {0}")]
+    UnsupportedByEngine(&'static str),
+
     #[error("Sender channel not specified")]
     SenderChannelNotSpecified,
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct WorkerBuilder {
-    pub threads: Option<usize>,
+    pub concurrency: Option<usize>,
+    pub auto_threads: bool,
+    pub engine: Engine,
+    pub runtime_workers: Option<usize>,
     pub recursion: Option<usize>,
-    pub timeout: Option<usize>,
+    pub timeout: Option<Duration>,
+    pub max_time: Option<Duration>,
+    pub max_time_per_dir: Option<Duration>,
+    pub max_errors: Option<usize>,
+    pub delay: Option<Duration>,
     pub wordlist: Option<PathBuf>,
+    pub wordlist_sha256: Option<String>,
     pub uri: Option<Url>,
     pub proxy_uri: Option<Url>,
+    /// Proxies requests rotate through.
+    pub proxies: Vec<Url>,
+    pub scan_mode: Option<ScanMode>,
+    pub method: HttpMethod,
+    pub cookie_jar: Option<PathBuf>,
+    pub browser_profile: Option<BrowserProfile>,
+    pub waf_pause: bool,
+    pub bind_addresses: Vec<IpAddr>,
+    pub match_regex: Option<Regex>,
+    pub filter_regex: Option<Regex>,
+    pub capture_regexes: Vec<Regex>,
+    pub filter_sizes: Vec<NumberRange>,
+    pub filter_words: Vec<NumberRange>,
+    pub filter_lines: Vec<NumberRange>,
+    pub status_codes: Vec<u16>,
+    pub exclude_statuses: Vec<u16>,
+    pub dedupe_bodies: bool,
+    pub custom_filters: FilterPipeline,
+    pub rate_limit: Option<u32>,
+    pub host_rate_limit: Option<u32>,
+    pub suppress_static: bool,
+    pub verify_hits: bool,
+    pub seed: Option<u64>,
+    pub shuffle: bool,
+    pub by_frequency: bool,
+    pub transforms: Vec<Transform>,
+    pub custom_transforms: CustomTransforms,
+    pub sinks: OutputSinks,
+    pub capture_quota: Option<u64>,
+    pub capture_sample: Option<u64>,
+    pub max_body_size: Option<u64>,
+    pub known_hits: Arc<KnownHits>,
+    pub checkpoint: Option<PathBuf>,
+    pub resume: Option<Arc<Checkpoint>>,
+    pub result_limits: StoreLimits,
+    pub headers: Vec<(String, String)>,
+    pub extra_words: Vec<String>,
+    pub check_packs: bool,
+    pub extensions: Vec<String>,
+    pub multi_segment_words: bool,
+    pub add_slash: Option<bool>,
+    pub politeness: Option<Politeness>,
+    pub jitter: Option<Duration>,
+    pub retries: Option<u32>,
+    pub upgrade_https: bool,
+    pub head_first: bool,
+    pub follow_redirects: usize,
+    pub force_recursion: bool,
+    pub calibration: Option<bool>,
+    pub respect_robots: bool,
+    pub phase_timings: bool,
+    pub canary: Option<String>,
+    pub canary_interval: Option<Duration>,
+    pub ramp_up: Option<Duration>,
+    pub allow_dangerous: bool,
+    pub dangerous_words: Option<Vec<String>>,
+    pub early_stop: bool,
+    pub early_stop_hits: Option<usize>,
+    pub stages: Vec<Stage>,
     error: Option<BuilderError>,
     message_sender: Option<Arc<Sender<WorkerMessage>>>,
 }
 
 impl WorkerBuilder {
-    pub fn threads(mut self, threads: usize) -> Self {
+    /// Older name of [`WorkerBuilder::concurrency`], from when every request had its own thread.
+    pub fn threads(self, threads: usize) -> Self {
+        self.concurrency(threads)
+    }
+
+    /// Number of requests in flight at once.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    /// Starts with a few requests in flight and raises their number up to
+    /// the concurrency while the target answers quickly and without errors,
+    /// lowering it again when it slows down or fails.
+    pub fn auto_threads(mut self, auto_threads: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.auto_threads = auto_threads;
+        self
+    }
+
+    /// Sends the requests of every directory with `engine`. The async one
+    /// needs the `async` feature and sends requests without the proxy
+    /// list, bind addresses, cookie jar, HEAD-first mode and phase timings,
+    /// so the scan fails to build when any of them is set.
+    pub fn engine(mut self, engine: Engine) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.engine = engine;
+        self
+    }
+
+    /// OS threads of the async runtime; the blocking engine ignores it.
+    pub fn runtime_workers(mut self, workers: usize) -> Self {
         if self.error.is_some() {
             return self;
         }
 
-        self.threads = Some(threads);
+        self.runtime_workers = Some(workers);
         self
     }
 
@@ -73,7 +259,7 @@ impl WorkerBuilder {
         self
     }
 
-    pub fn timeout(mut self, timeout: usize) -> Self {
+    pub fn timeout(mut self, timeout: Duration) -> Self {
         if self.error.is_some() {
             return self;
         }
@@ -82,10 +268,52 @@ impl WorkerBuilder {
         self
     }
 
+    pub fn max_time(mut self, max_time: Duration) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.max_time = Some(max_time);
+        self
+    }
+
+    pub fn max_time_per_dir(mut self, max_time: Duration) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.max_time_per_dir = Some(max_time);
+        self
+    }
+
+    /// Aborts the scan once `max_errors` candidates in a row failed without
+    /// a response, as they do when the target is down.
+    pub fn max_errors(mut self, max_errors: usize) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.max_errors = Some(max_errors);
+        self
+    }
+
+    pub fn delay(mut self, delay: Duration) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.delay = Some(delay);
+        self
+    }
+
     pub fn wordlist(mut self, wordlist_path: &str) -> Self {
         if self.error.is_some() {
             return self;
         }
+        let Some(wordlist_path) = self.interpolate(wordlist_path) else {
+            return self;
+        };
+        let wordlist_path = wordlist_path.as_str();
 
         let path: PathBuf = PathBuf::from(wordlist_path);
 
@@ -108,12 +336,26 @@ impl WorkerBuilder {
         self
     }
 
+    /// Pins the wordlist to a hex SHA-256, checked when the worker is
+    /// built, so a shared scan runs with the same words everywhere.
+    pub fn wordlist_sha256(mut self, hash: &str) -> Self {
+        if self.error.is_some() || hash.is_empty() {
+            return self;
+        }
+
+        self.wordlist_sha256 = Some(hash.trim().to_ascii_lowercase());
+        self
+    }
+
     pub fn uri(mut self, uri: &str) -> Self {
         if self.error.is_some() {
             return self;
         }
+        let Some(uri) = self.interpolate(uri) else {
+            return self;
+        };
 
-        let parsed_uri = match Url::parse(uri) {
+        let parsed_uri = match Url::parse(&uri) {
             Ok(url) => url,
             Err(err) => {
                 self.error = Some(BuilderError::UrlParseError(err));
@@ -130,8 +372,11 @@ impl WorkerBuilder {
         if self.error.is_some() || proxy_uri.is_empty() {
             return self;
         }
+        let Some(proxy_uri) = self.interpolate(proxy_uri) else {
+            return self;
+        };
 
-        let parsed_uri = match Url::parse(proxy_uri) {
+        let parsed_uri = match Url::parse(&proxy_uri) {
             Ok(url) => url,
             Err(err) => {
                 self.error = Some(BuilderError::UrlParseError(err));
@@ -144,33 +389,988 @@ impl WorkerBuilder {
         self
     }
 
-    pub fn build(self) -> Result<Worker, BuilderError> {
-        if let Some(err) = self.error {
-            return Err(err);
+    /// Rotates requests through the proxies listed in a file, one per line,
+    /// dropping those that keep failing. Requests outside the wordlist go
+    /// through the first one unless [`proxy_url`](Self::proxy_url) is set.
+    pub fn proxy_list(mut self, path: &str) -> Self {
+        if self.error.is_some() {
+            return self;
         }
 
-        let uri = self.uri.ok_or(BuilderError::TargetNotSpecified)?;
+        match proxy_pool::load(Path::new(path)) {
+            Ok(proxies) => self.proxies = proxies,
+            Err(err) => self.error = Some(BuilderError::InvalidProxyList(format!("{err:#}"))),
+        }
+        self
+    }
 
-        let threads = self.threads.unwrap_or(DEFAULT_THREADS_NUMBER);
-        let recursion_depth = self.recursion.unwrap_or(DEFAULT_RECURSIVE_MODE);
-        let timeout = self.timeout.unwrap_or(DEFAULT_TIMEOUT);
+    pub fn scan_mode(mut self, scan_mode: ScanMode) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
 
-        let wordlist = self.wordlist.ok_or(BuilderError::WordlistNotSpecified)?;
+        self.scan_mode = Some(scan_mode);
+        self
+    }
 
-        let message_sender = self
-            .message_sender
-            .ok_or(BuilderError::SenderChannelNotSpecified)?;
+    /// Requests every word with this method instead of GET. HEAD saves the
+    /// bandwidth of the bodies; HEAD-first mode only applies to GET scans.
+    pub fn method(mut self, method: HttpMethod) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
 
-        let proxy_uri = self.proxy_uri;
+        self.method = method;
+        self
+    }
 
-        Ok(Worker::new(
-            threads,
-            recursion_depth,
-            timeout,
-            wordlist,
-            uri,
-            message_sender,
-            proxy_uri,
-        ))
+    pub fn cookie_jar(mut self, cookie_jar_path: &str) -> Self {
+        if self.error.is_some() || cookie_jar_path.is_empty() {
+            return self;
+        }
+        let Some(cookie_jar_path) = self.interpolate(cookie_jar_path) else {
+            return self;
+        };
+        let cookie_jar_path = cookie_jar_path.as_str();
+
+        let path: PathBuf = PathBuf::from(cookie_jar_path);
+
+        if path.exists() && !path.is_file() {
+            self.error = Some(BuilderError::NotAFile(cookie_jar_path.to_string()));
+            return self;
+        }
+
+        self.cookie_jar = Some(path);
+        self
+    }
+
+    /// Takes concurrency, pacing, retries and browser profile from a preset;
+    /// each of them set on the builder explicitly wins over it.
+    pub fn politeness(mut self, politeness: Politeness) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.politeness = Some(politeness);
+        self
+    }
+
+    /// Adds a random pause of up to `jitter` to the delay before each request.
+    pub fn jitter(mut self, jitter: Duration) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.jitter = Some(jitter);
+        self
+    }
+
+    /// Tries requests that failed without a response this many more times.
+    pub fn retries(mut self, retries: u32) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.retries = Some(retries);
+        self
+    }
+
+    pub fn browser_profile(mut self, browser_profile: BrowserProfile) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.browser_profile = Some(browser_profile);
+        self
+    }
+
+    pub fn waf_pause(mut self, waf_pause: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.waf_pause = waf_pause;
+        self
+    }
+
+    /// Adds a local address to send requests from; may be called several times.
+    pub fn bind_address(mut self, address: &str) -> Self {
+        if self.error.is_some() || address.is_empty() {
+            return self;
+        }
+
+        match address.parse() {
+            Ok(address) => self.bind_addresses.push(address),
+            Err(_) => self.error = Some(BuilderError::InvalidBindAddress(address.to_string())),
+        }
+
+        self
+    }
+
+    pub fn match_regex(mut self, regex: &str) -> Self {
+        if self.error.is_some() || regex.is_empty() {
+            return self;
+        }
+
+        match Regex::new(regex) {
+            Ok(regex) => self.match_regex = Some(regex),
+            Err(err) => self.error = Some(BuilderError::InvalidRegex(err.to_string())),
+        }
+
+        self
+    }
+
+    /// Attaches the named groups of `regex` matching the body of a hit to
+    /// it, without filtering anything.
+    pub fn capture_regex(mut self, regex: &str) -> Self {
+        if self.error.is_some() || regex.is_empty() {
+            return self;
+        }
+
+        match Regex::new(regex) {
+            Ok(regex) if regex.capture_names().flatten().next().is_none() => {
+                self.error = Some(BuilderError::InvalidRegex(format!(
+                    "{regex}: no named capture groups"
+                )))
+            }
+            Ok(regex) => self.capture_regexes.push(regex),
+            Err(err) => self.error = Some(BuilderError::InvalidRegex(err.to_string())),
+        }
+
+        self
+    }
+
+    pub fn filter_regex(mut self, regex: &str) -> Self {
+        if self.error.is_some() || regex.is_empty() {
+            return self;
+        }
+
+        match Regex::new(regex) {
+            Ok(regex) => self.filter_regex = Some(regex),
+            Err(err) => self.error = Some(BuilderError::InvalidRegex(err.to_string())),
+        }
+
+        self
+    }
+
+    /// Caps the requests per second of all threads together; 0 lifts the cap.
+    pub fn rate_limit(mut self, rate_limit: u32) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Caps the requests per second sent to any single host.
+    pub fn host_rate_limit(mut self, host_rate_limit: u32) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.host_rate_limit = Some(host_rate_limit).filter(|rate| *rate > 0);
+        self
+    }
+
+    /// Sends a header with every request; can be called several times.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+        let Some(value) = self.interpolate(value) else {
+            return self;
+        };
+
+        if name.is_empty() || name.contains([':', ' ', '\r', '\n']) || value.contains(['\r', '\n'])
+        {
+            self.error = Some(BuilderError::InvalidHeader(format!("{name}: {value}")));
+            return self;
+        }
+
+        self.headers.push((name.to_string(), value));
+        self
+    }
+
+    /// Sends every one of these headers with every request.
+    pub fn headers<N, V>(mut self, headers: impl IntoIterator<Item = (N, V)>) -> Self
+    where
+        N: AsRef<str>,
+        V: AsRef<str>,
+    {
+        for (name, value) in headers {
+            self = self.header(name.as_ref(), value.as_ref());
+        }
+        self
+    }
+
+    /// Requests these words in addition to the wordlist.
+    pub fn extra_words(mut self, words: impl IntoIterator<Item = String>) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.extra_words.extend(words);
+        self
+    }
+
+    /// Probes the sensitive paths of the target's stack once it is recognized.
+    pub fn check_packs(mut self, check_packs: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.check_packs = check_packs;
+        self
+    }
+
+    /// Also requests `word.ext` for every word and each of these extensions.
+    pub fn extensions<S: AsRef<str>>(mut self, extensions: impl IntoIterator<Item = S>) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        for ext in extensions {
+            let ext = ext.as_ref().trim().trim_start_matches('.');
+            if !ext.is_empty() && !self.extensions.iter().any(|known| known == ext) {
+                self.extensions.push(ext.to_string());
+            }
+        }
+        self
+    }
+
+    /// Requests words such as `api/v1` as nested paths; by default their
+    /// slashes are encoded and each word stays a single segment.
+    pub fn multi_segment_words(mut self, multi_segment: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.multi_segment_words = multi_segment;
+        self
+    }
+
+    /// Requests words as `word/`, the way directories are addressed, or as
+    /// the bare `word` for targets whose routes answer differently with a
+    /// slash. On by default; extensions never get one.
+    pub fn add_slash(mut self, add_slash: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.add_slash = Some(add_slash);
+        self
+    }
+
+    /// Drops responses of this size or range of sizes in bytes; can be
+    /// called several times.
+    pub fn filter_size(mut self, size: impl Into<NumberRange>) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.filter_sizes.push(size.into());
+        self
+    }
+
+    /// Drops responses whose body has this number or range of numbers of
+    /// words; can be called several times.
+    pub fn filter_words(mut self, words: impl Into<NumberRange>) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.filter_words.push(words.into());
+        self
+    }
+
+    /// Drops responses whose body has this number or range of numbers of
+    /// lines; can be called several times.
+    pub fn filter_lines(mut self, lines: impl Into<NumberRange>) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.filter_lines.push(lines.into());
+        self
+    }
+
+    /// Reports only responses with this status; can be called several
+    /// times. Listing 404 reports missing paths too.
+    pub fn status_code(mut self, status: u16) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        if !(100..600).contains(&status) {
+            self.error = Some(BuilderError::InvalidStatus(status.to_string()));
+            return self;
+        }
+
+        self.status_codes.push(status);
+        self
+    }
+
+    /// Drops responses with this status, like 404 always is unless listed
+    /// with [`WorkerBuilder::status_code`]; can be called several times.
+    pub fn exclude_status(mut self, status: u16) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        if !(100..600).contains(&status) {
+            self.error = Some(BuilderError::InvalidStatus(status.to_string()));
+            return self;
+        }
+
+        self.exclude_statuses.push(status);
+        self
+    }
+
+    /// Reports responses with an already seen body only once.
+    pub fn dedupe_bodies(mut self, dedupe_bodies: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.dedupe_bodies = dedupe_bodies;
+        self
+    }
+
+    /// Appends a filter run after the built-in ones.
+    pub fn response_filter(mut self, filter: impl ResponseFilter + 'static) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.custom_filters.push(filter);
+        self
+    }
+
+    pub fn suppress_static(mut self, suppress_static: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.suppress_static = suppress_static;
+        self
+    }
+
+    pub fn verify_hits(mut self, verify_hits: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.verify_hits = verify_hits;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn shuffle(mut self, shuffle: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.shuffle = shuffle;
+        self
+    }
+
+    /// Reads wordlists as words annotated with how often they occur, as
+    /// produced from raft or commonspeak data, and requests the most frequent
+    /// first, so that most content turns up early. Overrides `shuffle`.
+    pub fn by_frequency(mut self, by_frequency: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.by_frequency = by_frequency;
+        self
+    }
+
+    /// Also tries the word rewritten by `transform`; can be called several times.
+    pub fn transform(mut self, transform: Transform) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        if !self.transforms.contains(&transform) {
+            self.transforms.push(transform);
+        }
+        self
+    }
+
+    /// Also tries the word rewritten by a transform of the caller's own,
+    /// after the built-in ones.
+    pub fn candidate_transform(mut self, transform: impl WordTransform + 'static) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.custom_transforms.push(transform);
+        self
+    }
+
+    /// Lets `plugin` register its filters, sinks and transforms.
+    pub fn plugin(mut self, plugin: &impl Plugin) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        if let Err(err) = plugin.register(&mut PluginRegistry::new(&mut self)) {
+            self.error = Some(BuilderError::Plugin {
+                name: plugin.name().to_string(),
+                reason: format!("{err:#}"),
+            });
+        }
+        self
+    }
+
+    /// Limits the bytes of response bodies read during the whole scan.
+    pub fn capture_quota(mut self, bytes: u64) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.capture_quota = Some(bytes);
+        self
+    }
+
+    /// Bytes read from bodies of responses with an uninteresting status.
+    pub fn capture_sample(mut self, bytes: u64) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.capture_sample = Some(bytes);
+        self
+    }
+
+    /// Scans the HTTPS version of an `http://` target found to redirect every
+    /// request there, instead of only warning about it.
+    pub fn upgrade_https(mut self, upgrade: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.upgrade_https = upgrade;
+        self
+    }
+
+    /// Sends HEAD before GET and skips the GET when HEAD answers 404. Turned
+    /// off by itself for targets whose HEAD answers don't match their GET ones.
+    pub fn head_first(mut self, head_first: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.head_first = head_first;
+        self
+    }
+
+    /// Recurses into every hit, files included. Only hits that look like
+    /// directories are recursed into otherwise: paths ending with `/` that
+    /// answer without a redirect, redirects to the path with a slash added
+    /// and directory listings.
+    pub fn force_recursion(mut self, force_recursion: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.force_recursion = force_recursion;
+        self
+    }
+
+    /// Probes every directory with paths that surely don't exist before
+    /// scanning it and drops the responses that look like the answer, as
+    /// SPAs or login redirects answer missing paths with something other
+    /// than 404. On by default.
+    pub fn calibration(mut self, calibration: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.calibration = Some(calibration);
+        self
+    }
+
+    /// Follows up to `follow_redirects` redirects of every candidate and
+    /// reports where they end, with the chain of `Location`s on the way.
+    /// Redirects are reported as they are by default.
+    pub fn follow_redirects(mut self, follow_redirects: usize) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.follow_redirects = follow_redirects;
+        self
+    }
+
+    /// Skips the paths the target's robots.txt disallows for yadb, or for
+    /// every agent, logging how many candidates that left out.
+    pub fn respect_robots(mut self, respect_robots: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.respect_robots = respect_robots;
+        self
+    }
+
+    /// Requests every hit once more over its own connection, recording how
+    /// long DNS, connecting, the TLS handshake and the first byte took. The
+    /// request counts against the rate limit. Skipped behind a proxy, which
+    /// the connection would bypass, and with a cookie jar.
+    pub fn phase_timings(mut self, phase_timings: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.phase_timings = phase_timings;
+        self
+    }
+
+    /// Requests words that look like they change state, such as `logout` or
+    /// `delete`, even when the scan sends cookies or credentials. They are
+    /// skipped in authenticated scans otherwise.
+    pub fn allow_dangerous(mut self, allow_dangerous: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.allow_dangerous = allow_dangerous;
+        self
+    }
+
+    /// Replaces the built-in [`DANGEROUS_WORDS`] skipped in authenticated scans.
+    pub fn dangerous_words(mut self, words: impl IntoIterator<Item = String>) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.dangerous_words = Some(words.into_iter().map(|word| word.to_lowercase()).collect());
+        self
+    }
+
+    /// Whether requests carry a session: a cookie jar or a `Cookie` or
+    /// `Authorization` header.
+    fn authenticated(&self) -> bool {
+        self.cookie_jar.is_some()
+            || self.headers.iter().any(|(name, _)| {
+                name.eq_ignore_ascii_case("cookie") || name.eq_ignore_ascii_case("authorization")
+            })
+    }
+
+    /// Starts the request threads evenly spread over `ramp_up` instead of all
+    /// at once, so rate-based WAF rules aren't tripped at the start.
+    pub fn ramp_up(mut self, ramp_up: Duration) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.ramp_up = Some(ramp_up).filter(|ramp_up| !ramp_up.is_zero());
+        self
+    }
+
+    /// Requests `path`, resolved against the target like a link, now and
+    /// then during the scan and alerts when its status or latency changes
+    /// notably from the first answer.
+    pub fn canary(mut self, path: &str) -> Self {
+        if self.error.is_some() || path.is_empty() {
+            return self;
+        }
+
+        self.canary = self.interpolate(path);
+        self
+    }
+
+    pub fn canary_interval(mut self, interval: Duration) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.canary_interval = Some(interval);
+        self
+    }
+
+    /// Appends a pass to run after the ones added before; the wordlist of
+    /// the stage, if any, has to exist.
+    pub fn stage(mut self, mut stage: Stage) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+        if let Some(wordlist) = &stage.wordlist {
+            let Some(wordlist) = self.interpolate(&wordlist.to_string_lossy()) else {
+                return self;
+            };
+            stage.wordlist = Some(PathBuf::from(wordlist));
+        }
+
+        if let Some(wordlist) = &stage.wordlist {
+            if !wordlist.exists() {
+                self.error = Some(BuilderError::FileNotFound(wordlist.display().to_string()));
+                return self;
+            }
+            if !wordlist.is_file() {
+                self.error = Some(BuilderError::NotAFile(wordlist.display().to_string()));
+                return self;
+            }
+        }
+
+        self.stages.push(stage);
+        self
+    }
+
+    /// Stops the scan when the rate of discovery drops deep into the
+    /// wordlist; otherwise stopping is only suggested.
+    pub fn early_stop(mut self, early_stop: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.early_stop = early_stop;
+        self
+    }
+
+    /// Hits per [`WINDOW`](crate::lib::worker::discovery::WINDOW) requests
+    /// below which stopping is suggested.
+    pub fn early_stop_hits(mut self, hits: usize) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.early_stop_hits = Some(hits);
+        self
+    }
+
+    /// Bytes read from each response body with an interesting status.
+    pub fn max_body_size(mut self, bytes: u64) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.max_body_size = Some(bytes);
+        self
+    }
+
+    /// Number of hits kept in memory; the rest are spilled to a temporary file.
+    pub fn max_stored_hits(mut self, hits: usize) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.result_limits.max_hits = hits;
+        self
+    }
+
+    /// Estimated bytes of hits kept in memory before spilling to a temporary file.
+    pub fn max_stored_bytes(mut self, bytes: usize) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.result_limits.max_bytes = bytes;
+        self
+    }
+
+    /// Re-checks the hits saved in a results file with conditional requests;
+    /// the ones answering `304 Not Modified` are reported as unchanged.
+    pub fn previous_results(mut self, path: &str) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        match KnownHits::load(path.as_ref()) {
+            Ok(known_hits) => self.known_hits = Arc::new(known_hits),
+            Err(err) => self.error = Some(BuilderError::InvalidPreviousResults(format!("{err:#}"))),
+        }
+        self
+    }
+
+    /// Saves how far the scan got to `path` every few seconds and removes
+    /// the file once the scan completes; when the file already exists, the
+    /// scan goes on from the progress saved in it. Pipelines always start
+    /// over.
+    pub fn checkpoint(mut self, path: &str) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        let path = PathBuf::from(path);
+        if path.exists() {
+            match Checkpoint::load(&path) {
+                Ok(checkpoint) => self.resume = Some(Arc::new(checkpoint)),
+                Err(err) => {
+                    self.error = Some(BuilderError::InvalidCheckpoint(format!("{err:#}")));
+                    return self;
+                }
+            }
+        }
+        self.checkpoint = Some(path);
+        self
+    }
+
+    /// Adds a sink that receives hits and logs straight from the worker.
+    pub fn output_sink(mut self, sink: impl OutputSink + 'static) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Adds a built-in sink from a `KIND=TARGET` spec, e.g. `csv=hits.csv`.
+    pub fn output(mut self, spec: &str) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+        let Some(spec) = self.interpolate(spec) else {
+            return self;
+        };
+
+        if let Err(err) = output::from_spec(&spec, &mut self.sinks) {
+            self.error = Some(BuilderError::InvalidOutput(err.to_string()));
+        }
+        self
+    }
+
+    /// `value` with `${NAME}` replaced by environment variables, so scan
+    /// files can refer to secrets and paths without containing them. An
+    /// unset variable fails the builder.
+    fn interpolate(&mut self, value: &str) -> Option<String> {
+        match util::interpolate_env(value) {
+            Ok(value) => Some(value),
+            Err(name) => {
+                self.error = Some(BuilderError::UndefinedVariable(name));
+                None
+            }
+        }
+    }
+
+    /// Assembles the built-in filters in the order they are applied.
+    fn filters(&self) -> FilterPipeline {
+        let mut filters = FilterPipeline::default();
+        let mut excluded = self.exclude_statuses.clone();
+        if !self.status_codes.contains(&404) {
+            excluded.push(404);
+        }
+        filters.push(StatusFilter::new(excluded).including(self.status_codes.clone()));
+
+        if self.scan_mode.unwrap_or_default() == ScanMode::Vhost {
+            filters.push(CalibrationFilter::default());
+        }
+
+        if !self.filter_sizes.is_empty() {
+            filters.push(SizeFilter::new(self.filter_sizes.clone()));
+        }
+
+        if !self.filter_words.is_empty() {
+            filters.push(CountFilter::new(
+                BodyCount::Words,
+                self.filter_words.clone(),
+            ));
+        }
+
+        if !self.filter_lines.is_empty() {
+            filters.push(CountFilter::new(
+                BodyCount::Lines,
+                self.filter_lines.clone(),
+            ));
+        }
+
+        if let Some(regex) = &self.match_regex {
+            filters.push(RegexFilter::matching(regex.clone()));
+        }
+
+        if let Some(regex) = &self.filter_regex {
+            filters.push(RegexFilter::excluding(regex.clone()));
+        }
+
+        for regex in &self.capture_regexes {
+            filters.push(CaptureFilter::new(regex.clone()));
+        }
+
+        if self.dedupe_bodies {
+            filters.push(HashDedupeFilter::default());
+        }
+
+        filters.extend(&self.custom_filters);
+        filters
+    }
+
+    /// Whether the chosen engine can run the scan as configured.
+    fn check_engine(&self) -> Result<(), BuilderError> {
+        if self.engine != Engine::Async {
+            return Ok(());
+        }
+        if !cfg!(feature = "async") {
+            return Err(BuilderError::AsyncEngineUnavailable);
+        }
+
+        let unsupported = [
+            (!self.proxies.is_empty(), "proxy lists"),
+            (!self.bind_addresses.is_empty(), "bind addresses"),
+            (self.cookie_jar.is_some(), "cookie jars"),
+            (self.head_first, "HEAD-first mode"),
+            (self.phase_timings, "phase timings"),
+        ];
+        match unsupported.into_iter().find(|(set, _)| *set) {
+            Some((_, option)) => Err(BuilderError::UnsupportedByEngine(option)),
+            None => Ok(()),
+        }
+    }
+
+    /// The first error a setter ran into, without building the worker.
+    pub fn validate(&self) -> Result<(), BuilderError> {
+        match &self.error {
+            Some(err) => Err(err.clone()),
+            None => Ok(()),
+        }
+    }
+
+    pub fn build(self) -> Result<Worker, BuilderError> {
+        self.validate()?;
+        self.check_engine()?;
+
+        let filters = self.filters();
+        let dangerous_words = if self.authenticated() && !self.allow_dangerous {
+            self.dangerous_words.clone().unwrap_or_else(|| {
+                DANGEROUS_WORDS
+                    .iter()
+                    .map(|word| word.to_string())
+                    .collect()
+            })
+        } else {
+            Vec::new()
+        };
+        let uri = self.uri.ok_or(BuilderError::TargetNotSpecified)?;
+        if let Some(resume) = &self.resume
+            && resume.target != uri.as_str()
+        {
+            return Err(BuilderError::InvalidCheckpoint(format!(
+                "saved for {}, not {uri}",
+                resume.target
+            )));
+        }
+
+        let preset = self.politeness.map(Politeness::settings);
+        let concurrency = self
+            .concurrency
+            .or(preset.map(|preset| preset.concurrency))
+            .unwrap_or(DEFAULT_THREADS_NUMBER)
+            .max(1);
+        let recursion_depth = self.recursion.unwrap_or(DEFAULT_RECURSIVE_MODE);
+        let timeout = self.timeout.unwrap_or(DEFAULT_TIMEOUT);
+
+        let wordlist = self.wordlist.ok_or(BuilderError::WordlistNotSpecified)?;
+        if let Some(expected) = self.wordlist_sha256 {
+            let path = wordlist.display().to_string();
+            let actual = util::sha256_file(&wordlist)
+                .map_err(|_| BuilderError::FileNotFound(path.clone()))?;
+            if actual != expected {
+                return Err(BuilderError::WordlistHashMismatch {
+                    path,
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        let message_sender = self
+            .message_sender
+            .ok_or(BuilderError::SenderChannelNotSpecified)?;
+
+        let config = WorkerConfig {
+            concurrency,
+            auto_threads: self.auto_threads,
+            engine: self.engine,
+            runtime_workers: self.runtime_workers,
+            recursion_depth,
+            timeout,
+            max_time: self.max_time,
+            max_time_per_dir: self.max_time_per_dir,
+            max_errors: self.max_errors,
+            delay: self
+                .delay
+                .or(preset.map(|preset| preset.delay))
+                .unwrap_or_default(),
+            jitter: self
+                .jitter
+                .or(preset.map(|preset| preset.jitter))
+                .unwrap_or_default(),
+            retries: self
+                .retries
+                .or(preset.map(|preset| preset.retries))
+                .unwrap_or_default(),
+            rate_limit: self
+                .rate_limit
+                .or(preset.and_then(|preset| preset.rate_limit))
+                .filter(|rate| *rate > 0),
+            host_rate_limit: self.host_rate_limit,
+            wordlist_path: wordlist,
+            uri,
+            proxy_url: self.proxy_uri,
+            proxies: self.proxies,
+            scan_mode: self.scan_mode.unwrap_or_default(),
+            method: self.method,
+            cookie_jar: self.cookie_jar,
+            browser_profile: self
+                .browser_profile
+                .or(preset.and_then(|preset| preset.browser_profile)),
+            waf_pause: self.waf_pause,
+            bind_addresses: self.bind_addresses,
+            filters,
+            suppress_static: self.suppress_static,
+            verify_hits: self.verify_hits,
+            seed: self
+                .resume
+                .as_ref()
+                .map(|resume| resume.seed)
+                .or(self.seed)
+                .unwrap_or_else(rng::random_seed),
+            shuffle: self.shuffle,
+            by_frequency: self.by_frequency,
+            transforms: self.transforms,
+            custom_transforms: self.custom_transforms,
+            sinks: self.sinks,
+            capture_quota: self.capture_quota,
+            capture_sample: self.capture_sample.unwrap_or(DEFAULT_SAMPLE_SIZE),
+            max_body_size: self.max_body_size.unwrap_or(DEFAULT_MAX_BODY_SIZE),
+            known_hits: self.known_hits,
+            checkpoint: self.checkpoint,
+            resume: self.resume,
+            result_limits: self.result_limits,
+            headers: self.headers,
+            extra_words: self.extra_words,
+            check_packs: self.check_packs,
+            extensions: self.extensions,
+            multi_segment_words: self.multi_segment_words,
+            add_slash: self.add_slash.unwrap_or(true),
+            politeness: self.politeness,
+            upgrade_https: self.upgrade_https,
+            head_first: self.head_first && self.method == HttpMethod::Get,
+            follow_redirects: self.follow_redirects,
+            force_recursion: self.force_recursion,
+            calibration: self.calibration.unwrap_or(true),
+            respect_robots: self.respect_robots,
+            phase_timings: self.phase_timings,
+            canary: self.canary,
+            canary_interval: self.canary_interval.unwrap_or(canary::DEFAULT_INTERVAL),
+            ramp_up: self.ramp_up,
+            dangerous_words,
+            stages: self.stages,
+            early_stop: self.early_stop,
+            early_stop_hits: self.early_stop_hits.unwrap_or(discovery::DEFAULT_MIN_HITS),
+        };
+
+        Ok(Worker::new(config, message_sender))
     }
 }
@@ -0,0 +1,102 @@
+use std::{fmt, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+/// Time between canary requests unless set otherwise.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(30);
+/// How many times slower than at first the canary has to answer to count as
+/// degraded...
+const SLOWDOWN_FACTOR: u32 = 3;
+/// ...provided it lost at least this much, so the jitter of fast targets
+/// doesn't count.
+const MIN_SLOWDOWN: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum CanaryChange {
+    /// Answers with another status than at first; `None` when the request failed.
+    Status { expected: u16, status: Option<u16> },
+    /// Answers much slower than at first.
+    Slow { baseline_ms: u64, latency_ms: u64 },
+    /// Answers like at first again after an alert.
+    Recovered,
+}
+
+/// Notable change of the canary's answer, a hint that the scan is wearing the
+/// target down or that it started blocking.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CanaryAlert {
+    pub url: String,
+    #[serde(flatten)]
+    pub change: CanaryChange,
+}
+
+impl fmt::Display for CanaryAlert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.change {
+            CanaryChange::Status {
+                expected,
+                status: Some(status),
+            } => write!(f, "{} answers {status} instead of {expected}", self.url),
+            CanaryChange::Status { status: None, .. } => {
+                write!(f, "{} doesn't answer anymore", self.url)
+            }
+            CanaryChange::Slow {
+                baseline_ms,
+                latency_ms,
+            } => write!(
+                f,
+                "{} answers in {latency_ms}ms instead of {baseline_ms}ms",
+                self.url
+            ),
+            CanaryChange::Recovered => write!(f, "{} answers like before again", self.url),
+        }
+    }
+}
+
+/// Answers of a known-good path requested now and then during a scan,
+/// compared to the first one.
+#[derive(Debug, Default)]
+pub struct Canary {
+    baseline: Option<(u16, Duration)>,
+    alerting: bool,
+}
+
+impl Canary {
+    /// Takes in an answer of the canary at `url`, returning an alert when it
+    /// is the first to differ notably from the baseline or the first to match
+    /// it again. The first successful answer becomes the baseline.
+    pub fn observe(
+        &mut self,
+        url: &str,
+        status: Option<u16>,
+        latency: Duration,
+    ) -> Option<CanaryAlert> {
+        let Some((expected, baseline)) = self.baseline else {
+            self.baseline = status.map(|status| (status, latency));
+            return None;
+        };
+
+        let change = if status != Some(expected) {
+            Some(CanaryChange::Status { expected, status })
+        } else if latency > baseline * SLOWDOWN_FACTOR && latency - baseline >= MIN_SLOWDOWN {
+            Some(CanaryChange::Slow {
+                baseline_ms: baseline.as_millis() as u64,
+                latency_ms: latency.as_millis() as u64,
+            })
+        } else {
+            None
+        };
+
+        let change = match change {
+            Some(change) if !self.alerting => change,
+            None if self.alerting => CanaryChange::Recovered,
+            _ => return None,
+        };
+        self.alerting = change != CanaryChange::Recovered;
+        Some(CanaryAlert {
+            url: url.to_string(),
+            change,
+        })
+    }
+}
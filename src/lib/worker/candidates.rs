@@ -0,0 +1,341 @@
+use std::{borrow::Cow, fmt, sync::Arc};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::lib::{wordlist::WordStream, worker::config::ScanMode};
+
+/// Rewrites a word into an additional candidate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Transform {
+    Lowercase,
+    Uppercase,
+    /// First letter uppercase, the rest unchanged.
+    Capitalize,
+}
+
+impl Transform {
+    pub fn apply(self, word: &str) -> String {
+        match self {
+            Transform::Lowercase => word.to_lowercase(),
+            Transform::Uppercase => word.to_uppercase(),
+            Transform::Capitalize => {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect())
+                    .unwrap_or_default()
+            }
+        }
+    }
+}
+
+/// A transform beyond the built-in ones, registered with
+/// [`WorkerBuilder::candidate_transform`](crate::lib::worker::builder::WorkerBuilder::candidate_transform).
+pub trait WordTransform: Send + Sync {
+    /// Rewrites a word into an additional candidate; returning the word
+    /// unchanged adds nothing.
+    fn apply(&self, word: &str) -> String;
+}
+
+/// Custom transforms of a scan; clones share them.
+#[derive(Clone, Default)]
+pub struct CustomTransforms(Vec<Arc<dyn WordTransform>>);
+
+impl fmt::Debug for CustomTransforms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CustomTransforms({})", self.0.len())
+    }
+}
+
+impl CustomTransforms {
+    pub fn push(&mut self, transform: impl WordTransform + 'static) {
+        self.0.push(Arc::new(transform));
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Number of candidates a generator yields for one base URL.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CandidateCount {
+    Exact(usize),
+    /// Upper bound; transforms producing an already seen word are skipped.
+    Estimated(usize),
+}
+
+impl CandidateCount {
+    pub fn value(self) -> usize {
+        match self {
+            CandidateCount::Exact(count) | CandidateCount::Estimated(count) => count,
+        }
+    }
+}
+
+impl fmt::Display for CandidateCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CandidateCount::Exact(count) => write!(f, "{count}"),
+            CandidateCount::Estimated(count) => write!(f, "~{count}"),
+        }
+    }
+}
+
+/// Words a [`CandidateGenerator`] draws from.
+#[derive(Debug, Clone)]
+pub enum Words {
+    Loaded(Arc<Vec<String>>),
+    /// Read from disk on every pass, for wordlists too big to load.
+    Streamed(Arc<WordStream>),
+}
+
+impl Words {
+    pub fn len(&self) -> usize {
+        match self {
+            Words::Loaded(words) => words.len(),
+            Words::Streamed(stream) => stream.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Cow<'_, str>> + Send + '_> {
+        match self {
+            Words::Loaded(words) => Box::new(words.iter().map(|word| Cow::Borrowed(word.as_str()))),
+            Words::Streamed(stream) => Box::new(stream.words().map(Cow::Owned)),
+        }
+    }
+}
+
+impl From<Arc<Vec<String>>> for Words {
+    fn from(words: Arc<Vec<String>>) -> Self {
+        Words::Loaded(words)
+    }
+}
+
+/// A single request to send.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    /// URL reported for the candidate.
+    pub target: String,
+    /// `Host` header to send to the base URL instead of requesting `target`.
+    pub host: Option<String>,
+}
+
+/// Lazily turns a wordlist into requests:
+/// words → transforms → extensions → URL template.
+///
+/// Progress totals, dry runs and the worker all use the same generator, so
+/// their numbers always agree.
+#[derive(Debug, Clone)]
+pub struct CandidateGenerator {
+    words: Words,
+    transforms: Vec<Transform>,
+    custom_transforms: CustomTransforms,
+    extensions: Vec<String>,
+    multi_segment: bool,
+    directories: bool,
+    slash: bool,
+    scan_mode: ScanMode,
+}
+
+impl CandidateGenerator {
+    pub fn new(words: impl Into<Words>, scan_mode: ScanMode) -> Self {
+        Self {
+            words: words.into(),
+            transforms: Vec::new(),
+            custom_transforms: CustomTransforms::default(),
+            extensions: Vec::new(),
+            multi_segment: false,
+            directories: true,
+            slash: true,
+            scan_mode,
+        }
+    }
+
+    pub fn transforms(mut self, transforms: Vec<Transform>) -> Self {
+        self.transforms = transforms;
+        self
+    }
+
+    /// Applied after the built-in transforms.
+    pub fn custom_transforms(mut self, transforms: CustomTransforms) -> Self {
+        self.custom_transforms = transforms;
+        self
+    }
+
+    /// File extensions tried in addition to the directory; ignored for vhosts.
+    pub fn extensions(mut self, extensions: Vec<String>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    /// Keeps `/` inside words as path separators, so that `api/v1` is
+    /// requested as two segments instead of one with an encoded slash.
+    pub fn multi_segment(mut self, multi_segment: bool) -> Self {
+        self.multi_segment = multi_segment;
+        self
+    }
+
+    /// Whether `word/` is requested besides the extensions; off for lists of
+    /// file names.
+    pub fn directories(mut self, directories: bool) -> Self {
+        self.directories = directories;
+        self
+    }
+
+    /// Whether the directory form of a word ends with a slash (`word/`) or
+    /// is the bare word, for targets whose routes don't take one.
+    pub fn trailing_slash(mut self, slash: bool) -> Self {
+        self.slash = slash;
+        self
+    }
+
+    pub fn count(&self) -> CandidateCount {
+        let suffixes = match self.scan_mode {
+            ScanMode::Directory if !self.directories => self.extensions.len().max(1),
+            ScanMode::Directory => 1 + self.extensions.len(),
+            ScanMode::Vhost => 1,
+        };
+        let transforms = self.transforms.len() + self.custom_transforms.len();
+        let count = self.words.len() * (1 + transforms) * suffixes;
+
+        if transforms == 0 {
+            CandidateCount::Exact(count)
+        } else {
+            CandidateCount::Estimated(count)
+        }
+    }
+
+    /// Candidates below `base`; an `Err` carries a word that can't form a request.
+    pub fn candidates<'a>(
+        &'a self,
+        base: &'a Url,
+    ) -> impl Iterator<Item = Result<Candidate, String>> + Send + 'a {
+        self.words
+            .iter()
+            .map(|word| self.segments(&word))
+            .flat_map(|word| self.variants(&word))
+            .flat_map(|word| self.suffixed(word))
+            .map(move |path| self.template(base, &path))
+    }
+
+    /// The word as a path below the base URL: without leading and trailing
+    /// slashes, and with inner ones encoded unless words are multi-segment.
+    /// Existing escapes are left alone, so `%2e%2e` isn't encoded twice.
+    fn segments(&self, word: &str) -> String {
+        if self.scan_mode == ScanMode::Vhost {
+            return word.to_string();
+        }
+
+        let path = match word.trim_matches('/') {
+            "" => word,
+            path => path,
+        };
+        if self.multi_segment {
+            path.to_string()
+        } else {
+            path.replace('/', "%2F")
+        }
+    }
+
+    /// The word followed by its distinct transformed forms.
+    fn variants(&self, word: &str) -> Vec<String> {
+        let mut variants = vec![word.to_string()];
+        let builtin = self
+            .transforms
+            .iter()
+            .map(|transform| transform.apply(word));
+        let custom = self
+            .custom_transforms
+            .0
+            .iter()
+            .map(|transform| transform.apply(word));
+        for variant in builtin.chain(custom) {
+            if !variants.contains(&variant) {
+                variants.push(variant);
+            }
+        }
+        variants
+    }
+
+    fn suffixed(&self, word: String) -> Vec<String> {
+        if self.scan_mode == ScanMode::Vhost {
+            return vec![word];
+        }
+
+        if !self.directories && self.extensions.is_empty() {
+            return vec![word];
+        }
+
+        let mut paths = Vec::with_capacity(1 + self.extensions.len());
+        paths.extend(self.extensions.iter().map(|ext| format!("{word}.{ext}")));
+        if self.directories {
+            paths.insert(0, if self.slash { format!("{word}/") } else { word });
+        }
+        paths
+    }
+
+    fn template(&self, base: &Url, path: &str) -> Result<Candidate, String> {
+        match self.scan_mode {
+            ScanMode::Directory => match join_path(base, path) {
+                Some(target) => Ok(Candidate {
+                    target: target.to_string(),
+                    host: None,
+                }),
+                None => Err(path.to_string()),
+            },
+            ScanMode::Vhost => {
+                let host = format!("{path}.{}", base.host_str().unwrap_or_default());
+                let mut target = base.clone();
+                if target.set_host(Some(&host)).is_err() {
+                    return Err(host);
+                }
+                Ok(Candidate {
+                    target: target.to_string(),
+                    host: Some(host),
+                })
+            }
+        }
+    }
+}
+
+/// `path` below the directory `base`, even when `base` lacks its trailing
+/// slash. The query of `base` is kept and its fragment dropped; `?`, `#`
+/// and `\` in `path` are encoded rather than starting a query, a fragment
+/// or a new segment, while `.` and `..` segments resolve as in a browser.
+pub fn join_path(base: &Url, path: &str) -> Option<Url> {
+    let mut directory = base.clone();
+    directory.set_query(None);
+    directory.set_fragment(None);
+    if !directory.path().ends_with('/') {
+        let path = format!("{}/", directory.path());
+        directory.set_path(&path);
+    }
+
+    // `./` keeps a word like `c:foo` from being read as a scheme.
+    let mut relative = String::with_capacity(path.len() + 2);
+    relative.push_str("./");
+    for c in path.chars() {
+        match c {
+            '?' => relative.push_str("%3F"),
+            '#' => relative.push_str("%23"),
+            '\\' => relative.push_str("%5C"),
+            c => relative.push(c),
+        }
+    }
+
+    let mut url = directory.join(&relative).ok()?;
+    url.set_query(base.query());
+    Some(url)
+}
@@ -0,0 +1,110 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use serde::Serialize;
+
+/// Bytes read from bodies of responses that are unlikely to be interesting.
+pub const DEFAULT_SAMPLE_SIZE: u64 = 4 * 1024;
+
+/// How much of the body capture budget a scan has spent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct CaptureUsage {
+    /// Body bytes read so far.
+    pub used: u64,
+    pub quota: Option<u64>,
+    /// Bodies read up to the maximum body size.
+    pub full: usize,
+    /// Bodies of which only the first bytes were read.
+    pub sampled: usize,
+    /// Bodies not read at all because the quota was spent.
+    pub skipped: usize,
+}
+
+/// Budget shared by every body a worker downloads.
+///
+/// Responses with a likely interesting status are read in full, the rest
+/// only up to the sample size, so long scans keep their memory in check.
+#[derive(Debug)]
+pub struct CaptureQuota {
+    quota: Option<u64>,
+    sample_size: u64,
+    /// Bytes read of bodies with an interesting status.
+    max_body_size: u64,
+    used: AtomicU64,
+    full: AtomicUsize,
+    sampled: AtomicUsize,
+    skipped: AtomicUsize,
+}
+
+impl CaptureQuota {
+    pub fn new(quota: Option<u64>, sample_size: u64, max_body_size: u64) -> Self {
+        Self {
+            quota,
+            sample_size: sample_size.min(max_body_size),
+            max_body_size,
+            used: AtomicU64::new(0),
+            full: AtomicUsize::new(0),
+            sampled: AtomicUsize::new(0),
+            skipped: AtomicUsize::new(0),
+        }
+    }
+
+    /// Reserves the bytes that may be read of a body answered with `status`.
+    ///
+    /// Returns zero once the quota is spent; unread bytes should be given back
+    /// with [`CaptureQuota::release`].
+    pub fn reserve(&self, status: u16) -> u64 {
+        let wanted = if is_interesting(status) {
+            self.max_body_size
+        } else {
+            self.sample_size
+        };
+
+        let granted = match self.quota {
+            None => {
+                self.used.fetch_add(wanted, Ordering::Relaxed);
+                wanted
+            }
+            Some(quota) => {
+                let reserved =
+                    self.used
+                        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                            (used < quota).then(|| used + wanted.min(quota - used))
+                        });
+                match reserved {
+                    Ok(used) => wanted.min(quota - used),
+                    Err(_) => 0,
+                }
+            }
+        };
+
+        let counter = if granted == 0 {
+            &self.skipped
+        } else if granted == self.max_body_size {
+            &self.full
+        } else {
+            &self.sampled
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        granted
+    }
+
+    /// Gives back reserved bytes the body turned out not to have.
+    pub fn release(&self, unused: u64) {
+        self.used.fetch_sub(unused, Ordering::Relaxed);
+    }
+
+    pub fn usage(&self) -> CaptureUsage {
+        CaptureUsage {
+            used: self.used.load(Ordering::Relaxed),
+            quota: self.quota,
+            full: self.full.load(Ordering::Relaxed),
+            sampled: self.sampled.load(Ordering::Relaxed),
+            skipped: self.skipped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Statuses whose bodies are worth keeping whole.
+fn is_interesting(status: u16) -> bool {
+    matches!(status, 200..=299 | 401 | 403 | 500..=599)
+}
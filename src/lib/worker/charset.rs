@@ -0,0 +1,43 @@
+use encoding_rs::{Encoding, UTF_8};
+
+/// Bytes at the start of a document searched for a `<meta>` charset declaration.
+const META_SNIFF_LEN: usize = 1024;
+
+/// Decodes a response body to UTF-8, replacing invalid sequences.
+///
+/// The charset is taken from the `Content-Type` header, then from a `<meta>`
+/// tag near the start of the body, falling back to UTF-8.
+pub fn decode(content_type: Option<&str>, body: &[u8]) -> String {
+    let encoding = content_type
+        .and_then(charset_param)
+        .or_else(|| sniff_meta(body))
+        .and_then(|label| Encoding::for_label(label.as_bytes()))
+        .unwrap_or(UTF_8);
+
+    encoding.decode(body).0.into_owned()
+}
+
+fn charset_param(value: &str) -> Option<String> {
+    let lower = value.to_ascii_lowercase();
+    let start = lower.find("charset=")? + "charset=".len();
+    let label: String = lower[start..]
+        .trim_start_matches(['"', '\''])
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'))
+        .collect();
+
+    (!label.is_empty()).then_some(label)
+}
+
+fn sniff_meta(body: &[u8]) -> Option<String> {
+    let head = String::from_utf8_lossy(&body[..body.len().min(META_SNIFF_LEN)]);
+    let head = head.to_ascii_lowercase();
+
+    head.match_indices("<meta")
+        .filter_map(|(start, _)| {
+            let tag = &head[start..];
+            let tag = &tag[..tag.find('>').unwrap_or(tag.len())];
+            charset_param(tag)
+        })
+        .next()
+}
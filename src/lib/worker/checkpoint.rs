@@ -0,0 +1,108 @@
+use std::{collections::BTreeSet, fs, path::Path, sync::Mutex, time::Duration};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// How often a running scan saves its checkpoint.
+pub const INTERVAL: Duration = Duration::from_secs(10);
+
+/// How far a scan got, saved every [`INTERVAL`] so that a scan that crashed
+/// or was stopped can go on from there, see
+/// [`WorkerBuilder::checkpoint`](crate::lib::worker::builder::WorkerBuilder::checkpoint).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Target the scan was started with, checked when resuming.
+    pub target: String,
+    /// Seed of the scan, so shuffled candidates come in the same order.
+    pub seed: u64,
+    /// Directories left to scan with their depth below the target, the one
+    /// being scanned first.
+    pub pending: Vec<(String, usize)>,
+    /// Candidates of the first pending directory that are all done.
+    pub offset: usize,
+    /// Hits found so far. They are in the outputs already, which a resumed
+    /// scan appends to, so only their number is kept.
+    pub hits: usize,
+}
+
+impl Checkpoint {
+    pub fn load(path: &Path) -> Result<Self> {
+        let data =
+            fs::read_to_string(path).with_context(|| format!("Can't read {}", path.display()))?;
+        serde_json::from_str(&data)
+            .with_context(|| format!("Invalid checkpoint {}", path.display()))
+    }
+
+    /// Writes a temporary file first and renames it over `path`, so a crash
+    /// while saving leaves the previous checkpoint intact.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+        fs::write(&temporary, serde_json::to_vec(self)?)
+            .and_then(|()| fs::rename(&temporary, path))
+            .with_context(|| format!("Can't write {}", path.display()))
+    }
+
+    /// Pending directories that are valid URLs, in scan order.
+    pub fn pending_urls(&self) -> Vec<(Url, usize)> {
+        self.pending
+            .iter()
+            .filter_map(|(url, depth)| Some((Url::parse(url).ok()?, *depth)))
+            .collect()
+    }
+}
+
+/// Where the scan is: the directories left and the candidates of the
+/// current one handed out to the request threads.
+#[derive(Debug, Default)]
+pub struct Position {
+    /// Scan order, the directory being scanned first.
+    pub pending: Vec<(Url, usize)>,
+    next: usize,
+    in_flight: BTreeSet<usize>,
+}
+
+impl Position {
+    /// Starts the first pending directory at candidate `offset`.
+    pub fn start_directory(&mut self, offset: usize) {
+        self.next = offset;
+        self.in_flight.clear();
+    }
+
+    /// Hands out the next candidate, which counts as in flight until the
+    /// returned guard is dropped, however its request ends.
+    pub fn take(position: &Mutex<Self>) -> Taken<'_> {
+        let mut guard = position.lock().unwrap();
+        let index = guard.next;
+        guard.next += 1;
+        guard.in_flight.insert(index);
+        Taken { position, index }
+    }
+
+    /// Candidates before this one are all done; the ones still in flight
+    /// are requested again after resuming.
+    pub fn offset(&self) -> usize {
+        self.in_flight.first().copied().unwrap_or(self.next)
+    }
+}
+
+/// A candidate handed out by [`Position::take`].
+pub struct Taken<'a> {
+    position: &'a Mutex<Position>,
+    index: usize,
+}
+
+impl Taken<'_> {
+    /// Leaves the candidate in flight without requesting it, so a checkpoint
+    /// saved after stopping has it requested again.
+    pub fn abandon(self) {
+        std::mem::forget(self);
+    }
+}
+
+impl Drop for Taken<'_> {
+    fn drop(&mut self) {
+        self.position.lock().unwrap().in_flight.remove(&self.index);
+    }
+}
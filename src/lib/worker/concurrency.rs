@@ -0,0 +1,112 @@
+use std::{
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+/// Threads sending requests when a scan with adaptive concurrency starts.
+pub const INITIAL_THREADS: usize = 4;
+/// Shortest span whose responses are judged before changing concurrency.
+const WINDOW: Duration = Duration::from_secs(1);
+/// Fewest requests a window needs to be judged.
+const MIN_SAMPLES: u64 = 10;
+/// Share of failed requests above which concurrency is halved.
+const MAX_ERROR_RATE: f64 = 0.05;
+/// Mean latency, relative to the best one seen, above which concurrency is
+/// halved.
+const SLOW: f64 = 2.0;
+/// Mean latency, relative to the best one seen, below which concurrency grows.
+const FAST: f64 = 1.5;
+/// Growth of the best latency every window, so a target that got slower for
+/// good isn't held to what it managed once.
+const BASELINE_DRIFT: f64 = 1.05;
+
+/// Requests of the current window.
+#[derive(Debug)]
+struct Window {
+    started: Instant,
+    requests: u64,
+    errors: u64,
+    /// Summed latency of the answered requests.
+    latency: Duration,
+    /// Best mean latency of the windows so far.
+    baseline: Option<Duration>,
+}
+
+/// Number of threads allowed to send requests, grown by a quarter while the
+/// target answers as fast as it did at its best and without errors, and
+/// halved once it slows down to twice that or fails more than 5% of the
+/// requests.
+#[derive(Debug)]
+pub struct AdaptiveConcurrency {
+    max: usize,
+    limit: AtomicUsize,
+    window: Mutex<Window>,
+}
+
+impl AdaptiveConcurrency {
+    /// Starts at [`INITIAL_THREADS`] and never goes above `max`.
+    pub fn new(max: usize) -> Self {
+        Self {
+            max: max.max(1),
+            limit: AtomicUsize::new(INITIAL_THREADS.clamp(1, max.max(1))),
+            window: Mutex::new(Window {
+                started: Instant::now(),
+                requests: 0,
+                errors: 0,
+                latency: Duration::ZERO,
+                baseline: None,
+            }),
+        }
+    }
+
+    /// Threads allowed to send requests now.
+    pub fn limit(&self) -> usize {
+        self.limit.load(Ordering::Relaxed)
+    }
+
+    /// Records a request answered, or not, after `latency`. Returns the new
+    /// limit when this closed a window that changed it.
+    pub fn record(&self, ok: bool, latency: Duration) -> Option<usize> {
+        let mut window = self.window.lock().unwrap();
+        window.requests += 1;
+        if ok {
+            window.latency += latency;
+        } else {
+            window.errors += 1;
+        }
+        if window.requests < MIN_SAMPLES || window.started.elapsed() < WINDOW {
+            return None;
+        }
+
+        let answered = window.requests - window.errors;
+        let error_rate = window.errors as f64 / window.requests as f64;
+        let mean = (answered > 0).then(|| window.latency.div_f64(answered as f64));
+
+        let limit = self.limit();
+        let next = match (mean, window.baseline) {
+            _ if error_rate > MAX_ERROR_RATE => limit / 2,
+            (Some(mean), Some(baseline)) if mean > baseline.mul_f64(SLOW) => limit / 2,
+            (Some(mean), Some(baseline)) if mean > baseline.mul_f64(FAST) => limit,
+            _ => limit + (limit / 4).max(1),
+        }
+        .clamp(1, self.max);
+
+        let baseline = match (mean, window.baseline) {
+            (Some(mean), Some(baseline)) => Some(mean.min(baseline.mul_f64(BASELINE_DRIFT))),
+            (mean, baseline) => mean.or(baseline),
+        };
+        *window = Window {
+            started: Instant::now(),
+            requests: 0,
+            errors: 0,
+            latency: Duration::ZERO,
+            baseline,
+        };
+
+        self.limit.store(next, Ordering::Relaxed);
+        (next != limit).then_some(next)
+    }
+}
@@ -0,0 +1,204 @@
+use std::{fmt, net::IpAddr, path::PathBuf, sync::Arc, time::Duration};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::lib::{
+    output::OutputSinks,
+    worker::{
+        candidates::{CustomTransforms, Transform},
+        checkpoint::Checkpoint,
+        filter::FilterPipeline,
+        known::KnownHits,
+        pipeline::Stage,
+        politeness::Politeness,
+        profile::BrowserProfile,
+        store::StoreLimits,
+    },
+};
+
+/// What a wordlist entry is substituted into.
+#[derive(Debug, Default, Clone, Copy, PartialEq, ValueEnum, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanMode {
+    /// Append words to the target path (`/word/`).
+    #[default]
+    Directory,
+    /// Send words as virtual host names (`Host: word.target`).
+    Vhost,
+}
+
+/// How the requests of a directory are sent.
+#[derive(Debug, Default, Clone, Copy, PartialEq, ValueEnum, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Engine {
+    /// A blocking OS thread per request in flight.
+    #[default]
+    Threads,
+    /// Tasks of a tokio runtime, which keep thousands of requests in flight
+    /// on a few threads; needs the `async` feature.
+    Async,
+}
+
+/// HTTP method every word is requested with.
+#[derive(Debug, Default, Clone, Copy, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HttpMethod {
+    #[default]
+    Get,
+    /// Only the status and headers come back, which saves bandwidth but
+    /// leaves the body filters nothing to look at.
+    Head,
+    Post,
+    Put,
+    Options,
+}
+
+impl HttpMethod {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Head => "HEAD",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Options => "OPTIONS",
+        }
+    }
+}
+
+impl fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Resolved scan parameters produced by [`WorkerBuilder`](crate::lib::worker::builder::WorkerBuilder).
+#[derive(Debug, Clone)]
+pub struct WorkerConfig {
+    /// Requests in flight at once; the blocking engine runs a thread for each.
+    pub concurrency: usize,
+    /// Start with few requests in flight and adapt their number, up to
+    /// `concurrency`, to how fast and cleanly the target answers.
+    pub auto_threads: bool,
+    pub engine: Engine,
+    /// OS threads of the async runtime, when it is used.
+    pub runtime_workers: Option<usize>,
+    pub recursion_depth: usize,
+    pub timeout: Duration,
+    /// Stop the whole scan once it has been running this long.
+    pub max_time: Option<Duration>,
+    /// Move on from a directory once it has been scanned this long.
+    pub max_time_per_dir: Option<Duration>,
+    /// Pause between two requests of the same thread.
+    pub delay: Duration,
+    /// Upper bound of a random pause added to the delay.
+    pub jitter: Duration,
+    /// Extra attempts of requests that failed without a response.
+    pub retries: u32,
+    /// Abort the scan once this many candidates in a row failed without a
+    /// response.
+    pub max_errors: Option<usize>,
+    /// Requests per second across all threads.
+    pub rate_limit: Option<u32>,
+    /// Requests per second to a single host.
+    pub host_rate_limit: Option<u32>,
+    pub wordlist_path: PathBuf,
+    pub uri: Url,
+    pub proxy_url: Option<Url>,
+    /// Proxies requests rotate through.
+    pub proxies: Vec<Url>,
+    pub scan_mode: ScanMode,
+    pub method: HttpMethod,
+    /// File the cookie jar is loaded from at start and saved to at the end.
+    pub cookie_jar: Option<PathBuf>,
+    /// Send the header set of this browser instead of the default one.
+    pub browser_profile: Option<BrowserProfile>,
+    /// Pause the scan when a WAF starts blocking requests.
+    pub waf_pause: bool,
+    /// Local addresses outgoing connections are made from, rotated per connection.
+    pub bind_addresses: Vec<IpAddr>,
+    /// Decides which responses are hits.
+    pub filters: FilterPipeline,
+    /// Flag static assets so frontends can hide them.
+    pub suppress_static: bool,
+    /// Request every hit once more after the scan to weed out flaky ones.
+    pub verify_hits: bool,
+    /// Seed of every random choice made during the scan.
+    pub seed: u64,
+    /// Request the words in a seed-determined random order.
+    pub shuffle: bool,
+    /// Read wordlists as words with counts and request the most frequent
+    /// first; `shuffle` is ignored then.
+    pub by_frequency: bool,
+    /// Extra forms of every word to try.
+    pub transforms: Vec<Transform>,
+    /// Transforms registered by the caller, applied after the built-in ones.
+    pub custom_transforms: CustomTransforms,
+    pub sinks: OutputSinks,
+    /// Total bytes of response bodies the scan may read.
+    pub capture_quota: Option<u64>,
+    /// Bytes read from bodies of uninteresting responses.
+    pub capture_sample: u64,
+    /// Bytes read from bodies of the other responses.
+    pub max_body_size: u64,
+    /// Hits of an earlier scan, re-checked with conditional requests.
+    pub known_hits: Arc<KnownHits>,
+    /// File the progress is saved to while scanning.
+    pub checkpoint: Option<PathBuf>,
+    /// Progress of an earlier run the scan goes on from.
+    pub resume: Option<Arc<Checkpoint>>,
+    /// Headers sent with every request, after the browser profile ones.
+    pub headers: Vec<(String, String)>,
+    /// Words requested in addition to the wordlist.
+    pub extra_words: Vec<String>,
+    /// Fingerprint the target and probe the sensitive paths of its stack.
+    pub check_packs: bool,
+    /// File extensions requested for every word besides the directory.
+    pub extensions: Vec<String>,
+    /// Request words containing `/` as nested paths instead of encoding
+    /// the slash.
+    pub multi_segment_words: bool,
+    /// Request the directory form of every word with a trailing slash.
+    pub add_slash: bool,
+    /// Hits kept in memory before the rest are spilled to a temporary file.
+    pub result_limits: StoreLimits,
+    /// Scan the HTTPS version of an `http://` target that redirects every
+    /// request there.
+    pub upgrade_https: bool,
+    /// Stop once fewer than `early_stop_hits` hits come per
+    /// [`WINDOW`](crate::lib::worker::discovery::WINDOW) requests deep into
+    /// the wordlist, instead of only suggesting it.
+    pub early_stop: bool,
+    pub early_stop_hits: usize,
+    /// Passes run one after another, each on the directories the earlier
+    /// ones found; a single pass with the settings above when empty.
+    pub stages: Vec<Stage>,
+    /// Send HEAD first and GET only paths that don't answer 404 to it.
+    pub head_first: bool,
+    /// Redirects of a candidate followed before reporting it; 0 reports the
+    /// redirect itself with where it points.
+    pub follow_redirects: usize,
+    /// Recurse into every hit rather than only those that look like
+    /// directories.
+    pub force_recursion: bool,
+    /// Learn what every directory answers for paths that don't exist and
+    /// drop responses like it.
+    pub calibration: bool,
+    /// Skip the paths the target's robots.txt disallows.
+    pub respect_robots: bool,
+    /// Time the phases of requesting each hit once more over a fresh connection.
+    pub phase_timings: bool,
+    /// Known-good path, resolved against the target, requested every
+    /// `canary_interval` to notice the target degrading or blocking.
+    pub canary: Option<String>,
+    pub canary_interval: Duration,
+    /// Time over which the request threads start one by one.
+    pub ramp_up: Option<Duration>,
+    /// Words skipped because requesting them could change state, see
+    /// [`danger::is_dangerous`](crate::lib::worker::danger::is_dangerous);
+    /// empty unless the scan is authenticated.
+    pub dangerous_words: Vec<String>,
+    /// Preset the pacing settings were derived from.
+    pub politeness: Option<Politeness>,
+}
@@ -0,0 +1,69 @@
+use std::sync::{
+    Condvar, Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+
+/// Pause/stop switches shared between a running worker and its owner.
+#[derive(Debug, Default)]
+pub struct WorkerControl {
+    paused: Mutex<bool>,
+    resumed: Condvar,
+    stopped: AtomicBool,
+    stats_requested: AtomicBool,
+}
+
+impl WorkerControl {
+    pub fn pause(&self) {
+        *self.paused.lock().unwrap() = true;
+    }
+
+    pub fn resume(&self) {
+        *self.paused.lock().unwrap() = false;
+        self.resumed.notify_all();
+    }
+
+    /// Resumes a paused worker and pauses a running one, returning whether
+    /// it is paused now.
+    pub fn toggle_pause(&self) -> bool {
+        let paused = !self.is_paused();
+        if paused {
+            self.pause();
+        } else {
+            self.resume();
+        }
+        paused
+    }
+
+    /// Asks the owner to show the progress of the scan at the next
+    /// occasion, see [`WorkerControl::take_stats_request`].
+    pub fn request_stats(&self) {
+        self.stats_requested.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether progress was asked for since the last call.
+    pub fn take_stats_request(&self) -> bool {
+        self.stats_requested.swap(false, Ordering::Relaxed)
+    }
+
+    /// Asks the worker to finish after the requests already in flight.
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::Relaxed);
+        self.resume();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        *self.paused.lock().unwrap()
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::Relaxed)
+    }
+
+    /// Blocks the calling thread until the worker is resumed or stopped.
+    pub(crate) fn wait_while_paused(&self) {
+        let mut paused = self.paused.lock().unwrap();
+        while *paused && !self.is_stopped() {
+            paused = self.resumed.wait(paused).unwrap();
+        }
+    }
+}
@@ -0,0 +1,45 @@
+/// Words of paths that tend to do something when merely requested: end the
+/// session, delete data or stop the service.
+pub const DANGEROUS_WORDS: &[&str] = &[
+    "logout",
+    "logoff",
+    "signout",
+    "delete",
+    "remove",
+    "destroy",
+    "purge",
+    "truncate",
+    "wipe",
+    "shutdown",
+    "reboot",
+    "restart",
+    "reset",
+    "uninstall",
+    "deactivate",
+    "unsubscribe",
+];
+
+/// Whether requesting `word` looks like it could have side effects: a part
+/// of its last segment, split on `-` and `_` or joined, starts with one of
+/// `dangerous`. Extensions are ignored, so `delete.php` and `deletephp` both
+/// count.
+pub fn is_dangerous(word: &str, dangerous: &[String]) -> bool {
+    let segment = word
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(word);
+    let stem = segment
+        .split('.')
+        .find(|part| !part.is_empty())
+        .unwrap_or_default()
+        .to_lowercase();
+    let joined = stem.replace(['-', '_'], "");
+
+    dangerous.iter().any(|dangerous| {
+        joined.starts_with(dangerous.as_str())
+            || stem
+                .split(['-', '_'])
+                .any(|part| part.starts_with(dangerous.as_str()))
+    })
+}
@@ -0,0 +1,47 @@
+use url::Url;
+
+use crate::lib::worker::html;
+
+/// Titles of the directory listings of common web servers.
+const LISTING_TITLES: &[&str] = &["index of /", "directory listing for /", "listing of /"];
+
+/// Directory to recurse into when the answer to `url` shows there is one:
+/// a redirect below the path with a slash added, a directory listing, or a
+/// path ending with `/` answered without a redirect, as web servers answer
+/// directories. `redirects` are the hops the answer went through.
+pub fn recursion_target(
+    url: &Url,
+    status: u16,
+    redirects: &[String],
+    content_type: Option<&str>,
+    body: Option<&str>,
+) -> Option<Url> {
+    let mut directory = url.clone();
+    directory.set_path(&format!("{}/", url.path().trim_end_matches('/')));
+
+    if let Some(location) = redirects.first() {
+        let below = Url::parse(location).is_ok_and(|location| {
+            location.origin() == url.origin() && location.path().starts_with(directory.path())
+        });
+        if below {
+            return Some(directory);
+        }
+    }
+
+    if html::is_html(content_type) && body.is_some_and(is_listing) {
+        return Some(directory);
+    }
+
+    let answered = matches!(status, 200..=299 | 401 | 403);
+    (url.path().ends_with('/') && redirects.is_empty() && answered).then_some(directory)
+}
+
+/// Whether an HTML body is a directory listing.
+fn is_listing(body: &str) -> bool {
+    html::extract_title(body).is_some_and(|title| {
+        let title = title.to_lowercase();
+        LISTING_TITLES
+            .iter()
+            .any(|prefix| title.starts_with(prefix))
+    })
+}
@@ -0,0 +1,75 @@
+use std::sync::Mutex;
+
+/// Requests the rate of discovery is measured over.
+pub const WINDOW: usize = 1000;
+/// Hits per [`WINDOW`] requests below which continuing is deemed pointless.
+pub const DEFAULT_MIN_HITS: usize = 1;
+/// Share of the requests sent before a slowdown counts; early on the most
+/// common words simply haven't been tried yet.
+const MIN_PROGRESS: f64 = 0.2;
+
+#[derive(Debug, Default)]
+struct Window {
+    total: usize,
+    done: usize,
+    /// Requests and hits of the window being filled.
+    requests: usize,
+    hits: usize,
+    slowed: bool,
+}
+
+/// Watches hits per [`WINDOW`] requests to tell when a scan stopped finding
+/// anything deep into its wordlist.
+#[derive(Debug)]
+pub struct DiscoveryRate {
+    min_hits: usize,
+    window: Mutex<Window>,
+}
+
+/// Reported once, when a window ends with fewer hits than wanted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Slowdown {
+    /// Hits in the last [`WINDOW`] requests.
+    pub hits: usize,
+    /// Requests sent so far out of the planned total.
+    pub progress: f64,
+}
+
+impl DiscoveryRate {
+    pub fn new(min_hits: usize) -> Self {
+        Self {
+            min_hits,
+            window: Mutex::default(),
+        }
+    }
+
+    /// Requests the scan plans to send; grows as recursion finds directories.
+    pub fn set_total(&self, total: usize) {
+        self.window.lock().unwrap().total = total;
+    }
+
+    pub fn hit(&self) {
+        self.window.lock().unwrap().hits += 1;
+    }
+
+    /// Counts a request, returning the slowdown the first time a full window
+    /// past [`MIN_PROGRESS`] holds too few hits.
+    pub fn request(&self) -> Option<Slowdown> {
+        let mut window = self.window.lock().unwrap();
+        window.done += 1;
+        window.requests += 1;
+        if window.requests < WINDOW {
+            return None;
+        }
+
+        let hits = std::mem::take(&mut window.hits);
+        window.requests = 0;
+        let progress = window.done as f64 / window.total.max(1) as f64;
+        if window.slowed || progress < MIN_PROGRESS || hits >= self.min_hits {
+            return None;
+        }
+
+        window.slowed = true;
+        Some(Slowdown { hits, progress })
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+
+/// Debug and error pages of frameworks, which leak source paths, settings
+/// or stack traces and deserve a look whatever their status.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorPage {
+    /// Whoops or Ignition page of a Laravel app in debug mode.
+    Laravel,
+    /// Technical 500 or 404 page of a Django project with `DEBUG = True`.
+    Django,
+    /// Spring Boot's default Whitelabel Error Page.
+    SpringWhitelabel,
+    /// Exception page of a Rails app in development.
+    Rails,
+    /// ASP.NET's "Server Error in Application" page.
+    AspNet,
+    /// Stack trace of the language, printed into the body.
+    StackTrace(Language),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Language {
+    Java,
+    Python,
+    Php,
+    Node,
+}
+
+impl fmt::Display for ErrorPage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let str = match self {
+            ErrorPage::Laravel => "Laravel debug page",
+            ErrorPage::Django => "Django debug page",
+            ErrorPage::SpringWhitelabel => "Spring Whitelabel error page",
+            ErrorPage::Rails => "Rails exception page",
+            ErrorPage::AspNet => "ASP.NET error page",
+            ErrorPage::StackTrace(Language::Java) => "Java stack trace",
+            ErrorPage::StackTrace(Language::Python) => "Python traceback",
+            ErrorPage::StackTrace(Language::Php) => "PHP stack trace",
+            ErrorPage::StackTrace(Language::Node) => "Node.js stack trace",
+        };
+        f.write_str(str)
+    }
+}
+
+impl ErrorPage {
+    /// Tags of the hit: the stack, then what kind of page it is.
+    pub fn tags(self) -> [&'static str; 2] {
+        match self {
+            ErrorPage::Laravel => ["laravel", "debug-page"],
+            ErrorPage::Django => ["django", "debug-page"],
+            ErrorPage::SpringWhitelabel => ["spring", "error-page"],
+            ErrorPage::Rails => ["rails", "debug-page"],
+            ErrorPage::AspNet => ["aspnet", "error-page"],
+            ErrorPage::StackTrace(Language::Java) => ["java", "stack-trace"],
+            ErrorPage::StackTrace(Language::Python) => ["python", "stack-trace"],
+            ErrorPage::StackTrace(Language::Php) => ["php", "stack-trace"],
+            ErrorPage::StackTrace(Language::Node) => ["nodejs", "stack-trace"],
+        }
+    }
+}
+
+/// Statuses worth reading the body of for an error page even when nothing
+/// else needs it.
+pub fn is_error_status(status: u16) -> bool {
+    status >= 500
+}
+
+/// Recognizes a framework's debug or error page, or a bare stack trace, in
+/// a response body.
+pub fn detect(body: &str) -> Option<ErrorPage> {
+    let body = body.to_ascii_lowercase();
+
+    if body.contains("whoops! there was an error")
+        || body.contains("whoops\\exception")
+        || (body.contains("ignition-") && body.contains("illuminate\\"))
+    {
+        return Some(ErrorPage::Laravel);
+    }
+
+    if body.contains("you're seeing this error because you have <code>debug = true</code>") {
+        return Some(ErrorPage::Django);
+    }
+
+    if body.contains("whitelabel error page") {
+        return Some(ErrorPage::SpringWhitelabel);
+    }
+
+    if body.contains("action controller: exception caught") {
+        return Some(ErrorPage::Rails);
+    }
+
+    if body.contains("server error in '/") && body.contains(" application.") {
+        return Some(ErrorPage::AspNet);
+    }
+
+    if body.contains("traceback (most recent call last):") {
+        return Some(ErrorPage::StackTrace(Language::Python));
+    }
+
+    if (body.contains("exception in thread") || body.contains("exception:"))
+        && (body.contains("\tat java.") || (body.contains("\n\tat ") && body.contains(".java:")))
+    {
+        return Some(ErrorPage::StackTrace(Language::Java));
+    }
+
+    if body.contains("stack trace:") && body.contains("#0 /") && body.contains(".php(") {
+        return Some(ErrorPage::StackTrace(Language::Php));
+    }
+
+    if body.contains("at module._compile") || body.contains("at object.<anonymous> (/") {
+        return Some(ErrorPage::StackTrace(Language::Node));
+    }
+
+    None
+}
@@ -0,0 +1,377 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+    hash::{DefaultHasher, Hash, Hasher},
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use regex::Regex;
+use ureq::http::HeaderMap;
+
+use crate::lib::worker::soft404::Soft404;
+
+/// What a filter gets to see of a response.
+pub struct ResponseInfo<'a> {
+    pub url: &'a str,
+    pub status: u16,
+    pub headers: &'a HeaderMap,
+    /// Decoded body, present when some filter asked for it.
+    pub body: Option<&'a str>,
+    pub size: Option<u64>,
+}
+
+/// A single stage of the response filtering pipeline.
+pub trait ResponseFilter: Send + Sync {
+    /// Whether the response should be reported as a hit.
+    fn keep(&self, response: &ResponseInfo) -> bool;
+
+    /// Whether [`ResponseInfo::body`] must be downloaded for this filter.
+    fn needs_body(&self) -> bool {
+        false
+    }
+
+    /// Shows the filter a response for a path that surely doesn't exist.
+    fn calibrate(&self, _baseline: &ResponseInfo) {}
+
+    /// Named values the filter extracts from a kept response, attached to
+    /// its hit.
+    fn captures(&self, _response: &ResponseInfo) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+/// Drops responses with the listed status codes and, when some are
+/// included, responses with any other.
+pub struct StatusFilter {
+    included: Vec<u16>,
+    excluded: Vec<u16>,
+}
+
+impl StatusFilter {
+    pub fn new(excluded: Vec<u16>) -> Self {
+        Self {
+            included: Vec::new(),
+            excluded,
+        }
+    }
+
+    /// Only these status codes pass, unless also excluded.
+    pub fn including(mut self, included: Vec<u16>) -> Self {
+        self.included = included;
+        self
+    }
+}
+
+impl ResponseFilter for StatusFilter {
+    fn keep(&self, response: &ResponseInfo) -> bool {
+        (self.included.is_empty() || self.included.contains(&response.status))
+            && !self.excluded.contains(&response.status)
+    }
+}
+
+/// Inclusive range of sizes or counts, written `42` or `100-200`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl NumberRange {
+    pub fn contains(&self, value: u64) -> bool {
+        (self.start..=self.end).contains(&value)
+    }
+}
+
+impl From<u64> for NumberRange {
+    fn from(value: u64) -> Self {
+        Self {
+            start: value,
+            end: value,
+        }
+    }
+}
+
+impl FromStr for NumberRange {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let parse = |number: &str| {
+            number
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| format!("{value:?}: expected a number or a range like 100-200"))
+        };
+
+        let range = match value.split_once('-') {
+            Some((start, end)) => Self {
+                start: parse(start)?,
+                end: parse(end)?,
+            },
+            None => parse(value)?.into(),
+        };
+        if range.start > range.end {
+            return Err(format!("{value:?}: range ends before it starts"));
+        }
+        Ok(range)
+    }
+}
+
+impl fmt::Display for NumberRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start)
+        } else {
+            write!(f, "{}-{}", self.start, self.end)
+        }
+    }
+}
+
+/// Drops responses whose size is in one of the ranges.
+pub struct SizeFilter {
+    excluded: Vec<NumberRange>,
+}
+
+impl SizeFilter {
+    pub fn new(excluded: Vec<NumberRange>) -> Self {
+        Self { excluded }
+    }
+}
+
+impl ResponseFilter for SizeFilter {
+    fn keep(&self, response: &ResponseInfo) -> bool {
+        response
+            .size
+            .is_none_or(|size| !self.excluded.iter().any(|range| range.contains(size)))
+    }
+}
+
+/// What a [`CountFilter`] counts in a body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyCount {
+    Words,
+    Lines,
+}
+
+impl BodyCount {
+    /// Words are separated by whitespace, as `wc -w` and ffuf count them.
+    pub fn of(self, body: &str) -> u64 {
+        match self {
+            BodyCount::Words => body.split_whitespace().count() as u64,
+            BodyCount::Lines => body.lines().count() as u64,
+        }
+    }
+}
+
+/// Drops responses whose body has a number of words or lines in one of
+/// the ranges.
+pub struct CountFilter {
+    count: BodyCount,
+    excluded: Vec<NumberRange>,
+}
+
+impl CountFilter {
+    pub fn new(count: BodyCount, excluded: Vec<NumberRange>) -> Self {
+        Self { count, excluded }
+    }
+}
+
+impl ResponseFilter for CountFilter {
+    fn keep(&self, response: &ResponseInfo) -> bool {
+        response.body.is_none_or(|body| {
+            let count = self.count.of(body);
+            !self.excluded.iter().any(|range| range.contains(count))
+        })
+    }
+
+    fn needs_body(&self) -> bool {
+        true
+    }
+}
+
+/// Keeps or drops responses whose body matches a regex.
+pub struct RegexFilter {
+    regex: Regex,
+    keep_matching: bool,
+}
+
+impl RegexFilter {
+    /// Only responses matching `regex` pass.
+    pub fn matching(regex: Regex) -> Self {
+        Self {
+            regex,
+            keep_matching: true,
+        }
+    }
+
+    /// Responses matching `regex` are dropped.
+    pub fn excluding(regex: Regex) -> Self {
+        Self {
+            regex,
+            keep_matching: false,
+        }
+    }
+}
+
+impl ResponseFilter for RegexFilter {
+    fn keep(&self, response: &ResponseInfo) -> bool {
+        response
+            .body
+            .is_none_or(|body| self.regex.is_match(body) == self.keep_matching)
+    }
+
+    fn needs_body(&self) -> bool {
+        true
+    }
+
+    /// Named groups of the first match, when keeping matching responses.
+    fn captures(&self, response: &ResponseInfo) -> Vec<(String, String)> {
+        if !self.keep_matching {
+            return Vec::new();
+        }
+        response
+            .body
+            .map(|body| named_captures(&self.regex, body))
+            .unwrap_or_default()
+    }
+}
+
+/// Keeps every response, extracting the named groups of a regex from the
+/// bodies of hits, e.g. `version (?<version>[\d.]+)`.
+pub struct CaptureFilter {
+    regex: Regex,
+}
+
+impl CaptureFilter {
+    pub fn new(regex: Regex) -> Self {
+        Self { regex }
+    }
+}
+
+impl ResponseFilter for CaptureFilter {
+    fn keep(&self, _response: &ResponseInfo) -> bool {
+        true
+    }
+
+    fn needs_body(&self) -> bool {
+        true
+    }
+
+    fn captures(&self, response: &ResponseInfo) -> Vec<(String, String)> {
+        response
+            .body
+            .map(|body| named_captures(&self.regex, body))
+            .unwrap_or_default()
+    }
+}
+
+/// Name and value of each named group that took part in the first match of
+/// `regex` in `body`.
+fn named_captures(regex: &Regex, body: &str) -> Vec<(String, String)> {
+    let Some(captures) = regex.captures(body) else {
+        return Vec::new();
+    };
+    regex
+        .capture_names()
+        .flatten()
+        .filter_map(|name| {
+            let value = captures.name(name)?;
+            Some((name.to_string(), value.as_str().to_string()))
+        })
+        .collect()
+}
+
+/// Reports each distinct body only once.
+#[derive(Default)]
+pub struct HashDedupeFilter {
+    seen: Mutex<HashSet<u64>>,
+}
+
+impl ResponseFilter for HashDedupeFilter {
+    fn keep(&self, response: &ResponseInfo) -> bool {
+        let Some(body) = response.body else {
+            return true;
+        };
+
+        let mut hasher = DefaultHasher::new();
+        response.status.hash(&mut hasher);
+        body.hash(&mut hasher);
+        self.seen.lock().unwrap().insert(hasher.finish())
+    }
+
+    fn needs_body(&self) -> bool {
+        true
+    }
+}
+
+/// Drops responses like the answer to a calibration probe, compared as
+/// [`Soft404`] does: the status alone would drop every real page of a target
+/// whose catch-all answers 200 too.
+#[derive(Default)]
+pub struct CalibrationFilter {
+    baseline: Mutex<Option<Soft404>>,
+}
+
+impl ResponseFilter for CalibrationFilter {
+    fn keep(&self, response: &ResponseInfo) -> bool {
+        !self
+            .baseline
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|baseline| baseline.matches(response))
+    }
+
+    /// Word counts tell pages reflecting the probe apart from real ones.
+    fn needs_body(&self) -> bool {
+        true
+    }
+
+    fn calibrate(&self, baseline: &ResponseInfo) {
+        *self.baseline.lock().unwrap() = Soft404::learn(baseline);
+    }
+}
+
+/// Ordered list of filters; a response is a hit when every stage keeps it.
+#[derive(Clone, Default)]
+pub struct FilterPipeline(Vec<Arc<dyn ResponseFilter>>);
+
+impl fmt::Debug for FilterPipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FilterPipeline({})", self.0.len())
+    }
+}
+
+impl FilterPipeline {
+    pub fn push(&mut self, filter: impl ResponseFilter + 'static) {
+        self.0.push(Arc::new(filter));
+    }
+
+    /// Appends the stages of `other`, sharing them.
+    pub fn extend(&mut self, other: &FilterPipeline) {
+        self.0.extend(other.0.iter().cloned());
+    }
+
+    pub fn keep(&self, response: &ResponseInfo) -> bool {
+        self.0.iter().all(|filter| filter.keep(response))
+    }
+
+    pub fn needs_body(&self) -> bool {
+        self.0.iter().any(|filter| filter.needs_body())
+    }
+
+    pub fn calibrate(&self, baseline: &ResponseInfo) {
+        for filter in &self.0 {
+            filter.calibrate(baseline);
+        }
+    }
+
+    /// Values captured by every stage; a later stage wins on the same name.
+    pub fn captures(&self, response: &ResponseInfo) -> BTreeMap<String, String> {
+        self.0
+            .iter()
+            .flat_map(|filter| filter.captures(response))
+            .collect()
+    }
+}
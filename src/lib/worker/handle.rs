@@ -0,0 +1,88 @@
+use std::{
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Result, anyhow};
+
+use crate::lib::{report::ScanReport, worker::control::WorkerControl};
+
+/// How long [`wait_for_dropped`] is given before exiting, for the stopped
+/// scans to wind down.
+pub const DROP_GRACE: Duration = Duration::from_secs(10);
+
+/// Threads joining the scans of dropped handles.
+static DROPPED: Mutex<Vec<JoinHandle<()>>> = Mutex::new(Vec::new());
+
+/// Owner of a worker running on its own thread, returned by [`Worker::spawn`](crate::lib::worker::unit::Worker::spawn).
+///
+/// Dropping the handle without [joining](WorkerHandle::join) stops the scan,
+/// so that it doesn't keep requesting the target once nobody waits for it.
+#[derive(Debug)]
+pub struct WorkerHandle {
+    /// Taken by [`WorkerHandle::join`].
+    thread: Option<JoinHandle<Result<ScanReport>>>,
+    control: Arc<WorkerControl>,
+}
+
+impl WorkerHandle {
+    pub(crate) fn new(thread: JoinHandle<Result<ScanReport>>, control: Arc<WorkerControl>) -> Self {
+        Self {
+            thread: Some(thread),
+            control,
+        }
+    }
+
+    pub fn control(&self) -> &WorkerControl {
+        &self.control
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.thread.as_ref().is_none_or(JoinHandle::is_finished)
+    }
+
+    /// Waits for the scan to end, turning a panic into an error.
+    pub fn join(mut self) -> Result<ScanReport> {
+        self.thread
+            .take()
+            .ok_or_else(|| anyhow!("Worker thread already joined"))?
+            .join()
+            .map_err(|err| anyhow!("Worker thread panicked: {err:?}"))?
+    }
+}
+
+impl Drop for WorkerHandle {
+    /// Stops the scan and joins it on a thread of its own, so dropping
+    /// doesn't wait for the requests in flight, see [`wait_for_dropped`].
+    fn drop(&mut self) {
+        let Some(thread) = self.thread.take() else {
+            return;
+        };
+
+        self.control.stop();
+        let reaper = thread::spawn(move || {
+            let _ = thread.join();
+        });
+
+        let mut dropped = DROPPED.lock().unwrap();
+        dropped.retain(|reaper| !reaper.is_finished());
+        dropped.push(reaper);
+    }
+}
+
+/// Waits up to `grace` for the scans of dropped handles to end, e.g. before
+/// the process exits so that they flush their outputs and save their
+/// checkpoints; scans still busy after that are left behind.
+pub fn wait_for_dropped(grace: Duration) {
+    let deadline = Instant::now() + grace;
+    for reaper in std::mem::take(&mut *DROPPED.lock().unwrap()) {
+        while !reaper.is_finished() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        if reaper.is_finished() {
+            let _ = reaper.join();
+        }
+    }
+}
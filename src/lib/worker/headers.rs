@@ -0,0 +1,133 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use ureq::http::HeaderMap;
+
+/// Headers debug toolbars and profilers add to their responses.
+const DEBUG_HEADERS: &[&str] = &["x-debug-token", "x-debug-token-link"];
+
+/// Headers that name the software behind the target.
+const SOFTWARE_HEADERS: &[&str] = &[
+    "server",
+    "x-powered-by",
+    "x-aspnet-version",
+    "x-aspnetmvc-version",
+    "x-generator",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HeaderFindingKind {
+    /// An HTTPS response without `Strict-Transport-Security`.
+    MissingHsts,
+    /// A header of a debug toolbar, such as Symfony's `X-Debug-Token`.
+    DebugHeader,
+    /// Software with its version number, as in `Server: nginx/1.18.0`.
+    VersionDisclosure,
+}
+
+/// Security-relevant observation about the response headers of a hit,
+/// reported for information only.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeaderFinding {
+    pub kind: HeaderFindingKind,
+    /// Lowercase header name.
+    pub header: String,
+    /// `None` for a missing header.
+    pub value: Option<String>,
+    /// First hit the finding was made on.
+    pub url: String,
+}
+
+impl fmt::Display for HeaderFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.value.as_deref().unwrap_or_default();
+        match self.kind {
+            HeaderFindingKind::MissingHsts => {
+                write!(f, "No Strict-Transport-Security on {}", self.url)
+            }
+            HeaderFindingKind::DebugHeader => {
+                write!(f, "Debug header {}: {value} on {}", self.header, self.url)
+            }
+            HeaderFindingKind::VersionDisclosure => {
+                write!(f, "Version in {}: {value} on {}", self.header, self.url)
+            }
+        }
+    }
+}
+
+/// Findings made on the hits of a scan so far, each kept once with the
+/// first URL it was made on.
+#[derive(Debug, Default)]
+pub struct HeaderAnalyzer {
+    findings: Vec<HeaderFinding>,
+}
+
+impl HeaderAnalyzer {
+    /// Looks at the headers of the hit at `url` and returns the findings
+    /// earlier hits didn't already produce.
+    pub fn analyze(&mut self, url: &str, headers: &HeaderMap) -> Vec<HeaderFinding> {
+        let new = analyze(url, headers)
+            .into_iter()
+            .filter(|finding| {
+                !self.findings.iter().any(|seen| {
+                    seen.kind == finding.kind
+                        && seen.header == finding.header
+                        && seen.value == finding.value
+                })
+            })
+            .collect::<Vec<_>>();
+        self.findings.extend(new.iter().cloned());
+        new
+    }
+
+    pub fn findings(&self) -> &[HeaderFinding] {
+        &self.findings
+    }
+}
+
+/// Every finding about `headers` of the response from `url`.
+pub fn analyze(url: &str, headers: &HeaderMap) -> Vec<HeaderFinding> {
+    let finding = |kind, header: &str, value: Option<&str>| HeaderFinding {
+        kind,
+        header: header.to_string(),
+        value: value.map(str::to_string),
+        url: url.to_string(),
+    };
+    let value = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    let mut findings = Vec::new();
+
+    if url.starts_with("https://") && !headers.contains_key("strict-transport-security") {
+        findings.push(finding(
+            HeaderFindingKind::MissingHsts,
+            "strict-transport-security",
+            None,
+        ));
+    }
+
+    for name in DEBUG_HEADERS {
+        if let Some(value) = value(name) {
+            findings.push(finding(HeaderFindingKind::DebugHeader, name, Some(value)));
+        }
+    }
+
+    for name in SOFTWARE_HEADERS {
+        if let Some(value) = value(name).filter(|value| has_version(value)) {
+            findings.push(finding(
+                HeaderFindingKind::VersionDisclosure,
+                name,
+                Some(value),
+            ));
+        }
+    }
+
+    findings
+}
+
+/// Whether `value` contains a dotted version number such as `1.18`.
+fn has_version(value: &str) -> bool {
+    value
+        .as_bytes()
+        .windows(3)
+        .any(|window| window[0].is_ascii_digit() && window[1] == b'.' && window[2].is_ascii_digit())
+}
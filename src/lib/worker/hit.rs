@@ -0,0 +1,201 @@
+use std::{collections::BTreeMap, fmt};
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::lib::worker::timing::PhaseTimings;
+
+/// A single non-404 response found during the scan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HitResult {
+    pub url: String,
+    pub status: u16,
+    /// `<title>` of HTML responses.
+    pub title: Option<String>,
+    pub content_type: Option<String>,
+    /// Body size in bytes, when known.
+    pub size: Option<u64>,
+    /// Words and lines of the body, when it was downloaded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub words: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lines: Option<u64>,
+    /// Where the redirects of the path led, in order; the last one wasn't
+    /// requested unless the redirects were followed to the end.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redirects: Vec<String>,
+    /// Last URL of the redirects.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_url: Option<String>,
+    /// Looks like a stylesheet, image or another static asset.
+    pub static_asset: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    /// Answered `304 Not Modified` to a conditional request; the other
+    /// fields come from the earlier scan.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unchanged: bool,
+    /// Labels of findings made by a check pack, e.g. `wordpress`, `version-disclosure`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Hex [SimHash](crate::lib::worker::html::dom_hash) of HTML bodies, for
+    /// grouping pages built from one template.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dom_hash: Option<String>,
+    /// Other extension variants of the path that answered with the same body,
+    /// folded into this hit.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<String>,
+    /// Worker of the TUI that reported the same URL earlier, when hits are
+    /// deduplicated across workers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_found_by: Option<String>,
+    /// Values extracted by named groups of the match and capture regexes.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub captures: BTreeMap<String, String>,
+    /// Phases of requesting the hit again, when timings were asked for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<PhaseTimings>,
+    /// Verdict given to the hit by hand in the TUI.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub triage: Option<Triage>,
+}
+
+/// Verdict on a hit given while going through the results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Triage {
+    Interesting,
+    FalsePositive,
+    Done,
+}
+
+impl fmt::Display for Triage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Triage::Interesting => "interesting",
+            Triage::FalsePositive => "false positive",
+            Triage::Done => "done",
+        })
+    }
+}
+
+impl fmt::Display for HitResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} -> {}", self.url, self.status)?;
+        if !self.redirects.is_empty() {
+            // Paths of the same origin are shown as `(/admin/ -> /admin/login)`.
+            let origin = Url::parse(&self.url).ok().map(|url| url.origin());
+            let hops: Vec<&str> = self
+                .redirects
+                .iter()
+                .map(|hop| match Url::parse(hop) {
+                    Ok(url) if Some(url.origin()) == origin => {
+                        &hop[url[..url::Position::BeforePath].len()..]
+                    }
+                    _ => hop.as_str(),
+                })
+                .collect();
+            write!(f, " ({})", hops.join(" -> "))?;
+        }
+        if let Some(title) = &self.title {
+            write!(f, " [{title}]")?;
+        }
+        if self.unchanged {
+            write!(f, " (unchanged)")?;
+        }
+        for tag in &self.tags {
+            write!(f, " #{tag}")?;
+        }
+        for (name, value) in &self.captures {
+            write!(f, " {name}={value}")?;
+        }
+        if !self.variants.is_empty() {
+            write!(f, " (+{} variants)", self.variants.len())?;
+        }
+        if let Some(worker) = &self.first_found_by {
+            write!(f, " (first found by {worker})")?;
+        }
+        if let Some(timings) = &self.timings {
+            write!(f, " ({timings})")?;
+        }
+        if let Some(triage) = self.triage {
+            write!(f, " [{triage}]")?;
+        }
+        Ok(())
+    }
+}
+
+/// How much a hit is likely to matter, going by its tags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Info,
+    Low,
+    Medium,
+    High,
+}
+
+impl Severity {
+    /// Severity of a finding tagged `tag` by a check pack.
+    pub fn of_tag(tag: &str) -> Self {
+        match tag {
+            "config-backup" | "credentials" | "debug-log" | "debug-page" | "script-console" => {
+                Severity::High
+            }
+            "admin-panel" | "installer" | "user-enumeration" | "directory-listing"
+            | "stack-trace" => Severity::Medium,
+            "version-disclosure" | "xmlrpc" | "examples" | "api" | "nodes" | "error-page" => {
+                Severity::Low
+            }
+            _ => Severity::Info,
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Info => "info",
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+        })
+    }
+}
+
+impl HitResult {
+    /// The highest severity of the hit's tags, `info` when it has none.
+    pub fn severity(&self) -> Severity {
+        self.tags
+            .iter()
+            .map(|tag| Severity::of_tag(tag))
+            .max()
+            .unwrap_or(Severity::Info)
+    }
+}
+
+/// Outcome of requesting a hit once more after the scan.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum Verification {
+    Confirmed,
+    /// Answered differently; `status` is `None` when the request failed.
+    Flaky {
+        status: Option<u16>,
+    },
+}
+
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verification::Confirmed => write!(f, "confirmed"),
+            Verification::Flaky {
+                status: Some(status),
+            } => write!(f, "flaky, now {status}"),
+            Verification::Flaky { status: None } => write!(f, "flaky, no response"),
+        }
+    }
+}
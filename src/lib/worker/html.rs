@@ -0,0 +1,122 @@
+/// Longest title kept, in characters.
+const MAX_TITLE_LEN: usize = 120;
+
+pub fn is_html(content_type: Option<&str>) -> bool {
+    content_type.is_some_and(|value| value.to_ascii_lowercase().contains("text/html"))
+}
+
+/// Extracts the text of the `<title>` element with whitespace collapsed.
+pub fn extract_title(body: &str) -> Option<String> {
+    let lower = body.to_ascii_lowercase();
+    let tag = lower.find("<title")?;
+    let start = tag + lower[tag..].find('>')? + 1;
+    let end = start + lower[start..].find("</title")?;
+
+    let title = decode_entities(&body[start..end])
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if title.is_empty() {
+        return None;
+    }
+
+    Some(title.chars().take(MAX_TITLE_LEN).collect())
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Bits two [`dom_hash`]es may differ in for the pages to count as one template.
+pub const SIMILARITY_DISTANCE: u32 = 6;
+
+/// SimHash of the tag structure and visible text of a page.
+///
+/// Pages rendered from the same template land a few bits apart even when
+/// their text differs, which lets them be grouped without a browser.
+pub fn dom_hash(body: &str) -> u64 {
+    let mut weights = [0i32; 64];
+    for token in dom_tokens(body) {
+        let hash = fnv1a(token.as_bytes());
+        for (bit, weight) in weights.iter_mut().enumerate() {
+            if hash & (1 << bit) != 0 {
+                *weight += 1;
+            } else {
+                *weight -= 1;
+            }
+        }
+    }
+
+    weights
+        .iter()
+        .enumerate()
+        .filter(|(_, weight)| **weight > 0)
+        .fold(0, |hash, (bit, _)| hash | (1 << bit))
+}
+
+/// Number of bits two [`dom_hash`]es differ in.
+pub fn hash_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// Tag names and words of the text, with digits folded so counters and
+/// dates don't tell pages apart. Scripts and styles are skipped.
+fn dom_tokens(body: &str) -> Vec<String> {
+    let lower = body.to_ascii_lowercase();
+    let mut tokens = Vec::new();
+    let mut rest = lower.as_str();
+
+    while !rest.is_empty() {
+        let Some(open) = rest.find('<') else {
+            push_words(&mut tokens, rest);
+            break;
+        };
+        push_words(&mut tokens, &rest[..open]);
+        rest = &rest[open + 1..];
+
+        let name = rest
+            .trim_start_matches('/')
+            .split(|c: char| !c.is_ascii_alphanumeric() && c != '!')
+            .next()
+            .unwrap_or_default();
+        if !name.is_empty() {
+            tokens.push(format!("<{name}>"));
+        }
+
+        if name == "script" || name == "style" {
+            match rest.find(&format!("</{name}")) {
+                Some(end) => rest = &rest[end + 2..],
+                None => break,
+            }
+        }
+
+        match rest.find('>') {
+            Some(close) => rest = &rest[close + 1..],
+            None => break,
+        }
+    }
+
+    tokens
+}
+
+fn push_words(tokens: &mut Vec<String>, text: &str) {
+    tokens.extend(
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| word.replace(|c: char| c.is_ascii_digit(), "0")),
+    );
+}
+
+/// 64-bit FNV-1a, stable across runs and platforms unlike the std hasher.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
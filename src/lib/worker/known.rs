@@ -0,0 +1,44 @@
+use std::{collections::HashMap, fs::File, io::BufReader, path::Path};
+
+use anyhow::{Context, Result};
+
+use crate::lib::{report, worker::hit::HitResult};
+
+/// Hits of an earlier scan, used to ask the server whether they changed.
+#[derive(Debug, Default)]
+pub struct KnownHits(HashMap<String, HitResult>);
+
+impl KnownHits {
+    /// Loads hits saved by the `jsonl` sink or `--progress-jsonl`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = File::open(path).with_context(|| format!("Can't open {}", path.display()))?;
+        let hits = report::read_hits(BufReader::new(file))?;
+        Ok(Self(
+            hits.into_iter().map(|hit| (hit.url.clone(), hit)).collect(),
+        ))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, url: &str) -> Option<&HitResult> {
+        self.0.get(url)
+    }
+
+    /// `If-None-Match`/`If-Modified-Since` headers for a known hit with validators.
+    pub fn conditional_headers(&self, url: &str) -> Vec<(&'static str, &str)> {
+        let Some(hit) = self.get(url) else {
+            return Vec::new();
+        };
+
+        let mut headers = Vec::new();
+        if let Some(etag) = &hit.etag {
+            headers.push(("If-None-Match", etag.as_str()));
+        }
+        if let Some(last_modified) = &hit.last_modified {
+            headers.push(("If-Modified-Since", last_modified.as_str()));
+        }
+        headers
+    }
+}
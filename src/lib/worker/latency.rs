@@ -0,0 +1,155 @@
+use std::{collections::BTreeMap, fmt, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+/// Sub-buckets per power of two of microseconds; eight keep every bucket
+/// within about 12% of the latencies it holds.
+const SUB_BUCKET_BITS: u32 = 3;
+const SUB_BUCKETS: u64 = 1 << SUB_BUCKET_BITS;
+
+/// Upper limits of the rows of the ASCII histogram, in milliseconds.
+const ROW_LIMITS_MS: [u64; 14] = [
+    1, 2, 5, 10, 20, 50, 100, 200, 500, 1_000, 2_000, 5_000, 10_000, 30_000,
+];
+const BAR_WIDTH: u64 = 40;
+
+/// How long requests took to be answered, counted in buckets that grow with
+/// the latency like those of HdrHistogram, so a long scan takes little room.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencyHistogram {
+    /// Requests per bucket, keyed by the lowest latency of the bucket in
+    /// microseconds.
+    buckets: BTreeMap<u64, u64>,
+}
+
+impl LatencyHistogram {
+    pub fn record(&mut self, latency: Duration) {
+        let micros = latency.as_micros().min(u64::MAX as u128) as u64;
+        *self.buckets.entry(bucket_start(micros)).or_default() += 1;
+    }
+
+    pub fn merge(&mut self, other: &LatencyHistogram) {
+        for (start, count) in &other.buckets {
+            *self.buckets.entry(*start).or_default() += count;
+        }
+    }
+
+    pub fn count(&self) -> u64 {
+        self.buckets.values().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+    }
+
+    /// Latency below which `quantile` of the requests were answered, such as
+    /// 0.99 for p99, to the precision of a bucket.
+    pub fn quantile(&self, quantile: f64) -> Option<Duration> {
+        let rank = ((self.count() as f64 * quantile.clamp(0.0, 1.0)).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (start, count) in &self.buckets {
+            seen += count;
+            if seen >= rank {
+                let middle = start + (bucket_end(*start) - start) / 2;
+                return Some(Duration::from_micros(middle));
+            }
+        }
+        None
+    }
+
+    /// p50, p90 and p99, for summaries.
+    pub fn percentiles(&self) -> Vec<(&'static str, Duration)> {
+        [("p50", 0.5), ("p90", 0.9), ("p99", 0.99)]
+            .into_iter()
+            .filter_map(|(label, quantile)| Some((label, self.quantile(quantile)?)))
+            .collect()
+    }
+
+    /// Requests per row of the ASCII histogram, between the first and the
+    /// last row that has any.
+    pub fn rows(&self) -> Vec<(String, u64)> {
+        let mut counts = [0; ROW_LIMITS_MS.len() + 1];
+        for (start, count) in &self.buckets {
+            let row = ROW_LIMITS_MS
+                .iter()
+                .position(|limit| *start < limit * 1000)
+                .unwrap_or(ROW_LIMITS_MS.len());
+            counts[row] += count;
+        }
+
+        let (Some(first), Some(last)) = (
+            counts.iter().position(|count| *count > 0),
+            counts.iter().rposition(|count| *count > 0),
+        ) else {
+            return Vec::new();
+        };
+
+        (first..=last)
+            .map(|row| {
+                let label = match row {
+                    0 => format!("< {}", format_ms(ROW_LIMITS_MS[0])),
+                    row if row == ROW_LIMITS_MS.len() => {
+                        format!(">= {}", format_ms(ROW_LIMITS_MS[row - 1]))
+                    }
+                    row => format!(
+                        "{}-{}",
+                        format_ms(ROW_LIMITS_MS[row - 1]),
+                        format_ms(ROW_LIMITS_MS[row])
+                    ),
+                };
+                (label, counts[row])
+            })
+            .collect()
+    }
+
+    /// One line per row of [`LatencyHistogram::rows`] with a bar of `#`
+    /// scaled to the fullest row.
+    pub fn ascii(&self) -> Vec<String> {
+        let rows = self.rows();
+        let max = rows.iter().map(|(_, count)| *count).max().unwrap_or(0);
+        let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+        rows.into_iter()
+            .map(|(label, count)| {
+                let bar = (count * BAR_WIDTH).div_ceil(max.max(1)) as usize;
+                format!("{label:>width$} | {} {count}", "#".repeat(bar))
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for LatencyHistogram {
+    /// The percentiles, as in `p50 12.0ms, p90 48.1ms, p99 210.3ms`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let percentiles = self
+            .percentiles()
+            .into_iter()
+            .map(|(label, latency)| format!("{label} {:.1}ms", latency.as_secs_f64() * 1000.0))
+            .collect::<Vec<_>>();
+        write!(f, "{}", percentiles.join(", "))
+    }
+}
+
+/// Lowest latency of the bucket `micros` falls in.
+fn bucket_start(micros: u64) -> u64 {
+    if micros < SUB_BUCKETS {
+        return micros;
+    }
+    let shift = micros.ilog2() - SUB_BUCKET_BITS;
+    (micros >> shift) << shift
+}
+
+/// Lowest latency of the bucket after the one starting at `start`.
+fn bucket_end(start: u64) -> u64 {
+    if start < SUB_BUCKETS {
+        return start + 1;
+    }
+    start.saturating_add(1 << (start.ilog2() - SUB_BUCKET_BITS))
+}
+
+fn format_ms(ms: u64) -> String {
+    if ms >= 1000 && ms.is_multiple_of(1000) {
+        format!("{}s", ms / 1000)
+    } else {
+        format!("{ms}ms")
+    }
+}
@@ -1,19 +1,85 @@
-use crate::lib::logger::traits::LogLevel;
+use crate::lib::{
+    logger::traits::LogLevel,
+    report::{ScanMetadata, ScanSummary},
+    worker::{
+        canary::CanaryAlert,
+        capture::CaptureUsage,
+        headers::HeaderFinding,
+        hit::{HitResult, Verification},
+        latency::LatencyHistogram,
+        scheduler::SchedulerSnapshot,
+        tls::TlsInfo,
+        waf::WafVendor,
+    },
+};
 
+#[derive(Debug, Clone)]
 pub enum WorkerMessage {
+    /// Sent first, before any request.
+    Started(ScanMetadata),
     Progress(ProgressMessage),
+    /// Stage `index` of `count` of a multi-stage scan begins; the total
+    /// progress starts over with it.
+    Stage {
+        index: usize,
+        count: usize,
+        name: String,
+    },
+    /// Scanning of a directory `depth` levels below the target begins; the
+    /// `Current` progress messages that follow are about it.
+    Directory {
+        url: String,
+        depth: usize,
+    },
     Log(LogLevel, String),
+    Hit(HitResult),
+    /// Sent once, when the target first answers with a WAF block page.
+    WafDetected(WafVendor),
+    /// Handshake details of an HTTPS host, sent once per host.
+    Tls(TlsInfo),
+    /// Sent the first time a hit's headers show something notable.
+    HeaderFinding(HeaderFinding),
+    /// The canary's answer changed notably, or is back to normal.
+    Canary(CanaryAlert),
+    /// Result of re-requesting a hit after the scan.
+    Verified(HitResult, Verification),
+    /// `variant` answered like the hit at `url` and was folded into it
+    /// instead of being reported.
+    Variant {
+        url: String,
+        variant: String,
+    },
+    /// Threads allowed to send requests with adaptive concurrency, sent at
+    /// the start and whenever it changes.
+    Concurrency(usize),
+    /// Pacing state, sent when it changes and periodically while requests flow.
+    Scheduler(SchedulerSnapshot),
+    /// Body capture budget spent so far, sent after every directory.
+    Capture(CaptureUsage),
+    /// Latencies of the whole scan, sent once at its end.
+    Latency(LatencyHistogram),
+    /// The scan gave up early for this reason; it still ends with
+    /// `Finished`.
+    Aborted(String),
+    /// Sent last, once the scan is over.
+    Finished(ScanSummary),
+    /// The scan ended with an error or a panic; no more messages follow.
+    Failed(String),
 }
+#[derive(Debug, Clone)]
 pub enum ProgressMessage {
     Total(ProgressChangeMessage),
     Current(ProgressChangeMessage),
 }
 
+#[derive(Debug, Clone)]
 pub enum ProgressChangeMessage {
     SetMessage(String),
     SetSize(usize),
     Start(usize),
     Advance,
+    /// Several steps at once, as request threads report them in batches.
+    AdvanceBy(usize),
     Print(String),
     Finish,
 }
@@ -29,6 +95,10 @@ impl WorkerMessage {
         )))
     }
 
+    pub fn start_current(size: usize) -> WorkerMessage {
+        WorkerMessage::Progress(ProgressMessage::Current(ProgressChangeMessage::Start(size)))
+    }
+
     pub fn finish_total() -> WorkerMessage {
         WorkerMessage::Progress(ProgressMessage::Total(ProgressChangeMessage::Finish))
     }
@@ -41,6 +111,10 @@ impl WorkerMessage {
         WorkerMessage::Log(level, str)
     }
 
+    pub fn hit(hit: HitResult) -> WorkerMessage {
+        WorkerMessage::Hit(hit)
+    }
+
     pub fn advance_current() -> WorkerMessage {
         WorkerMessage::Progress(ProgressMessage::Current(ProgressChangeMessage::Advance))
     }
@@ -48,4 +122,16 @@ impl WorkerMessage {
     pub fn advance_total() -> WorkerMessage {
         WorkerMessage::Progress(ProgressMessage::Total(ProgressChangeMessage::Advance))
     }
+
+    pub fn advance_current_by(steps: usize) -> WorkerMessage {
+        WorkerMessage::Progress(ProgressMessage::Current(ProgressChangeMessage::AdvanceBy(
+            steps,
+        )))
+    }
+
+    pub fn advance_total_by(steps: usize) -> WorkerMessage {
+        WorkerMessage::Progress(ProgressMessage::Total(ProgressChangeMessage::AdvanceBy(
+            steps,
+        )))
+    }
 }
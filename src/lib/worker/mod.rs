@@ -1,3 +1,39 @@
+pub mod bind;
 pub mod builder;
+pub mod canary;
+pub mod candidates;
+pub mod capture;
+pub mod charset;
+pub mod checkpoint;
+pub mod concurrency;
+pub mod config;
+pub mod control;
+pub mod danger;
+pub mod directory;
+pub mod discovery;
+pub mod error_page;
+pub mod filter;
+pub mod handle;
+pub mod headers;
+pub mod hit;
+pub mod html;
+pub mod known;
+pub mod latency;
 pub mod messages;
+pub mod noise;
+pub mod pipeline;
+pub mod plugin;
+pub mod politeness;
+pub mod profile;
+pub mod proxy_pool;
+pub mod rng;
+pub mod robots;
+pub mod scheduler;
+pub mod soft404;
+pub mod stack;
+pub mod store;
+pub mod timing;
+pub mod tls;
 pub mod unit;
+pub mod variants;
+pub mod waf;
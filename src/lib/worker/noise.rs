@@ -0,0 +1,62 @@
+use std::{collections::HashMap, sync::Mutex};
+
+const STATIC_EXTENSIONS: &[&str] = &[
+    "css", "js", "map", "png", "jpg", "jpeg", "gif", "bmp", "webp", "svg", "ico", "woff", "woff2",
+    "ttf", "eot", "otf", "mp3", "mp4", "webm",
+];
+
+const STATIC_CONTENT_TYPES: &[&str] = &[
+    "image/",
+    "font/",
+    "audio/",
+    "video/",
+    "text/css",
+    "text/javascript",
+    "application/javascript",
+    "application/font",
+];
+
+/// Responses up to this size are considered placeholders when they repeat.
+const TINY_SIZE: u64 = 512;
+/// How many hits of the same tiny size make it a placeholder.
+const TINY_REPEATS: usize = 3;
+
+/// Heuristic recognizing favicons, stylesheets, scripts and similar static noise.
+#[derive(Debug, Default)]
+pub struct StaticAssetFilter {
+    tiny_sizes: Mutex<HashMap<u64, usize>>,
+}
+
+impl StaticAssetFilter {
+    pub fn is_static(&self, url: &str, content_type: Option<&str>, size: Option<u64>) -> bool {
+        let path = url.split(['?', '#']).next().unwrap_or_default();
+        let extension = path
+            .trim_end_matches('/')
+            .rsplit_once('/')
+            .and_then(|(_, file)| file.rsplit_once('.'))
+            .map(|(_, ext)| ext.to_ascii_lowercase());
+
+        if extension.is_some_and(|ext| STATIC_EXTENSIONS.contains(&ext.as_str())) {
+            return true;
+        }
+
+        if let Some(content_type) = content_type {
+            let content_type = content_type.to_ascii_lowercase();
+            if STATIC_CONTENT_TYPES
+                .iter()
+                .any(|prefix| content_type.starts_with(prefix))
+            {
+                return true;
+            }
+        }
+
+        if let Some(size) = size.filter(|size| *size <= TINY_SIZE) {
+            let mut tiny_sizes = self.tiny_sizes.lock().unwrap();
+            let seen = tiny_sizes.entry(size).or_default();
+            *seen += 1;
+            return *seen >= TINY_REPEATS;
+        }
+
+        false
+    }
+}
@@ -0,0 +1,88 @@
+use std::{fmt, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::lib::worker::candidates::Transform;
+
+/// Suffixes of the copies editors and admins leave next to a file.
+const BACKUP_SUFFIXES: &[&str] = &[".bak", ".old", ".orig", ".save", "~"];
+
+/// One pass of a multi-stage scan. Stages run in order, each on the target
+/// and the directories the earlier ones found.
+///
+/// ```toml
+/// [[stages]]
+/// name = "sweep"
+/// wordlist = "/usr/share/wordlists/common.txt"
+///
+/// [[stages]]
+/// name = "extensions"
+/// wordlist = "/usr/share/wordlists/raft-small-files.txt"
+/// extensions = ["php", "txt"]
+///
+/// [[stages]]
+/// name = "backups"
+/// backups = true
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Stage {
+    pub name: String,
+    /// Replaces the scan's wordlist.
+    pub wordlist: Option<PathBuf>,
+    /// Replace the scan's extensions when not empty.
+    pub extensions: Vec<String>,
+    /// Replace the scan's transforms when not empty.
+    pub transforms: Vec<Transform>,
+    /// Replaces the scan's recursion depth.
+    pub recursion: Option<usize>,
+    /// Request backup copies of the files found so far instead of words.
+    pub backups: bool,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.name.is_empty() {
+            f.write_str(&self.name)
+        } else if self.backups {
+            f.write_str("backups")
+        } else {
+            f.write_str("words")
+        }
+    }
+}
+
+/// Directories among the `found` URLs, the ones later stages scan.
+pub fn directories(found: &[Url]) -> impl Iterator<Item = &Url> {
+    found.iter().filter(|url| url.path().ends_with('/'))
+}
+
+/// Backup names of the files among `found` below `target`, as paths
+/// relative to it.
+pub fn backup_words(target: &Url, found: &[Url]) -> Vec<String> {
+    let mut words = Vec::new();
+
+    for url in found.iter().filter(|url| !url.path().ends_with('/')) {
+        let Some(path) = url.path().strip_prefix(target.path().trim_end_matches('/')) else {
+            continue;
+        };
+        let path = path.trim_start_matches('/');
+        if path.is_empty() {
+            continue;
+        }
+
+        words.extend(
+            BACKUP_SUFFIXES
+                .iter()
+                .map(|suffix| format!("{path}{suffix}")),
+        );
+        words.push(match path.rsplit_once('/') {
+            Some((dir, name)) => format!("{dir}/.{name}.swp"),
+            None => format!(".{path}.swp"),
+        });
+    }
+
+    words.dedup();
+    words
+}
@@ -0,0 +1,52 @@
+use anyhow::Result;
+
+use crate::lib::{
+    output::OutputSink,
+    worker::{builder::WorkerBuilder, candidates::WordTransform, filter::ResponseFilter},
+};
+
+/// Extension of the scan pipeline shipped by another crate, so that yadb
+/// can be extended without forking it.
+///
+/// Plugins are installed at compile time with
+/// [`WorkerBuilder::plugin`]; see `examples/plugin.rs`.
+pub trait Plugin {
+    /// Shown in errors.
+    fn name(&self) -> &str;
+
+    /// Registers the plugin's filters, sinks and transforms; an error fails
+    /// the build of the worker.
+    fn register(&self, registry: &mut PluginRegistry) -> Result<()>;
+}
+
+/// What a [`Plugin`] can add to a worker being built.
+pub struct PluginRegistry<'a> {
+    builder: &'a mut WorkerBuilder,
+}
+
+impl<'a> PluginRegistry<'a> {
+    pub fn new(builder: &'a mut WorkerBuilder) -> Self {
+        Self { builder }
+    }
+
+    /// Appends a filter run after the built-in ones.
+    pub fn register_filter(&mut self, filter: impl ResponseFilter + 'static) -> &mut Self {
+        self.builder.custom_filters.push(filter);
+        self
+    }
+
+    /// Adds a sink that receives hits and logs straight from the worker.
+    pub fn register_sink(&mut self, sink: impl OutputSink + 'static) -> &mut Self {
+        self.builder.sinks.push(sink);
+        self
+    }
+
+    /// Also tries every word rewritten by `transform`.
+    pub fn register_candidate_transform(
+        &mut self,
+        transform: impl WordTransform + 'static,
+    ) -> &mut Self {
+        self.builder.custom_transforms.push(transform);
+        self
+    }
+}
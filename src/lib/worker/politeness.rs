@@ -0,0 +1,75 @@
+use std::{fmt, time::Duration};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::lib::worker::profile::BrowserProfile;
+
+/// How hard a target is pushed; bundles pacing and request settings that
+/// explicit builder settings override one by one.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Politeness {
+    /// Slow, browser-like requests for fragile or closely watched targets.
+    Paranoid,
+    Normal,
+    /// As fast as the machine allows, no second tries.
+    Aggressive,
+}
+
+impl fmt::Display for Politeness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let str = match self {
+            Politeness::Paranoid => "paranoid",
+            Politeness::Normal => "normal",
+            Politeness::Aggressive => "aggressive",
+        };
+        f.write_str(str)
+    }
+}
+
+/// Settings a [`Politeness`] preset stands for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PolitenessSettings {
+    pub concurrency: usize,
+    /// Requests per second across all threads.
+    pub rate_limit: Option<u32>,
+    pub delay: Duration,
+    /// Upper bound of a random pause added to the delay.
+    pub jitter: Duration,
+    /// Extra attempts of requests that failed without a response.
+    pub retries: u32,
+    /// Headers, the `User-Agent` among them, of a browser instead of the default ones.
+    pub browser_profile: Option<BrowserProfile>,
+}
+
+impl Politeness {
+    pub fn settings(self) -> PolitenessSettings {
+        match self {
+            Politeness::Paranoid => PolitenessSettings {
+                concurrency: 2,
+                rate_limit: Some(2),
+                delay: Duration::from_millis(500),
+                jitter: Duration::from_millis(500),
+                retries: 3,
+                browser_profile: Some(BrowserProfile::Firefox),
+            },
+            Politeness::Normal => PolitenessSettings {
+                concurrency: 20,
+                rate_limit: Some(100),
+                delay: Duration::ZERO,
+                jitter: Duration::from_millis(50),
+                retries: 1,
+                browser_profile: None,
+            },
+            Politeness::Aggressive => PolitenessSettings {
+                concurrency: 100,
+                rate_limit: None,
+                delay: Duration::ZERO,
+                jitter: Duration::ZERO,
+                retries: 0,
+                browser_profile: None,
+            },
+        }
+    }
+}
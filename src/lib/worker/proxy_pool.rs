@@ -0,0 +1,133 @@
+use std::{
+    fs,
+    path::Path,
+    sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering},
+};
+
+use anyhow::{Context, Result, bail};
+use ureq::Agent;
+use url::Url;
+
+/// Failures in a row after which a proxy is dropped from the rotation.
+pub const MAX_FAILURES: u32 = 3;
+
+/// Reads a file of proxies, one URL per line; `#` starts a comment and
+/// `host:port` lines are taken as HTTP proxies.
+pub fn load(path: &Path) -> Result<Vec<Url>> {
+    let data =
+        fs::read_to_string(path).with_context(|| format!("Can't read {}", path.display()))?;
+
+    let mut proxies = Vec::new();
+    for line in data.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let url = if line.contains("://") {
+            Url::parse(line)
+        } else {
+            Url::parse(&format!("http://{line}"))
+        };
+        proxies.push(url.with_context(|| format!("Invalid proxy {line}"))?);
+    }
+
+    if proxies.is_empty() {
+        bail!("No proxies in {}", path.display());
+    }
+    Ok(proxies)
+}
+
+/// A proxy of a [`ProxyPool`] with the agent that goes through it.
+#[derive(Debug)]
+pub struct PooledProxy {
+    pub url: Url,
+    pub agent: Agent,
+    failures: AtomicU32,
+    dropped: AtomicBool,
+}
+
+/// Proxies the requests of a scan rotate through round-robin. Proxies that
+/// fail [`MAX_FAILURES`] times in a row are dropped, all but the last one.
+#[derive(Debug)]
+pub struct ProxyPool {
+    proxies: Vec<PooledProxy>,
+    next: AtomicUsize,
+    alive: AtomicUsize,
+}
+
+impl ProxyPool {
+    /// `None` without proxies.
+    pub fn new(proxies: impl IntoIterator<Item = (Url, Agent)>) -> Option<Self> {
+        let proxies: Vec<PooledProxy> = proxies
+            .into_iter()
+            .map(|(url, agent)| PooledProxy {
+                url,
+                agent,
+                failures: AtomicU32::new(0),
+                dropped: AtomicBool::new(false),
+            })
+            .collect();
+        if proxies.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            next: AtomicUsize::new(0),
+            alive: AtomicUsize::new(proxies.len()),
+            proxies,
+        })
+    }
+
+    /// The proxy whose turn it is.
+    pub fn next(&self) -> &PooledProxy {
+        for _ in 0..self.proxies.len() {
+            let index = self.next.fetch_add(1, Ordering::Relaxed) % self.proxies.len();
+            let proxy = &self.proxies[index];
+            if !proxy.dropped.load(Ordering::Relaxed) {
+                return proxy;
+            }
+        }
+
+        // Dropped meanwhile by other threads; one is always kept.
+        self.proxies
+            .iter()
+            .find(|proxy| !proxy.dropped.load(Ordering::Relaxed))
+            .unwrap_or(&self.proxies[0])
+    }
+
+    /// Records how a request through `proxy` went; `true` when this failure
+    /// dropped it from the rotation.
+    pub fn record(&self, proxy: &PooledProxy, ok: bool) -> bool {
+        if ok {
+            proxy.failures.store(0, Ordering::Relaxed);
+            return false;
+        }
+
+        if proxy.failures.fetch_add(1, Ordering::Relaxed) + 1 < MAX_FAILURES
+            || proxy.dropped.swap(true, Ordering::Relaxed)
+        {
+            return false;
+        }
+
+        let last = self
+            .alive
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |alive| {
+                (alive > 1).then(|| alive - 1)
+            })
+            .is_err();
+        if last {
+            proxy.dropped.store(false, Ordering::Relaxed);
+        }
+        !last
+    }
+
+    pub fn proxies(&self) -> &[PooledProxy] {
+        &self.proxies
+    }
+
+    /// Proxies still in the rotation.
+    pub fn alive(&self) -> usize {
+        self.alive.load(Ordering::Relaxed)
+    }
+}
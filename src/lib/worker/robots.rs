@@ -0,0 +1,123 @@
+use regex::Regex;
+
+/// Product token looked up among the `User-agent` lines of a robots.txt.
+pub const USER_AGENT_TOKEN: &str = "yadb";
+
+#[derive(Debug, Clone)]
+struct Rule {
+    allow: bool,
+    /// Path pattern as written in the file.
+    path: String,
+    pattern: Regex,
+}
+
+/// The `Allow` and `Disallow` rules of a robots.txt that apply to one user
+/// agent, as specified by RFC 9309: the groups naming the agent, or the `*`
+/// groups when none does.
+#[derive(Debug, Clone, Default)]
+pub struct RobotsRules {
+    rules: Vec<Rule>,
+}
+
+impl RobotsRules {
+    pub fn parse(text: &str, user_agent: &str) -> Self {
+        let user_agent = user_agent.to_ascii_lowercase();
+        let mut specific = Vec::new();
+        let mut any = Vec::new();
+        let mut names_agent = false;
+
+        let mut agents: Vec<String> = Vec::new();
+        let mut in_rules = false;
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+
+            match key.trim().to_ascii_lowercase().as_str() {
+                "user-agent" => {
+                    if in_rules {
+                        agents.clear();
+                        in_rules = false;
+                    }
+                    let agent = value.to_ascii_lowercase();
+                    names_agent |= agent == user_agent;
+                    agents.push(agent);
+                }
+                key @ ("allow" | "disallow") => {
+                    in_rules = true;
+                    // An empty `Disallow` allows everything, which is the default.
+                    let Some(rule) = Rule::new(key == "allow", value) else {
+                        continue;
+                    };
+                    if agents.contains(&user_agent) {
+                        specific.push(rule.clone());
+                    }
+                    if agents.iter().any(|agent| agent == "*") {
+                        any.push(rule);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            rules: if names_agent { specific } else { any },
+        }
+    }
+
+    /// Whether `path`, with its query, may be requested: the longest
+    /// matching rule decides, `Allow` winning a tie.
+    pub fn is_allowed(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|rule| rule.pattern.is_match(path))
+            .max_by_key(|rule| (rule.path.len(), rule.allow))
+            .is_none_or(|rule| rule.allow)
+    }
+
+    /// Rules of a robots.txt that couldn't be fetched because of a server
+    /// error, which RFC 9309 says to take as disallowing everything.
+    pub fn disallow_all() -> Self {
+        Self {
+            rules: Rule::new(false, "/").into_iter().collect(),
+        }
+    }
+
+    /// Number of `Disallow` rules.
+    pub fn disallowed(&self) -> usize {
+        self.rules.iter().filter(|rule| !rule.allow).count()
+    }
+}
+
+impl Rule {
+    /// `None` for an empty path.
+    fn new(allow: bool, path: &str) -> Option<Self> {
+        if path.is_empty() {
+            return None;
+        }
+
+        let (body, anchored) = match path.strip_suffix('$') {
+            Some(body) => (body, true),
+            None => (path, false),
+        };
+        let mut pattern = String::from("^");
+        pattern.push_str(
+            &body
+                .split('*')
+                .map(regex::escape)
+                .collect::<Vec<_>>()
+                .join(".*"),
+        );
+        if anchored {
+            pattern.push('$');
+        }
+
+        Some(Self {
+            allow,
+            path: path.to_string(),
+            pattern: Regex::new(&pattern).ok()?,
+        })
+    }
+}
@@ -0,0 +1,452 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock, mpsc::Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use ureq::http::HeaderMap;
+
+use crate::lib::worker::{control::WorkerControl, messages::WorkerMessage, rng::SeededRng};
+
+/// Upper bound of a single sleep, so stop requests are noticed quickly.
+const MAX_SLEEP: Duration = Duration::from_millis(200);
+/// First back-off after a 429 without `Retry-After`, doubled while 429s go on.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+/// Longest back-off, whatever `Retry-After` asks for.
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+/// Minimal interval between two published snapshots without a state change.
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(1);
+/// Minimal interval between any two snapshots, so rate limiting doesn't flood frontends.
+const SNAPSHOT_MIN_GAP: Duration = Duration::from_millis(100);
+
+/// What the scheduler is currently doing with request threads.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchedulerState {
+    Running,
+    /// Threads wait for tokens of the rate limit.
+    Limited,
+    /// Backing off after the target asked to slow down.
+    Throttled,
+    /// Threads join one by one during the ramp-up.
+    WarmingUp,
+    Paused,
+    Stopped,
+}
+
+/// Live view of the scheduler for progress displays.
+#[derive(Debug, Clone, Serialize)]
+pub struct SchedulerSnapshot {
+    pub state: SchedulerState,
+    /// Requests per second allowed across all threads.
+    pub rate_limit: Option<u32>,
+    pub permits: u64,
+    /// Time threads spent waiting for a permit, summed over all threads.
+    pub waited: Duration,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+    refilled: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u32) -> Self {
+        Self {
+            rate: f64::from(rate),
+            tokens: f64::from(rate),
+            refilled: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.refilled).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate.max(1.0));
+        self.refilled = now;
+    }
+
+    /// Time until a token is available.
+    fn wait_time(&self) -> Duration {
+        Duration::from_secs_f64(((1.0 - self.tokens) / self.rate).max(0.0))
+    }
+}
+
+#[derive(Debug)]
+struct Budgets {
+    global: Option<TokenBucket>,
+    hosts: HashMap<String, TokenBucket>,
+    throttled_until: Option<Instant>,
+    /// Back-off after the next 429 without `Retry-After`.
+    backoff: Duration,
+    permits: u64,
+    waited: Duration,
+    published: Option<(SchedulerState, Instant)>,
+}
+
+/// Hands out request permits to all threads of a worker.
+///
+/// Every pacing rule (pause, throttling, the global rate limit and per-host
+/// budgets) lives here, so request loops only have to call
+/// [`Pacer::acquire`] before sending.
+#[derive(Debug)]
+pub struct Scheduler {
+    control: Arc<WorkerControl>,
+    message_sender: Arc<Sender<WorkerMessage>>,
+    rate_limit: Option<u32>,
+    host_rate_limit: Option<u32>,
+    /// Time over which threads start, so concurrency grows gradually.
+    ramp_up: Option<Duration>,
+    /// When the first thread asked for a permit.
+    ramp_started: OnceLock<Instant>,
+    budgets: Mutex<Budgets>,
+}
+
+impl Scheduler {
+    pub fn new(
+        control: Arc<WorkerControl>,
+        message_sender: Arc<Sender<WorkerMessage>>,
+        rate_limit: Option<u32>,
+        host_rate_limit: Option<u32>,
+        ramp_up: Option<Duration>,
+    ) -> Self {
+        Self {
+            control,
+            message_sender,
+            rate_limit,
+            host_rate_limit,
+            ramp_up,
+            ramp_started: OnceLock::new(),
+            budgets: Mutex::new(Budgets {
+                global: rate_limit.map(TokenBucket::new),
+                hosts: HashMap::new(),
+                throttled_until: None,
+                backoff: INITIAL_BACKOFF,
+                permits: 0,
+                waited: Duration::ZERO,
+                published: None,
+            }),
+        }
+    }
+
+    /// Per-thread handle keeping at least `delay` between its own requests.
+    pub fn pacer(&self, delay: Duration) -> Pacer<'_> {
+        Pacer {
+            scheduler: self,
+            delay,
+            slot: None,
+            jitter: None,
+            wait: delay,
+            last: None,
+        }
+    }
+
+    /// Holds every thread back for `duration`.
+    pub fn throttle(&self, duration: Duration) {
+        let until = Instant::now() + duration;
+        let mut budgets = self.budgets.lock().unwrap();
+        budgets.throttled_until = Some(budgets.throttled_until.map_or(until, |t| t.max(until)));
+        self.publish(&mut budgets, SchedulerState::Throttled);
+    }
+
+    /// Backs off after the target answered 429, for as long as its
+    /// `Retry-After` asks or else twice as long as the last time. Returns the
+    /// back-off when this starts a new one rather than joining the current.
+    pub fn too_many_requests(&self, retry_after: Option<Duration>) -> Option<Duration> {
+        let mut budgets = self.budgets.lock().unwrap();
+        let now = Instant::now();
+        let backing_off = budgets.throttled_until.is_some_and(|until| until > now);
+        let duration = retry_after.unwrap_or(budgets.backoff).min(MAX_BACKOFF);
+        if !backing_off {
+            budgets.backoff = (budgets.backoff * 2).min(MAX_BACKOFF);
+        }
+        drop(budgets);
+
+        self.throttle(duration);
+        (!backing_off).then_some(duration)
+    }
+
+    /// Starts the back-off over once the target answers normally again.
+    pub fn recovered(&self) {
+        let mut budgets = self.budgets.lock().unwrap();
+        if budgets.throttled_until.is_none() {
+            budgets.backoff = INITIAL_BACKOFF;
+        }
+    }
+
+    pub fn snapshot(&self) -> SchedulerSnapshot {
+        let budgets = self.budgets.lock().unwrap();
+        self.snapshot_of(&budgets, self.state_of(&budgets))
+    }
+
+    fn state_of(&self, budgets: &Budgets) -> SchedulerState {
+        if self.control.is_stopped() {
+            SchedulerState::Stopped
+        } else if self.control.is_paused() {
+            SchedulerState::Paused
+        } else if budgets
+            .throttled_until
+            .is_some_and(|until| until > Instant::now())
+        {
+            SchedulerState::Throttled
+        } else if self.warming_up() {
+            SchedulerState::WarmingUp
+        } else {
+            SchedulerState::Running
+        }
+    }
+
+    fn warming_up(&self) -> bool {
+        match (self.ramp_up, self.ramp_started.get()) {
+            (Some(ramp_up), Some(started)) => started.elapsed() < ramp_up,
+            _ => false,
+        }
+    }
+
+    /// When thread `index` of `count` may send its first request.
+    fn ramp_slot(&self, index: usize, count: usize) -> Option<Instant> {
+        let ramp_up = self.ramp_up?;
+        let started = *self.ramp_started.get_or_init(Instant::now);
+        Some(started + ramp_up.mul_f64(index as f64 / count.max(1) as f64))
+    }
+
+    fn snapshot_of(&self, budgets: &Budgets, state: SchedulerState) -> SchedulerSnapshot {
+        SchedulerSnapshot {
+            state,
+            rate_limit: self.rate_limit,
+            permits: budgets.permits,
+            waited: budgets.waited,
+        }
+    }
+
+    /// Sends a snapshot when the state changed or the last one is stale.
+    fn publish(&self, budgets: &mut Budgets, state: SchedulerState) {
+        let now = Instant::now();
+        if let Some((published, at)) = budgets.published {
+            let since = now.duration_since(at);
+            if since < SNAPSHOT_MIN_GAP || (published == state && since < SNAPSHOT_INTERVAL) {
+                return;
+            }
+        }
+
+        budgets.published = Some((state, now));
+        let _ = self
+            .message_sender
+            .send(WorkerMessage::Scheduler(self.snapshot_of(budgets, state)));
+    }
+
+    /// Blocks until a request to `host` may be sent; `false` once stopped.
+    fn acquire(&self, host: &str) -> bool {
+        let started = Instant::now();
+
+        loop {
+            match self.try_acquire(host, started) {
+                Permit::Granted => return true,
+                Permit::Stopped => return false,
+                Permit::Paused => self.control.wait_while_paused(),
+                Permit::Wait(wait) => thread::sleep(wait),
+            }
+        }
+    }
+
+    /// [`Scheduler::acquire`] for tasks of the async engine.
+    #[cfg(feature = "async")]
+    async fn acquire_async(&self, host: &str) -> bool {
+        let started = Instant::now();
+
+        loop {
+            match self.try_acquire(host, started) {
+                Permit::Granted => return true,
+                Permit::Stopped => return false,
+                Permit::Paused => tokio::time::sleep(MAX_SLEEP).await,
+                Permit::Wait(wait) => tokio::time::sleep(wait).await,
+            }
+        }
+    }
+
+    /// Grants a permit to a request to `host` asked for at `started` if one
+    /// is free, or says what to wait for.
+    fn try_acquire(&self, host: &str, started: Instant) -> Permit {
+        if self.control.is_paused() {
+            self.publish(&mut self.budgets.lock().unwrap(), SchedulerState::Paused);
+            return Permit::Paused;
+        }
+
+        if self.control.is_stopped() {
+            return Permit::Stopped;
+        }
+
+        let mut budgets = self.budgets.lock().unwrap();
+        let now = Instant::now();
+
+        if let Some(until) = budgets.throttled_until {
+            if until > now {
+                self.publish(&mut budgets, SchedulerState::Throttled);
+                return Permit::Wait((until - now).min(MAX_SLEEP));
+            }
+            budgets.throttled_until = None;
+        }
+
+        let host_rate_limit = self.host_rate_limit;
+        let Budgets { global, hosts, .. } = &mut *budgets;
+        let host_bucket = host_rate_limit.map(|rate| {
+            hosts
+                .entry(host.to_string())
+                .or_insert_with(|| TokenBucket::new(rate))
+        });
+
+        let mut wait = Duration::ZERO;
+        for bucket in global.iter_mut().chain(host_bucket) {
+            bucket.refill(now);
+            wait = wait.max(bucket.wait_time());
+        }
+
+        if !wait.is_zero() {
+            self.publish(&mut budgets, SchedulerState::Limited);
+            return Permit::Wait(wait.min(MAX_SLEEP));
+        }
+
+        let Budgets { global, hosts, .. } = &mut *budgets;
+        if let Some(bucket) = global {
+            bucket.tokens -= 1.0;
+        }
+        if let Some(bucket) = hosts.get_mut(host) {
+            bucket.tokens -= 1.0;
+        }
+
+        budgets.permits += 1;
+        budgets.waited += started.elapsed();
+        let state = if self.warming_up() {
+            SchedulerState::WarmingUp
+        } else {
+            SchedulerState::Running
+        };
+        self.publish(&mut budgets, state);
+        Permit::Granted
+    }
+}
+
+/// Answer of the scheduler to a thread asking for a permit.
+enum Permit {
+    Granted,
+    /// Rate limited or throttled; ask again after this long.
+    Wait(Duration),
+    Paused,
+    Stopped,
+}
+
+/// A request thread's view of the [`Scheduler`].
+pub struct Pacer<'a> {
+    scheduler: &'a Scheduler,
+    delay: Duration,
+    /// Index of the thread among its siblings, for the ramp-up.
+    slot: Option<(usize, usize)>,
+    /// Upper bound of the random addition to the delay and its source.
+    jitter: Option<(Duration, SeededRng)>,
+    /// Delay before the next request, jitter included.
+    wait: Duration,
+    last: Option<Instant>,
+}
+
+impl Pacer<'_> {
+    /// Adds a random pause of up to `jitter` to every delay.
+    pub fn jitter(mut self, jitter: Duration, seed: u64) -> Self {
+        if !jitter.is_zero() {
+            self.jitter = Some((jitter, SeededRng::new(seed)));
+        }
+        self
+    }
+
+    /// Makes the thread, `index` of `count`, join the scan at its share of
+    /// the scheduler's ramp-up.
+    pub fn ramp(mut self, index: usize, count: usize) -> Self {
+        self.slot = Some((index, count));
+        self
+    }
+
+    /// Waits for the thread's delay and a scheduler permit; `false` once stopped.
+    pub fn acquire(&mut self, host: &str) -> bool {
+        if let Some(ready) = self.ready() {
+            while Instant::now() < ready {
+                if self.scheduler.control.is_stopped() {
+                    return false;
+                }
+                thread::sleep(
+                    ready
+                        .saturating_duration_since(Instant::now())
+                        .min(MAX_SLEEP),
+                );
+            }
+        }
+
+        let granted = self.scheduler.acquire(host);
+        self.granted();
+        granted
+    }
+
+    /// [`Pacer::acquire`] for tasks of the async engine.
+    #[cfg(feature = "async")]
+    pub async fn acquire_async(&mut self, host: &str) -> bool {
+        if let Some(ready) = self.ready() {
+            while Instant::now() < ready {
+                if self.scheduler.control.is_stopped() {
+                    return false;
+                }
+                tokio::time::sleep(
+                    ready
+                        .saturating_duration_since(Instant::now())
+                        .min(MAX_SLEEP),
+                )
+                .await;
+            }
+        }
+
+        let granted = self.scheduler.acquire_async(host).await;
+        self.granted();
+        granted
+    }
+
+    /// When the thread's delay or ramp-up slot is over, if it has one.
+    fn ready(&self) -> Option<Instant> {
+        let ready = match (self.last, self.slot) {
+            (Some(last), _) => Some(last + self.wait),
+            (None, Some((index, count))) => self.scheduler.ramp_slot(index, count),
+            (None, None) => None,
+        }?;
+        if self.last.is_none() && Instant::now() < ready {
+            self.scheduler.publish(
+                &mut self.scheduler.budgets.lock().unwrap(),
+                SchedulerState::WarmingUp,
+            );
+        }
+        Some(ready)
+    }
+
+    /// Starts the delay before the next request.
+    fn granted(&mut self) {
+        self.last = Some(Instant::now());
+        self.wait = match &mut self.jitter {
+            Some((jitter, rng)) => {
+                let extra = rng.next_u64() % (jitter.as_millis() as u64 + 1);
+                self.delay + Duration::from_millis(extra)
+            }
+            None => self.delay,
+        };
+    }
+}
+
+/// How long a response asks to wait with its `Retry-After` header, given in
+/// seconds or as an HTTP date.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get("retry-after")?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&Utc) - Utc::now()).to_std().ok()
+}
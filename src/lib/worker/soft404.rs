@@ -0,0 +1,59 @@
+use ureq::http::HeaderMap;
+
+use crate::lib::worker::filter::{BodyCount, ResponseInfo};
+
+/// What a directory answers for a path that doesn't exist when that isn't a
+/// plain 404: an SPA serving its index for every path, a redirect to a
+/// login page and the like. Learned anew for every directory, since
+/// directories of one site often answer differently.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Soft404 {
+    status: u16,
+    size: Option<u64>,
+    words: Option<u64>,
+    /// Redirect target without its query, which may carry the path.
+    location: Option<String>,
+}
+
+impl Soft404 {
+    /// Learns from the answer to a probe; `None` for a 404, which the status
+    /// filter drops anyway.
+    pub fn learn(probe: &ResponseInfo) -> Option<Self> {
+        if probe.status == 404 {
+            return None;
+        }
+
+        Some(Self {
+            status: probe.status,
+            size: probe.size,
+            words: probe.body.map(|body| BodyCount::Words.of(body)),
+            location: location(probe.headers),
+        })
+    }
+
+    /// Whether a response looks like the answer to the probe: the same
+    /// status and redirect target, or the same status and size or number of
+    /// words, as pages reflecting the requested path differ in size only.
+    pub fn matches(&self, response: &ResponseInfo) -> bool {
+        if response.status != self.status {
+            return false;
+        }
+
+        if self.location.is_some() {
+            return location(response.headers) == self.location;
+        }
+
+        let words = response.body.map(|body| BodyCount::Words.of(body));
+        (self.size.is_some() && response.size == self.size)
+            || (self.words.is_some() && words == self.words)
+    }
+
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+}
+
+fn location(headers: &HeaderMap) -> Option<String> {
+    let location = headers.get("location")?.to_str().ok()?;
+    Some(location.split('?').next().unwrap_or_default().to_string())
+}
@@ -0,0 +1,126 @@
+use std::fmt;
+
+use serde::Serialize;
+use ureq::http::HeaderMap;
+
+/// Software a target was recognized to run on.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stack {
+    WordPress,
+    Tomcat,
+    Jenkins,
+}
+
+impl fmt::Display for Stack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let str = match self {
+            Stack::WordPress => "WordPress",
+            Stack::Tomcat => "Apache Tomcat",
+            Stack::Jenkins => "Jenkins",
+        };
+        f.write_str(str)
+    }
+}
+
+/// A path worth requesting on a stack and what finding it means.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Check {
+    /// Relative to the scanned URL.
+    pub path: &'static str,
+    pub tag: &'static str,
+}
+
+const fn check(path: &'static str, tag: &'static str) -> Check {
+    Check { path, tag }
+}
+
+const WORDPRESS: &[Check] = &[
+    check("readme.html", "version-disclosure"),
+    check("license.txt", "version-disclosure"),
+    check("feed/", "version-disclosure"),
+    check("wp-json/wp/v2/users", "user-enumeration"),
+    check("xmlrpc.php", "xmlrpc"),
+    check("wp-config.php.bak", "config-backup"),
+    check("wp-config.php~", "config-backup"),
+    check("wp-content/debug.log", "debug-log"),
+    check("wp-content/uploads/", "directory-listing"),
+    check("wp-admin/install.php", "installer"),
+];
+
+const TOMCAT: &[Check] = &[
+    check("manager/html", "admin-panel"),
+    check("manager/status", "admin-panel"),
+    check("host-manager/html", "admin-panel"),
+    check("docs/", "version-disclosure"),
+    check("RELEASE-NOTES.txt", "version-disclosure"),
+    check("examples/servlets/", "examples"),
+    check("examples/jsp/snp/snoop.jsp", "examples"),
+];
+
+const JENKINS: &[Check] = &[
+    check("script", "script-console"),
+    check("manage", "admin-panel"),
+    check("api/json", "api"),
+    check("asynchPeople/", "user-enumeration"),
+    check("whoAmI/", "version-disclosure"),
+    check("computer/", "nodes"),
+    check("credentials/", "credentials"),
+];
+
+impl Stack {
+    /// Sensitive and version-disclosing paths of the stack.
+    pub fn checks(self) -> &'static [Check] {
+        match self {
+            Stack::WordPress => WORDPRESS,
+            Stack::Tomcat => TOMCAT,
+            Stack::Jenkins => JENKINS,
+        }
+    }
+
+    /// Tag of findings made by the stack's checks.
+    pub fn tag(self) -> &'static str {
+        match self {
+            Stack::WordPress => "wordpress",
+            Stack::Tomcat => "tomcat",
+            Stack::Jenkins => "jenkins",
+        }
+    }
+}
+
+/// Recognizes the stack of a target by the headers and body of its main page.
+pub fn detect(headers: &HeaderMap, body: &str) -> Option<Stack> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_ascii_lowercase()
+    };
+    let body = body.to_ascii_lowercase();
+
+    if headers.contains_key("x-jenkins")
+        || headers.contains_key("x-hudson")
+        || (body.contains("/static/") && body.contains("jenkins"))
+    {
+        return Some(Stack::Jenkins);
+    }
+
+    if header("link").contains("api.w.org")
+        || body.contains("/wp-content/")
+        || body.contains("/wp-includes/")
+    {
+        return Some(Stack::WordPress);
+    }
+
+    if header("server").contains("coyote") || body.contains("apache tomcat") {
+        return Some(Stack::Tomcat);
+    }
+
+    None
+}
+
+/// Statuses that show a checked path exists.
+pub fn is_found(status: u16) -> bool {
+    matches!(status, 200..=299 | 401)
+}
@@ -0,0 +1,199 @@
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader, Write},
+    mem,
+    path::Path,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use serde::{Serialize, Serializer, ser::SerializeSeq};
+use tempfile::NamedTempFile;
+
+use crate::lib::worker::hit::HitResult;
+
+pub const DEFAULT_MAX_STORED_HITS: usize = 100_000;
+pub const DEFAULT_MAX_STORED_BYTES: usize = 64 * 1024 * 1024;
+
+static REVISION: AtomicU64 = AtomicU64::new(0);
+
+/// How many hits a [`ResultStore`] keeps in memory before moving the rest to disk.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StoreLimits {
+    pub max_hits: usize,
+    /// Estimated heap size of the kept hits.
+    pub max_bytes: usize,
+}
+
+impl Default for StoreLimits {
+    fn default() -> Self {
+        Self {
+            max_hits: DEFAULT_MAX_STORED_HITS,
+            max_bytes: DEFAULT_MAX_STORED_BYTES,
+        }
+    }
+}
+
+struct Spill {
+    /// Made with a random name and only readable by its owner, as the
+    /// temporary directory may be shared; removed when dropped.
+    file: NamedTempFile,
+    count: usize,
+}
+
+/// Hits of a scan, kept in memory up to [`StoreLimits`] and appended to a
+/// temporary JSON Lines file past them.
+///
+/// The file is removed when the store is dropped.
+pub struct ResultStore {
+    limits: StoreLimits,
+    memory: Vec<HitResult>,
+    bytes: usize,
+    spill: Option<Spill>,
+    revision: u64,
+}
+
+impl fmt::Debug for ResultStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResultStore")
+            .field("in_memory", &self.memory.len())
+            .field("spilled", &self.spilled())
+            .finish()
+    }
+}
+
+impl Default for ResultStore {
+    fn default() -> Self {
+        Self::new(StoreLimits::default())
+    }
+}
+
+impl ResultStore {
+    pub fn new(limits: StoreLimits) -> Self {
+        Self {
+            limits,
+            memory: Vec::new(),
+            bytes: 0,
+            spill: None,
+            revision: next_revision(),
+        }
+    }
+
+    /// Stores a hit, writing it to the spill file once the limits are reached.
+    pub fn push(&mut self, hit: HitResult) -> io::Result<()> {
+        let size = estimated_size(&hit);
+        if self.spill.is_none()
+            && self.memory.len() < self.limits.max_hits
+            && self.bytes + size <= self.limits.max_bytes
+        {
+            self.bytes += size;
+            self.memory.push(hit);
+            self.revision = next_revision();
+            return Ok(());
+        }
+
+        let spill = match &mut self.spill {
+            Some(spill) => spill,
+            None => self.spill.insert(Self::open_spill()?),
+        };
+
+        let mut line = serde_json::to_vec(&hit)?;
+        line.push(b'\n');
+        spill.file.write_all(&line)?;
+        spill.count += 1;
+        self.revision = next_revision();
+        Ok(())
+    }
+
+    fn open_spill() -> io::Result<Spill> {
+        let file = tempfile::Builder::new()
+            .prefix("yadb-")
+            .suffix(".jsonl")
+            .tempfile()?;
+
+        Ok(Spill { file, count: 0 })
+    }
+
+    pub fn len(&self) -> usize {
+        self.memory.len() + self.spilled()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of hits moved to disk.
+    pub fn spilled(&self) -> usize {
+        self.spill.as_ref().map_or(0, |spill| spill.count)
+    }
+
+    pub fn spill_path(&self) -> Option<&Path> {
+        self.spill.as_ref().map(|spill| spill.file.path())
+    }
+
+    /// Hits kept in memory, the first ones found.
+    pub fn in_memory(&self) -> &[HitResult] {
+        &self.memory
+    }
+
+    /// Changes the latest in-memory hit for `url`; hits already spilled to
+    /// disk are left as they are. Returns whether the hit was found.
+    pub fn annotate(&mut self, url: &str, f: impl FnOnce(&mut HitResult)) -> bool {
+        match self.memory.iter_mut().rev().find(|hit| hit.url == url) {
+            Some(hit) => {
+                f(hit);
+                self.revision = next_revision();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Changes with every hit stored or annotated, so views built from the
+    /// hits can tell when they are out of date. No two stores share one.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Every hit in the order it was stored, reading spilled ones back from disk.
+    pub fn iter(&self) -> io::Result<impl Iterator<Item = HitResult> + '_> {
+        let spilled = match &self.spill {
+            Some(spill) => Some(BufReader::new(File::open(spill.file.path())?)),
+            None => None,
+        };
+
+        let spilled = spilled
+            .into_iter()
+            .flat_map(BufRead::lines)
+            .map_while(Result::ok)
+            .filter_map(|line| serde_json::from_str(&line).ok());
+
+        Ok(self.memory.iter().cloned().chain(spilled))
+    }
+}
+
+impl Serialize for ResultStore {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let hits = self.iter().map_err(serde::ser::Error::custom)?;
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for hit in hits {
+            seq.serialize_element(&hit)?;
+        }
+        seq.end()
+    }
+}
+
+/// Rough heap footprint of a hit.
+fn estimated_size(hit: &HitResult) -> usize {
+    let text = |value: &Option<String>| value.as_ref().map_or(0, String::len);
+    mem::size_of::<HitResult>()
+        + hit.url.len()
+        + text(&hit.title)
+        + text(&hit.content_type)
+        + text(&hit.etag)
+        + text(&hit.last_modified)
+}
+
+fn next_revision() -> u64 {
+    REVISION.fetch_add(1, Ordering::Relaxed)
+}
@@ -0,0 +1,128 @@
+use std::{
+    fmt,
+    io::{self, Read, Write},
+    net::{IpAddr, TcpStream, ToSocketAddrs},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::lib::worker::{bind, config::HttpMethod, tls};
+
+/// How long each phase of requesting a URL over a fresh connection took, in
+/// milliseconds. Slow DNS or connects point at the network, a slow first
+/// byte at the application.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PhaseTimings {
+    pub dns_ms: f64,
+    pub connect_ms: f64,
+    /// `None` for plain HTTP.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_ms: Option<f64>,
+    /// From sending the request to the first byte of the response.
+    pub ttfb_ms: f64,
+}
+
+impl fmt::Display for PhaseTimings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "dns {:.1}ms, connect {:.1}ms",
+            self.dns_ms, self.connect_ms
+        )?;
+        if let Some(tls) = self.tls_ms {
+            write!(f, ", tls {tls:.1}ms")?;
+        }
+        write!(f, ", ttfb {:.1}ms", self.ttfb_ms)
+    }
+}
+
+/// Requests `url` with `method` and `headers` over its own socket, from one
+/// of the `bind` addresses if there are any, timing each phase. `host`
+/// replaces the `Host` header and SNI, for virtual hosts.
+pub fn measure(
+    url: &Url,
+    method: HttpMethod,
+    host: Option<&str>,
+    headers: &[(&str, &str)],
+    bind: &[IpAddr],
+    timeout: Duration,
+) -> io::Result<PhaseTimings> {
+    let addr = url
+        .host_str()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "URL without a host"))?;
+    let port = url.port_or_known_default().unwrap_or(80);
+    let host = host.unwrap_or(addr);
+
+    let start = Instant::now();
+    let socket = (addr, port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "host address not found"))?;
+    let dns = start.elapsed();
+
+    let start = Instant::now();
+    let tcp = if bind.is_empty() {
+        TcpStream::connect_timeout(&socket, timeout)?
+    } else {
+        let local = bind
+            .iter()
+            .find(|local| local.is_ipv4() == socket.is_ipv4())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::AddrNotAvailable,
+                    "No bind address matches the target address family",
+                )
+            })?;
+        bind::connect_from(*local, socket, Some(timeout))?
+    };
+    tcp.set_read_timeout(Some(timeout))?;
+    tcp.set_write_timeout(Some(timeout))?;
+    let connect = start.elapsed();
+
+    let mut path = url.path().to_string();
+    if let Some(query) = url.query() {
+        path = format!("{path}?{query}");
+    }
+    let mut request = format!("{method} {path} HTTP/1.1\r\nHost: {host}\r\n");
+    for (name, value) in headers {
+        request.push_str(&format!("{name}: {value}\r\n"));
+    }
+    if matches!(method, HttpMethod::Post | HttpMethod::Put) {
+        request.push_str("Content-Length: 0\r\n");
+    }
+    request.push_str("Connection: close\r\n\r\n");
+
+    let (tls, ttfb) = if url.scheme() == "https" {
+        let start = Instant::now();
+        let mut stream = tls::handshake(tcp, host)?;
+        let tls = start.elapsed();
+        (Some(tls), first_byte(&mut stream, &request)?)
+    } else {
+        (None, first_byte(&mut { tcp }, &request)?)
+    };
+
+    Ok(PhaseTimings {
+        dns_ms: millis(dns),
+        connect_ms: millis(connect),
+        tls_ms: tls.map(millis),
+        ttfb_ms: millis(ttfb),
+    })
+}
+
+/// Sends `request` and waits for the first byte of the answer.
+fn first_byte(stream: &mut (impl Read + Write), request: &str) -> io::Result<Duration> {
+    let start = Instant::now();
+    stream.write_all(request.as_bytes())?;
+    stream.flush()?;
+    let mut byte = [0; 1];
+    if stream.read(&mut byte)? == 0 {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(start.elapsed())
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_micros() as f64 / 1000.0
+}
@@ -0,0 +1,255 @@
+use std::{
+    fmt, io,
+    net::{TcpStream, ToSocketAddrs},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::NaiveDateTime;
+use rustls::{
+    ClientConfig, ClientConnection, DigitallySignedStruct, ProtocolVersion, RootCertStore,
+    SignatureScheme, StreamOwned,
+    client::{
+        WebPkiServerVerifier,
+        danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    },
+    pki_types::{CertificateDer, ServerName, UnixTime},
+};
+use serde::{Deserialize, Serialize};
+
+/// OID of the common name attribute, 2.5.4.3.
+const COMMON_NAME: &[u8] = &[0x55, 0x04, 0x03];
+
+/// What a TLS handshake with a host negotiated and the certificate it got.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TlsInfo {
+    /// Name sent as SNI.
+    pub host: String,
+    pub port: u16,
+    pub version: String,
+    pub cipher: String,
+    /// Common name of the certificate subject.
+    pub subject: Option<String>,
+    pub not_after: Option<String>,
+    /// Why the certificate wouldn't be trusted, such as an expired one or one
+    /// issued for another name.
+    pub problem: Option<String>,
+}
+
+impl fmt::Display for TlsInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{} {} {}, certificate {} valid until {}",
+            self.host,
+            self.port,
+            self.version,
+            self.cipher,
+            self.subject.as_deref().unwrap_or("-"),
+            self.not_after.as_deref().unwrap_or("-"),
+        )?;
+        if let Some(problem) = &self.problem {
+            write!(f, " ({problem})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Accepts any certificate so the handshake completes, remembering why the
+/// usual verification would have rejected it.
+#[derive(Debug)]
+struct RecordingVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    problem: Mutex<Option<String>>,
+}
+
+impl ServerCertVerifier for RecordingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        if let Err(err) = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        ) {
+            *self.problem.lock().unwrap() = Some(err.to_string());
+        }
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Client connection sending `host` as SNI that completes the handshake
+/// whatever the certificate, and its verifier.
+fn client(host: &str) -> io::Result<(ClientConnection, Arc<RecordingVerifier>)> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let roots = Arc::new(RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    });
+    let verifier = Arc::new(RecordingVerifier {
+        inner: WebPkiServerVerifier::builder_with_provider(roots, provider.clone())
+            .build()
+            .map_err(io::Error::other)?,
+        problem: Mutex::new(None),
+    });
+    let config = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(io::Error::other)?
+        .dangerous()
+        .with_custom_certificate_verifier(verifier.clone())
+        .with_no_client_auth();
+
+    let server_name = ServerName::try_from(host.to_string())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    let conn = ClientConnection::new(Arc::new(config), server_name).map_err(io::Error::other)?;
+    Ok((conn, verifier))
+}
+
+/// Handshakes over `tcp`, sending `host` as SNI, accepting any certificate.
+pub fn handshake(
+    mut tcp: TcpStream,
+    host: &str,
+) -> io::Result<StreamOwned<ClientConnection, TcpStream>> {
+    let (mut conn, _) = client(host)?;
+    while conn.is_handshaking() {
+        conn.complete_io(&mut tcp)?;
+    }
+    Ok(StreamOwned::new(conn, tcp))
+}
+
+/// Handshakes with `addr`, sending `host` as SNI, and reports what was
+/// negotiated. No HTTP is spoken.
+pub fn probe(addr: (&str, u16), host: &str, timeout: Duration) -> io::Result<TlsInfo> {
+    let (mut conn, verifier) = client(host)?;
+
+    let addr = addr
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "host address not found"))?;
+    let mut tcp = TcpStream::connect_timeout(&addr, timeout)?;
+    tcp.set_read_timeout(Some(timeout))?;
+    tcp.set_write_timeout(Some(timeout))?;
+    while conn.is_handshaking() {
+        conn.complete_io(&mut tcp)?;
+    }
+
+    let (subject, not_after) = conn
+        .peer_certificates()
+        .and_then(|certs| certs.first())
+        .and_then(|cert| certificate_details(cert))
+        .unwrap_or_default();
+
+    let problem = verifier.problem.lock().unwrap().take();
+    Ok(TlsInfo {
+        host: host.to_string(),
+        port: addr.port(),
+        version: match conn.protocol_version() {
+            Some(ProtocolVersion::TLSv1_3) => "TLS 1.3".to_string(),
+            Some(ProtocolVersion::TLSv1_2) => "TLS 1.2".to_string(),
+            Some(version) => format!("{version:?}"),
+            None => "-".to_string(),
+        },
+        cipher: conn
+            .negotiated_cipher_suite()
+            .map_or_else(|| "-".to_string(), |suite| format!("{:?}", suite.suite())),
+        subject,
+        not_after,
+        problem,
+    })
+}
+
+/// Subject common name and end of validity of a DER certificate.
+fn certificate_details(cert: &[u8]) -> Option<(Option<String>, Option<String>)> {
+    let (_, cert, _) = der(cert)?;
+    let (_, tbs, _) = der(cert)?;
+
+    // The explicitly tagged version is optional; the serial number follows.
+    let (tag, _, rest) = der(tbs)?;
+    let rest = if tag == 0xa0 { der(rest)?.2 } else { rest };
+    let (_, _signature, rest) = der(rest)?;
+    let (_, _issuer, rest) = der(rest)?;
+    let (_, validity, rest) = der(rest)?;
+    let (_, subject, _) = der(rest)?;
+
+    let (_, _not_before, validity) = der(validity)?;
+    let not_after = der(validity).and_then(|(tag, time, _)| parse_time(tag, time));
+    Some((common_name(subject), not_after))
+}
+
+fn common_name(mut name: &[u8]) -> Option<String> {
+    while let Some((_, set, rest)) = der(name) {
+        let (_, attribute, _) = der(set)?;
+        let (_, oid, value) = der(attribute)?;
+        if oid == COMMON_NAME {
+            let (_, value, _) = der(value)?;
+            return Some(String::from_utf8_lossy(value).into_owned());
+        }
+        name = rest;
+    }
+    None
+}
+
+/// Formats a `UTCTime` or `GeneralizedTime`.
+fn parse_time(tag: u8, time: &[u8]) -> Option<String> {
+    let time = std::str::from_utf8(time).ok()?;
+    let time = match tag {
+        0x17 => {
+            let century = if time.get(..2)? < "50" { "20" } else { "19" };
+            format!("{century}{time}")
+        }
+        0x18 => time.to_string(),
+        _ => return None,
+    };
+    let time = NaiveDateTime::parse_from_str(&time, "%Y%m%d%H%M%SZ").ok()?;
+    Some(time.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+}
+
+/// Splits a DER element off `input`: its tag, contents and what follows.
+fn der(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = input.split_first()?;
+    let (&first, rest) = rest.split_first()?;
+
+    let (len, rest) = if first < 0x80 {
+        (first as usize, rest)
+    } else {
+        let octets = (first & 0x7f) as usize;
+        if octets == 0 || octets > 4 || rest.len() < octets {
+            return None;
+        }
+        let (len, rest) = rest.split_at(octets);
+        (
+            len.iter().fold(0, |len, byte| len << 8 | *byte as usize),
+            rest,
+        )
+    };
+
+    (rest.len() >= len).then(|| (tag, &rest[..len], &rest[len..]))
+}
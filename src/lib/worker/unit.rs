@@ -1,202 +1,2216 @@
-use anyhow::Result;
-use std::io::{BufRead, BufReader};
-use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufReader, ErrorKind, Read};
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex, OnceLock};
 use std::thread::{self, ScopedJoinHandle};
-use std::time::Duration;
-use std::{fs::File, path::PathBuf};
+use std::time::{Duration, Instant};
 use thiserror::Error;
-use ureq::{Agent, Proxy};
+use ureq::http::{HeaderMap, Response};
+use ureq::typestate::WithoutBody;
+use ureq::unversioned::resolver::DefaultResolver;
+use ureq::unversioned::transport::{ConnectProxyConnector, Connector, RustlsConnector};
+use ureq::{Agent, Body, Proxy, RequestBuilder};
 use url::Url;
 
+use chrono::Local;
+
 use crate::lib::logger::traits::LogLevel;
+use crate::lib::report::{ScanMetadata, ScanParameters, ScanReport, ScanSummary};
+use crate::lib::util;
+use crate::lib::wordlist::{STREAM_THRESHOLD, WordStream, Wordlist};
+use crate::lib::worker::bind::BindConnector;
+use crate::lib::worker::canary::Canary;
+use crate::lib::worker::candidates::{self, CandidateGenerator, Words};
+use crate::lib::worker::capture::CaptureQuota;
+use crate::lib::worker::charset;
+use crate::lib::worker::checkpoint::{self, Checkpoint, Position};
+use crate::lib::worker::concurrency::AdaptiveConcurrency;
+use crate::lib::worker::config::{HttpMethod, ScanMode, WorkerConfig};
+use crate::lib::worker::control::WorkerControl;
+use crate::lib::worker::danger;
+use crate::lib::worker::directory;
+use crate::lib::worker::discovery::{self, DiscoveryRate, Slowdown};
+use crate::lib::worker::error_page;
+use crate::lib::worker::filter::{BodyCount, ResponseInfo};
+use crate::lib::worker::handle::WorkerHandle;
+use crate::lib::worker::headers::HeaderAnalyzer;
+use crate::lib::worker::hit::{HitResult, Verification};
+use crate::lib::worker::html;
+use crate::lib::worker::latency::LatencyHistogram;
 use crate::lib::worker::messages::{ProgressChangeMessage, ProgressMessage, WorkerMessage};
+use crate::lib::worker::noise::StaticAssetFilter;
+use crate::lib::worker::pipeline::{self, Stage};
+use crate::lib::worker::proxy_pool::{self, PooledProxy, ProxyPool};
+use crate::lib::worker::rng::SeededRng;
+use crate::lib::worker::robots::{self, RobotsRules};
+use crate::lib::worker::scheduler::{self, Pacer, Scheduler};
+use crate::lib::worker::soft404::Soft404;
+use crate::lib::worker::stack::{self, Stack};
+use crate::lib::worker::store::ResultStore;
+use crate::lib::worker::timing::{self, PhaseTimings};
+use crate::lib::worker::tls::{self, TlsInfo};
+use crate::lib::worker::variants::VariantIndex;
+use crate::lib::worker::waf::{self, WafVendor};
+
+#[cfg(feature = "async")]
+mod async_engine;
+
+/// How often the canary watchdog checks whether the scan is over.
+const CANARY_POLL: Duration = Duration::from_millis(100);
+
+/// Response body bytes kept for inspection unless set otherwise.
+pub const DEFAULT_MAX_BODY_SIZE: u64 = 1024 * 1024;
+
+/// Times a candidate answered with 429 is requested again after backing off.
+const MAX_THROTTLED_ATTEMPTS: u32 = 5;
+
+/// Other proxies a failed request is sent through before it counts as failed.
+const MAX_PROXY_SWITCHES: u32 = 3;
+
+/// How often a thread parked by adaptive concurrency checks whether it may
+/// go on.
+const TURN_POLL: Duration = Duration::from_millis(100);
+
+/// Longest a request thread keeps finished requests to itself before
+/// reporting them as progress.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(50);
+/// Finished requests a thread reports at once, however soon.
+const PROGRESS_BATCH: usize = 100;
+
+/// Minimal pause before each verification request.
+const VERIFY_DELAY: Duration = Duration::from_millis(250);
+/// Sent on verification requests so that UA-keyed caches and balancers answer anew.
+const VERIFY_USER_AGENT: &str =
+    "Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0";
 
 #[derive(Error, Debug, Clone)]
 pub enum WorkerError {
     #[error("Request error: {0}")]
     RequestError(String),
+    #[error("{0} is unreachable: {1}")]
+    Unreachable(Url, String),
+}
+
+/// Request counters reported in the [`ScanReport`].
+#[derive(Debug, Default)]
+struct Tally {
+    requests: AtomicUsize,
+    errors: AtomicUsize,
+    /// Latencies of the requests that were answered.
+    latency: Mutex<LatencyHistogram>,
+    /// Sum of those latencies in microseconds, for their mean.
+    latency_micros: AtomicU64,
+    /// Candidates whose requests failed since the last one answered.
+    failed_in_a_row: AtomicUsize,
+}
+
+impl Tally {
+    /// Counts a request sent at `start`.
+    fn request(&self, start: Instant, ok: bool) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        if ok {
+            let latency = start.elapsed();
+            self.latency.lock().unwrap().record(latency);
+            self.latency_micros
+                .fetch_add(latency.as_micros() as u64, Ordering::Relaxed);
+        } else {
+            self.error();
+        }
+    }
+
+    fn error(&self) {
+        self.errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts how a candidate went, returning the failures in a row so far.
+    fn candidate(&self, ok: bool) -> usize {
+        if ok {
+            self.failed_in_a_row.store(0, Ordering::Relaxed);
+            0
+        } else {
+            self.failed_in_a_row.fetch_add(1, Ordering::Relaxed) + 1
+        }
+    }
+}
+
+/// Requests a thread finished since it last reported progress, so that
+/// fast scans don't send a pair of messages per request.
+struct PendingProgress {
+    steps: usize,
+    since: Instant,
+}
+
+impl PendingProgress {
+    fn new() -> Self {
+        Self {
+            steps: 0,
+            since: Instant::now(),
+        }
+    }
+
+    /// Counts a finished request, returning the pending ones once they are
+    /// due to be reported.
+    fn step(&mut self) -> Option<usize> {
+        self.steps += 1;
+        (self.steps >= PROGRESS_BATCH || self.since.elapsed() >= PROGRESS_INTERVAL)
+            .then(|| self.take())
+    }
+
+    fn take(&mut self) -> usize {
+        self.since = Instant::now();
+        mem::take(&mut self.steps)
+    }
+}
+
+/// What scanning a directory turned up.
+#[derive(Debug, Default)]
+pub struct Found {
+    pub hits: Vec<Url>,
+    /// Hits that look like directories, to recurse into.
+    pub directories: Vec<Url>,
 }
 
 #[derive(Debug, Clone)]
 pub struct Worker {
-    threads: usize,
-    recursion_depth: usize,
-    wordlist_path: PathBuf,
+    config: WorkerConfig,
     message_sender: Arc<Sender<WorkerMessage>>,
-    uri: Url,
-    timeout: usize,
-    proxy_url: Option<Url>,
+    control: Arc<WorkerControl>,
+    waf: OnceLock<WafVendor>,
+    stack: OnceLock<Stack>,
+    /// Why the scan was aborted, once it was.
+    aborted: OnceLock<String>,
+    /// How long the target took to answer before the scan started.
+    baseline_latency: OnceLock<Duration>,
+    /// Set when robots.txt is respected.
+    robots: OnceLock<RobotsRules>,
+    /// Candidates left out because robots.txt disallows them.
+    robots_skipped: Arc<AtomicUsize>,
+    static_assets: Arc<StaticAssetFilter>,
+    hits: Arc<Mutex<ResultStore>>,
+    variants: Arc<Mutex<VariantIndex>>,
+    headers: Arc<Mutex<HeaderAnalyzer>>,
+    discovery: Arc<DiscoveryRate>,
+    /// Handshake details of the HTTPS hosts seen so far.
+    tls: Arc<Mutex<Vec<TlsInfo>>>,
+    /// Directories left when their time budget ran out.
+    partial_dirs: Arc<Mutex<Vec<String>>>,
+    /// Starts as configured; cleared when HEAD can't be trusted.
+    head_first: Arc<AtomicBool>,
+    rng: Arc<Mutex<SeededRng>>,
+    scheduler: Arc<Scheduler>,
+    capture: Arc<CaptureQuota>,
+    tally: Arc<Tally>,
+    /// Where the scan is, for checkpoints.
+    position: Arc<Mutex<Position>>,
+    proxy_pool: Option<Arc<ProxyPool>>,
+    /// Set with adaptive concurrency.
+    concurrency: Option<Arc<AdaptiveConcurrency>>,
+    #[cfg(feature = "async")]
+    async_engine: Arc<OnceLock<async_engine::AsyncEngine>>,
 }
 
 impl Worker {
-    pub fn new(
-        threads: usize,
-        recursion_depth: usize,
-        timeout: usize,
-        wordlist: PathBuf,
-        uri: Url,
-        message_sender: Arc<Sender<WorkerMessage>>,
-        proxy_uri: Option<Url>,
-    ) -> Worker {
+    pub fn new(config: WorkerConfig, message_sender: Arc<Sender<WorkerMessage>>) -> Worker {
+        let rng = SeededRng::new(config.seed);
+        let control = Arc::<WorkerControl>::default();
+        let scheduler = Scheduler::new(
+            control.clone(),
+            message_sender.clone(),
+            config.rate_limit,
+            config.host_rate_limit,
+            config.ramp_up,
+        );
+        let capture = CaptureQuota::new(
+            config.capture_quota,
+            config.capture_sample,
+            config.max_body_size,
+        );
+        let hits = ResultStore::new(config.result_limits);
+        let variants = VariantIndex::new(config.extensions.clone());
+        let head_first = config.head_first;
+        let min_hits = config.early_stop_hits;
+        let concurrency = config
+            .auto_threads
+            .then(|| Arc::new(AdaptiveConcurrency::new(config.concurrency)));
+        let proxy_pool = ProxyPool::new(
+            config
+                .proxies
+                .iter()
+                .map(|url| (url.clone(), Self::build_agent(&config, Some(url)))),
+        );
         Worker {
-            threads,
-            recursion_depth,
-            wordlist_path: wordlist,
+            config,
             message_sender,
-            uri,
-            timeout,
-            proxy_url: proxy_uri,
+            control,
+            waf: OnceLock::new(),
+            stack: OnceLock::new(),
+            aborted: OnceLock::new(),
+            baseline_latency: OnceLock::new(),
+            robots: OnceLock::new(),
+            robots_skipped: Arc::default(),
+            static_assets: Arc::default(),
+            hits: Arc::new(Mutex::new(hits)),
+            variants: Arc::new(Mutex::new(variants)),
+            headers: Arc::default(),
+            discovery: Arc::new(DiscoveryRate::new(min_hits)),
+            tls: Arc::default(),
+            partial_dirs: Arc::default(),
+            head_first: Arc::new(AtomicBool::new(head_first)),
+            rng: Arc::new(Mutex::new(rng)),
+            scheduler: Arc::new(scheduler),
+            capture: Arc::new(capture),
+            tally: Arc::default(),
+            position: Arc::default(),
+            proxy_pool: proxy_pool.map(Arc::new),
+            concurrency,
+            #[cfg(feature = "async")]
+            async_engine: Arc::default(),
+        }
+    }
+
+    pub fn config(&self) -> &WorkerConfig {
+        &self.config
+    }
+
+    pub fn control(&self) -> Arc<WorkerControl> {
+        self.control.clone()
+    }
+
+    /// Passes a message to the output sinks and the frontend.
+    ///
+    /// A frontend that dropped the receiver along with the
+    /// [`WorkerHandle`] no longer listens, but the scan still has to get to
+    /// its last checkpoint and finish the sinks, so send errors are ignored.
+    fn send(&self, msg: WorkerMessage) {
+        let errors = self.config.sinks.dispatch(&msg);
+        let _ = self.message_sender.send(msg);
+
+        for err in errors {
+            let _ = self.message_sender.send(WorkerMessage::log(
+                LogLevel::WARN,
+                format!("Output sink error: {err}"),
+            ));
+        }
+    }
+
+    /// Runs the scan on a new thread.
+    ///
+    /// A scan that fails or panics ends with [`WorkerMessage::Failed`], so
+    /// frontends never wait for a worker that is gone.
+    pub fn spawn(self) -> WorkerHandle {
+        let control = self.control();
+        let message_sender = self.message_sender.clone();
+
+        WorkerHandle::new(
+            thread::spawn(move || {
+                let result = panic::catch_unwind(AssertUnwindSafe(|| self.run())).unwrap_or_else(
+                    |payload| {
+                        Err(anyhow!(
+                            "Worker thread panicked: {}",
+                            panic_message(&*payload)
+                        ))
+                    },
+                );
+
+                if let Err(err) = &result {
+                    // The receiver may be the reason of the failure, so errors are ignored.
+                    let _ = message_sender
+                        .send(WorkerMessage::log(LogLevel::CRITICAL, err.to_string()));
+                    let _ = message_sender.send(WorkerMessage::Failed(err.to_string()));
+                }
+                result
+            }),
+            control,
+        )
+    }
+
+    /// Loads the wordlist into the generator of this worker's requests.
+    pub fn candidates(&self) -> Result<CandidateGenerator> {
+        Ok(CandidateGenerator::new(
+            self.words(&self.config.wordlist_path)?,
+            self.config.scan_mode,
+        )
+        .transforms(self.config.transforms.clone())
+        .custom_transforms(self.config.custom_transforms.clone())
+        .extensions(self.config.extensions.clone())
+        .multi_segment(self.config.multi_segment_words)
+        .trailing_slash(self.config.add_slash))
+    }
+
+    /// Generator of a pipeline stage: backup names of the files `found` so
+    /// far, or the stage's wordlist, extensions and transforms in place of
+    /// the scan's.
+    fn stage_candidates(
+        &self,
+        stage: &Stage,
+        target: &Url,
+        found: &[Url],
+    ) -> Result<CandidateGenerator> {
+        if stage.backups {
+            let words = pipeline::backup_words(target, found);
+            return Ok(
+                CandidateGenerator::new(Arc::new(words), ScanMode::Directory)
+                    .multi_segment(true)
+                    .directories(false),
+            );
+        }
+
+        let wordlist = stage
+            .wordlist
+            .as_ref()
+            .unwrap_or(&self.config.wordlist_path);
+        Ok(
+            CandidateGenerator::new(self.words(wordlist)?, self.config.scan_mode)
+                .transforms(or_scan(&stage.transforms, &self.config.transforms))
+                .custom_transforms(self.config.custom_transforms.clone())
+                .extensions(or_scan(&stage.extensions, &self.config.extensions))
+                .multi_segment(self.config.multi_segment_words)
+                .trailing_slash(self.config.add_slash),
+        )
+    }
+
+    /// Words of `wordlist` and the extra words, most frequent first or
+    /// shuffled if asked to, without the dangerous-looking ones in
+    /// authenticated scans. Huge wordlists are streamed from disk unless
+    /// they have to be ranked or shuffled.
+    fn words(&self, wordlist: &Path) -> Result<Words> {
+        let size = fs::metadata(wordlist).map_or(0, |metadata| metadata.len());
+        if size > STREAM_THRESHOLD && !self.config.shuffle && !self.config.by_frequency {
+            let dangerous = self.config.dangerous_words.clone();
+            let stream = WordStream::open(
+                wordlist,
+                move |word| dangerous.is_empty() || !danger::is_dangerous(word, &dangerous),
+                &self.config.extra_words,
+            )?;
+            self.skipped_dangerous(stream.skipped);
+            self.send(WorkerMessage::log(
+                LogLevel::INFO,
+                format!(
+                    "Streaming {} words of {} from disk",
+                    stream.len(),
+                    wordlist.display()
+                ),
+            ));
+            return Ok(Words::Streamed(Arc::new(stream)));
+        }
+
+        let wordlist = Wordlist::read(wordlist)?;
+        let mut words = if self.config.by_frequency {
+            wordlist.ranked()
+        } else {
+            wordlist.lines
+        };
+        for word in &self.config.extra_words {
+            if !words.contains(word) {
+                words.push(word.clone());
+            }
+        }
+
+        if !self.config.dangerous_words.is_empty() {
+            let before = words.len();
+            words.retain(|word| !danger::is_dangerous(word, &self.config.dangerous_words));
+            self.skipped_dangerous(before - words.len());
+        }
+
+        if self.config.shuffle && !self.config.by_frequency {
+            self.rng.lock().unwrap().shuffle(&mut words);
+        }
+
+        Ok(Words::Loaded(Arc::new(words)))
+    }
+
+    fn skipped_dangerous(&self, skipped: usize) {
+        if skipped > 0 {
+            self.send(WorkerMessage::log(
+                LogLevel::WARN,
+                format!(
+                    "Skipped {skipped} words that look like they change state, such as logout or \
+                     delete, since the scan is authenticated"
+                ),
+            ));
+        }
+    }
+
+    /// Runs the whole scan on the calling thread.
+    pub fn run(&self) -> Result<ScanReport> {
+        let started = Local::now();
+        let timer = Instant::now();
+
+        let mut metadata = ScanMetadata::new(&self.config, started);
+        self.send(WorkerMessage::Started(metadata.clone()));
+        if let Some(concurrency) = &self.concurrency {
+            self.send(WorkerMessage::Concurrency(concurrency.limit()));
+        }
+
+        self.send(WorkerMessage::log(
+            LogLevel::INFO,
+            format!("Seed: {}", self.config.seed),
+        ));
+
+        let proxy = self.config.proxy_url.as_ref();
+        let client = Self::build_agent(&self.config, proxy.or(self.config.proxies.first()));
+        self.load_cookies(&client);
+        for proxy in self.proxy_pool.iter().flat_map(|pool| pool.proxies()) {
+            self.load_cookies(&proxy.agent);
+        }
+
+        let location = self.preflight(&client)?;
+        let target = self.target(&client, location);
+        self.check_tls(&target, None);
+
+        if self.config.respect_robots && self.config.scan_mode == ScanMode::Directory {
+            self.load_robots(&client, &target);
+        }
+
+        if self.config.head_first && self.config.scan_mode == ScanMode::Directory {
+            self.check_head(&client, &target);
+        }
+
+        if self.config.check_packs && self.config.scan_mode == ScanMode::Directory {
+            self.run_check_pack(&client, &target);
+        }
+
+        let deadline = self
+            .config
+            .max_time
+            .map(|max_time| Instant::now() + max_time);
+
+        let scanning = AtomicBool::new(true);
+        let stopped = thread::scope(|scope| {
+            if let Some(path) = &self.config.canary {
+                match target.join(path) {
+                    Ok(canary) => {
+                        let (client, scanning) = (&client, &scanning);
+                        scope.spawn(move || self.watch_canary(client, &canary, scanning));
+                    }
+                    Err(err) => self.send(WorkerMessage::log(
+                        LogLevel::WARN,
+                        format!("Invalid canary path {path}: {err}"),
+                    )),
+                }
+            }
+
+            if let Some(path) = self.checkpoint_path() {
+                let scanning = &scanning;
+                scope.spawn(move || self.keep_checkpoint(path, scanning));
+            }
+
+            let stopped = self.scan_all(&client, &target, deadline);
+            scanning.store(false, Ordering::Relaxed);
+            stopped
+        })?;
+
+        if let Some(path) = self.checkpoint_path() {
+            let saved = if stopped || self.control.is_stopped() {
+                self.checkpoint().save(path)
+            } else {
+                fs::remove_file(path).or_else(|err| match err.kind() {
+                    ErrorKind::NotFound => Ok(()),
+                    _ => Err(err.into()),
+                })
+            };
+            if let Err(err) = saved {
+                self.send(WorkerMessage::log(
+                    LogLevel::WARN,
+                    format!("Can't update checkpoint {}: {err:#}", path.display()),
+                ));
+            }
+        }
+
+        let robots_skipped = self.robots_skipped.load(Ordering::Relaxed);
+        if robots_skipped > 0 {
+            self.send(WorkerMessage::log(
+                LogLevel::WARN,
+                format!("Skipped {robots_skipped} candidates disallowed by robots.txt"),
+            ));
+        }
+
+        if self.config.verify_hits {
+            self.verify(&client);
+        }
+
+        self.save_cookies(&client);
+
+        let latency = self.tally.latency.lock().unwrap().clone();
+        if !latency.is_empty() {
+            self.send(WorkerMessage::Latency(latency.clone()));
+        }
+
+        for err in self.config.sinks.finish() {
+            self.send(WorkerMessage::log(
+                LogLevel::WARN,
+                format!("Output sink error: {err}"),
+            ));
+        }
+
+        self.send(WorkerMessage::finish_total());
+
+        let hits = std::mem::take(&mut *self.hits.lock().unwrap());
+        let summary = ScanSummary {
+            requests: self.tally.requests.load(Ordering::Relaxed),
+            hits: self.restored_hits() + hits.len(),
+            errors: self.tally.errors.load(Ordering::Relaxed),
+            elapsed: timer.elapsed(),
+            average_latency: (!latency.is_empty()).then(|| {
+                Duration::from_micros(
+                    self.tally.latency_micros.load(Ordering::Relaxed) / latency.count(),
+                )
+            }),
+        };
+        self.send(WorkerMessage::Finished(summary.clone()));
+
+        metadata.finished = Some(Local::now());
+        metadata.tls = self.tls.lock().unwrap().clone();
+        Ok(ScanReport {
+            metadata,
+            parameters: ScanParameters::from(&self.config),
+            started,
+            duration: summary.elapsed,
+            requests: summary.requests,
+            errors: summary.errors,
+            latency,
+            baseline_latency: self.baseline_latency.get().copied(),
+            hits,
+            stopped: stopped || self.control.is_stopped(),
+            waf: self.waf.get().copied(),
+            stack: self.stack.get().copied(),
+            header_findings: self.headers.lock().unwrap().findings().to_vec(),
+            capture: self.capture.usage(),
+            partial_dirs: std::mem::take(&mut *self.partial_dirs.lock().unwrap()),
+        })
+    }
+
+    /// Runs the scan, or each stage of a pipeline, returning whether it was
+    /// stopped or ran out of time.
+    fn scan_all(&self, client: &Agent, target: &Url, deadline: Option<Instant>) -> Result<bool> {
+        let mut stopped = false;
+
+        if self.config.stages.is_empty() {
+            let generator = self.candidates()?;
+            let (dirs, offset) = match &self.config.resume {
+                Some(resume) => {
+                    self.restore(resume);
+                    (resume.pending_urls(), resume.offset)
+                }
+                None => (vec![(target.clone(), 0)], 0),
+            };
+            stopped = self
+                .scan(
+                    client,
+                    dirs,
+                    offset,
+                    &generator,
+                    self.config.recursion_depth,
+                    deadline,
+                )?
+                .is_none();
+        } else {
+            let mut found: Vec<Url> = Vec::new();
+
+            for (index, stage) in self.config.stages.iter().enumerate() {
+                self.send(WorkerMessage::Stage {
+                    index,
+                    count: self.config.stages.len(),
+                    name: stage.to_string(),
+                });
+
+                let generator = self.stage_candidates(stage, target, &found)?;
+                let mut dirs = vec![(target.clone(), 0)];
+                if !stage.backups {
+                    dirs.extend(pipeline::directories(&found).map(|url| (url.clone(), 0)));
+                }
+                let recursion_depth = stage.recursion.unwrap_or(self.config.recursion_depth);
+
+                let Some(stage_found) =
+                    self.scan(client, dirs, 0, &generator, recursion_depth, deadline)?
+                else {
+                    stopped = true;
+                    break;
+                };
+                for url in stage_found {
+                    if !found.contains(&url) {
+                        found.push(url);
+                    }
+                }
+            }
         }
+
+        Ok(stopped)
     }
 
-    pub fn run(&self) -> Result<()> {
-        let mut urls_vec: Vec<Url> = Vec::new();
-        urls_vec.push(self.uri.clone());
-        let file = File::open(&self.wordlist_path)?;
-        let lines: Arc<Vec<String>> =
-            Arc::new(BufReader::new(file).lines().map_while(Result::ok).collect());
-        let lines_len = lines.len();
-        let mut progress_len = lines_len;
-        let path_len_start = self.uri.path_segments().unwrap().collect::<Vec<_>>().len();
+    /// Scans `dirs` and the directories found below them up to
+    /// `recursion_depth`, starting the first one at candidate `offset`, and
+    /// returns every URL found; `None` once the scan is stopped or out of
+    /// time.
+    fn scan(
+        &self,
+        client: &Agent,
+        dirs: Vec<(Url, usize)>,
+        mut offset: usize,
+        generator: &CandidateGenerator,
+        recursion_depth: usize,
+        deadline: Option<Instant>,
+    ) -> Result<Option<Vec<Url>>> {
+        let lines_len = generator.count().value();
+        let mut progress_len = (dirs.len() * lines_len).saturating_sub(offset);
+        let mut found = Vec::new();
+
+        // Directories to scan with their depth below the target, counted in
+        // hits rather than path segments since a word may span several.
+        let mut urls_vec: Vec<(Url, usize)> = dirs.into_iter().rev().collect();
+
+        let out_of_time = || {
+            let out_of_time = deadline.is_some_and(|deadline| Instant::now() >= deadline);
+            if out_of_time {
+                self.send(WorkerMessage::log(
+                    LogLevel::WARN,
+                    "Maximum scan time reached, stopping".to_string(),
+                ));
+            }
+            out_of_time
+        };
+
+        while let Some((url, depth)) = urls_vec.pop() {
+            if self.control.is_stopped() || out_of_time() {
+                return Ok(None);
+            }
+
+            if depth > recursion_depth {
+                continue;
+            }
+
+            self.send(WorkerMessage::Directory {
+                url: url.to_string(),
+                depth,
+            });
+            self.send(WorkerMessage::set_total_size(progress_len));
+            self.discovery.set_total(progress_len);
+
+            self.send(WorkerMessage::set_current_size(
+                lines_len.saturating_sub(offset),
+            ));
+
+            {
+                let mut position = self.position.lock().unwrap();
+                position.pending = std::iter::once((url.clone(), depth))
+                    .chain(urls_vec.iter().rev().cloned())
+                    .collect();
+                position.start_directory(offset);
+            }
+
+            let budget = self
+                .config
+                .max_time_per_dir
+                .map(|max_time| Instant::now() + max_time)
+                .filter(|budget| deadline.is_none_or(|deadline| *budget < deadline));
+            let urls_result =
+                self.execute(client, url.clone(), generator, offset, budget.or(deadline))?;
+            offset = 0;
+            self.send(WorkerMessage::Capture(self.capture.usage()));
+
+            if budget.is_some_and(|budget| Instant::now() >= budget) && !self.control.is_stopped() {
+                self.send(WorkerMessage::log(
+                    LogLevel::WARN,
+                    format!("Time budget of {url} spent, moving on"),
+                ));
+                self.partial_dirs.lock().unwrap().push(url.to_string());
+            }
+
+            if self.config.scan_mode == ScanMode::Directory {
+                progress_len += urls_result.directories.len() * lines_len;
+                found.extend(urls_result.hits);
+                urls_vec.extend(
+                    urls_result
+                        .directories
+                        .into_iter()
+                        .map(|url| (url, depth + 1)),
+                );
+            }
 
-        while let Some(url) = urls_vec.pop() {
-            if url.path_segments().unwrap().collect::<Vec<_>>().len() - path_len_start
-                > self.recursion_depth
+            // Once the directory is done, a checkpoint starts with the next one.
+            if !self.control.is_stopped()
+                && deadline.is_none_or(|deadline| Instant::now() < deadline)
             {
+                let mut position = self.position.lock().unwrap();
+                position.pending = urls_vec.iter().rev().cloned().collect();
+                position.start_directory(0);
+            }
+        }
+
+        // Time can run out in the last directory too, with words left in it.
+        if self.control.is_stopped() || out_of_time() {
+            return Ok(None);
+        }
+        Ok(Some(found))
+    }
+
+    /// Requests the target once before any thread starts, so a target that
+    /// can't be reached fails the scan with one error instead of one per
+    /// candidate. Returns where the target redirects to, if anywhere.
+    fn preflight(&self, client: &Agent) -> Result<Option<Url>, WorkerError> {
+        let target = &self.config.uri;
+        let mut attempt = 0;
+        let (res, latency) = loop {
+            let start = Instant::now();
+            let response = self
+                .get(client, target.as_str())
+                .config()
+                .max_redirects(0)
+                .build()
+                .call();
+            self.tally.request(start, response.is_ok());
+            match response {
+                Ok(res) => break (res, start.elapsed()),
+                Err(err) if attempt >= self.config.retries => {
+                    return Err(WorkerError::Unreachable(target.clone(), err.to_string()));
+                }
+                Err(_) => attempt += 1,
+            }
+        };
+
+        self.send(WorkerMessage::log(
+            LogLevel::INFO,
+            format!(
+                "{target} answered {} in {:.1}ms",
+                res.status().as_u16(),
+                latency.as_secs_f64() * 1000.0
+            ),
+        ));
+        let _ = self.baseline_latency.set(latency);
+
+        if !res.status().is_redirection() {
+            return Ok(None);
+        }
+        Ok(Self::header(&res, "location").and_then(|location| target.join(&location).ok()))
+    }
+
+    /// The target, or its HTTPS version when `location`, where the target
+    /// redirects to, shows it is served over HTTPS only.
+    fn target(&self, client: &Agent, location: Option<Url>) -> Url {
+        let target = &self.config.uri;
+        let Some(upgraded) = self.https_upgrade(client, location) else {
+            return target.clone();
+        };
+
+        if self.config.upgrade_https {
+            self.send(WorkerMessage::log(
+                LogLevel::WARN,
+                format!("{target} redirects every request to HTTPS, scanning {upgraded} instead"),
+            ));
+            upgraded
+        } else {
+            self.send(WorkerMessage::log(
+                LogLevel::WARN,
+                format!(
+                    "{target} redirects every request to HTTPS, so most responses will be redirects; \
+                     scan {upgraded} or allow upgrading the target"
+                ),
+            ));
+            target.clone()
+        }
+    }
+
+    /// HTTPS version of an `http://` target whose URL and a made-up path both
+    /// redirect to HTTPS on the same host.
+    fn https_upgrade(&self, client: &Agent, location: Option<Url>) -> Option<Url> {
+        let target = &self.config.uri;
+        if target.scheme() != "http" {
+            return None;
+        }
+        let location = location.filter(|location| is_https_of(location, target))?;
+
+        let word = format!("{:016x}", self.rng.lock().unwrap().next_u64());
+        let probe = target.join(&word).ok()?;
+        self.https_redirect(client, &probe)?;
+
+        let mut upgraded = target.clone();
+        upgraded.set_scheme("https").ok()?;
+        upgraded.set_port(location.port()).ok()?;
+        Some(upgraded)
+    }
+
+    /// Where `url` redirects to, if that is HTTPS on the same host.
+    fn https_redirect(&self, client: &Agent, url: &Url) -> Option<Url> {
+        let start = Instant::now();
+        let response = self
+            .get(client, url.as_str())
+            .config()
+            .max_redirects(0)
+            .build()
+            .call();
+        self.tally.request(start, response.is_ok());
+
+        let res = response.ok()?;
+        if !res.status().is_redirection() {
+            return None;
+        }
+        let location = url.join(&Self::header(&res, "location")?).ok()?;
+        is_https_of(&location, url).then_some(location)
+    }
+
+    /// Fetches the robots.txt of the target's host. A missing file allows
+    /// everything and an unreachable one nothing, as RFC 9309 has it.
+    fn load_robots(&self, client: &Agent, target: &Url) {
+        let Ok(url) = target.join("/robots.txt") else {
+            return;
+        };
+
+        let start = Instant::now();
+        let response = self.get(client, url.as_str()).call();
+        self.tally.request(start, response.is_ok());
+
+        let rules = match response {
+            Ok(mut res) if res.status().is_success() => {
+                let text = res.body_mut().read_to_string().unwrap_or_default();
+                let rules = RobotsRules::parse(&text, robots::USER_AGENT_TOKEN);
+                self.send(WorkerMessage::log(
+                    LogLevel::INFO,
+                    format!(
+                        "{url} has {} Disallow rules for yadb, skipping what they match",
+                        rules.disallowed()
+                    ),
+                ));
+                rules
+            }
+            Ok(res) if res.status().is_client_error() => {
+                self.send(WorkerMessage::log(
+                    LogLevel::INFO,
+                    format!("No {url} ({}), every path is allowed", res.status()),
+                ));
+                RobotsRules::default()
+            }
+            Ok(res) => {
+                self.send(WorkerMessage::log(
+                    LogLevel::WARN,
+                    format!(
+                        "{url} answered {}, so every path is taken as disallowed",
+                        res.status()
+                    ),
+                ));
+                RobotsRules::disallow_all()
+            }
+            Err(err) => {
+                self.send(WorkerMessage::log(
+                    LogLevel::WARN,
+                    format!("Can't fetch {url} ({err}), so every path is taken as disallowed"),
+                ));
+                RobotsRules::disallow_all()
+            }
+        };
+        let _ = self.robots.set(rules);
+    }
+
+    /// Whether robots.txt, when respected, allows requesting `target`;
+    /// counts the candidates it doesn't.
+    fn robots_allow(&self, target: &str) -> bool {
+        let Some(robots) = self.robots.get() else {
+            return true;
+        };
+        let Ok(url) = Url::parse(target) else {
+            return true;
+        };
+
+        let path = match url.query() {
+            Some(query) => format!("{}?{query}", url.path()),
+            None => url.path().to_string(),
+        };
+        if robots.is_allowed(&path) {
+            return true;
+        }
+        self.robots_skipped.fetch_add(1, Ordering::Relaxed);
+        false
+    }
+
+    /// Fingerprints the target by its main page and requests the paths of its
+    /// stack's check pack, reporting the ones found as tagged hits.
+    fn run_check_pack(&self, client: &Agent, target: &Url) {
+        let base = target.as_str();
+        let start = Instant::now();
+        let response = self.get(client, base).call();
+        self.tally.request(start, response.is_ok());
+        let Ok(mut res) = response else {
+            return;
+        };
+
+        let body = self.read_body(&mut res).unwrap_or_default();
+        let Some(stack) = stack::detect(res.headers(), &body) else {
+            self.send(WorkerMessage::log(
+                LogLevel::INFO,
+                "No known stack detected".to_string(),
+            ));
+            return;
+        };
+        let _ = self.stack.set(stack);
+
+        self.send(WorkerMessage::log(
+            LogLevel::WARN,
+            format!("Stack detected: {stack}, running its check pack"),
+        ));
+
+        let mut found = Vec::new();
+        for check in stack.checks() {
+            if self.control.is_stopped() {
+                break;
+            }
+
+            let Some(target) = candidates::join_path(target, check.path) else {
+                continue;
+            };
+            let target = target.to_string();
+            if !self.robots_allow(&target) {
+                continue;
+            }
+            let start = Instant::now();
+            let response = self.get(client, &target).call();
+            self.tally.request(start, response.is_ok());
+            let Ok(res) = response else {
                 continue;
+            };
+
+            let status = res.status().as_u16();
+            if !stack::is_found(status) {
+                continue;
+            }
+
+            let hit = HitResult {
+                url: target,
+                status,
+                title: None,
+                content_type: Self::content_type(&res),
+                size: res.body().content_length(),
+                words: None,
+                lines: None,
+                redirects: Vec::new(),
+                final_url: None,
+                static_asset: false,
+                etag: Self::header(&res, "etag"),
+                last_modified: Self::header(&res, "last-modified"),
+                unchanged: false,
+                tags: vec![stack.tag().to_string(), check.tag.to_string()],
+                dom_hash: None,
+                variants: Vec::new(),
+                first_found_by: None,
+                captures: BTreeMap::new(),
+                timings: None,
+                triage: None,
+            };
+            self.report_hit(hit, &mut found);
+        }
+    }
+
+    /// Requests every hit once more and reports whether it still answers the same.
+    fn verify(&self, client: &Agent) {
+        // Taken out so spilled hits can be streamed back without holding the lock.
+        let store = std::mem::take(&mut *self.hits.lock().unwrap());
+        self.verify_stored(client, &store);
+        *self.hits.lock().unwrap() = store;
+    }
+
+    fn verify_stored(&self, client: &Agent, store: &ResultStore) {
+        if store.is_empty() {
+            return;
+        }
+
+        let hits = match store.iter() {
+            Ok(hits) => hits,
+            Err(err) => {
+                self.send(WorkerMessage::log(
+                    LogLevel::WARN,
+                    format!("Can't read spilled hits back: {err}"),
+                ));
+                return;
+            }
+        };
+
+        self.send(WorkerMessage::start_current(store.len()));
+
+        let host = self.config.uri.host_str().unwrap_or_default();
+        let mut pacer = self.scheduler.pacer(VERIFY_DELAY.max(self.config.delay));
+
+        for hit in hits {
+            if !pacer.acquire(host) {
+                break;
             }
 
-            let lines = lines.clone();
+            self.send(WorkerMessage::Progress(ProgressMessage::Current(
+                ProgressChangeMessage::SetMessage(format!("Verifying {}", hit.url)),
+            )));
 
-            self.message_sender
-                .send(WorkerMessage::set_total_size(progress_len))
-                .expect("SENDER ERROR");
+            // The custom headers may carry the credentials the hit needed.
+            let mut headers: Vec<(&str, &str)> = self
+                .config
+                .headers
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str()))
+                .collect();
+            let host;
+            let url = match self.config.scan_mode {
+                ScanMode::Directory => hit.url.as_str(),
+                ScanMode::Vhost => {
+                    host = Url::parse(&hit.url)
+                        .ok()
+                        .and_then(|url| url.host_str().map(str::to_string))
+                        .unwrap_or_default();
+                    headers.push(("Host", &host));
+                    self.config.uri.as_str()
+                }
+            };
+            headers.push(("User-Agent", VERIFY_USER_AGENT));
 
-            self.message_sender
-                .send(WorkerMessage::set_current_size(lines_len))
-                .expect("SENDER ERROR");
+            let start = Instant::now();
+            let response = self.fetch(client, url, false, headers);
+            self.tally.request(start, response.is_ok());
+            // Followed as in the scan, so the status compared is the same one.
+            let limit = match self.config.scan_mode {
+                ScanMode::Directory => self.config.follow_redirects,
+                ScanMode::Vhost => 0,
+            };
+            let status = response
+                .ok()
+                .map(|res| self.follow(client, &mut pacer, url, res, limit).0)
+                .map(|res| res.status().as_u16());
 
-            let urls_result = self.execute(url, lines)?;
+            let verification = if status == Some(hit.status) {
+                Verification::Confirmed
+            } else {
+                Verification::Flaky { status }
+            };
 
-            progress_len += urls_result.len() * lines_len;
-            urls_vec.extend(urls_result);
+            self.send(WorkerMessage::Verified(hit, verification));
+            self.send(WorkerMessage::advance_current());
         }
+    }
+
+    fn load_cookies(&self, client: &Agent) {
+        let Some(path) = &self.config.cookie_jar else {
+            return;
+        };
+
+        if !path.exists() {
+            return;
+        }
+
+        let result = File::open(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|file| Ok(client.cookie_jar_lock().load_json(BufReader::new(file))?));
 
-        self.message_sender
-            .send(WorkerMessage::finish_total())
-            .expect("SENDER ERROR");
-        Ok(())
+        if let Err(err) = result {
+            self.send(WorkerMessage::log(
+                LogLevel::WARN,
+                format!("Can't load cookie jar {}: {err}", path.display()),
+            ));
+        }
     }
 
-    pub fn execute(&self, url: Url, lines: Arc<Vec<String>>) -> Result<Vec<Url>> {
-        let slice_size = lines.len() / self.threads;
+    fn save_cookies(&self, client: &Agent) {
+        let Some(path) = &self.config.cookie_jar else {
+            return;
+        };
 
-        let lines_arc = lines.clone();
+        let result = File::create(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|mut file| Ok(client.cookie_jar_lock().save_json(&mut file)?));
 
-        let mut result: Vec<Url> = Vec::new();
+        if let Err(err) = result {
+            self.send(WorkerMessage::log(
+                LogLevel::WARN,
+                format!("Can't save cookie jar {}: {err}", path.display()),
+            ));
+        }
+    }
 
+    fn build_agent(config: &WorkerConfig, proxy: Option<&Url>) -> Agent {
         let mut agent = Agent::config_builder()
-            .timeout_global(Some(Duration::from_secs(self.timeout.try_into().unwrap())))
+            .timeout_global(Some(config.timeout))
             .http_status_as_error(false);
 
-        if let Some(proxy_url) = &self.proxy_url {
+        if let Some(proxy_url) = proxy {
             let proxy = Proxy::new(proxy_url.as_str()).ok();
             agent = agent.proxy(proxy);
         }
 
-        let agent: Agent = agent.build().into();
+        if config.bind_addresses.is_empty() {
+            return agent.build().into();
+        }
 
-        let client = Arc::new(agent);
+        let connector =
+            ().chain(ConnectProxyConnector::default())
+                .chain(BindConnector::new(config.bind_addresses.clone()))
+                .chain(RustlsConnector::default());
 
-        thread::scope(|s| {
-            let mut threads: Vec<ScopedJoinHandle<Result<Vec<Url>, WorkerError>>> = Vec::new();
+        Agent::with_parts(agent.build(), connector, DefaultResolver::default())
+    }
+
+    fn header(res: &Response<Body>, name: &str) -> Option<String> {
+        header_value(res.headers(), name)
+    }
+
+    fn content_type(res: &Response<Body>) -> Option<String> {
+        Self::header(res, "content-type")
+    }
+
+    /// Records a hit and queues its URL for recursion.
+    fn report_hit(&self, hit: HitResult, found: &mut Vec<Url>) {
+        if let Ok(url) = Url::parse(&hit.url) {
+            found.push(url);
+        }
+        self.discovery.hit();
+        self.store_hit(hit.clone());
+        self.send(WorkerMessage::hit(hit));
+    }
+
+    /// Reports a hit unless an earlier extension variant of it answered with
+    /// the same body, in which case it is folded into that one.
+    fn report_unique_hit(&self, hit: HitResult, body: Option<&str>, found: &mut Vec<Url>) {
+        // Held while reporting, so a variant is never folded into a hit
+        // frontends haven't seen yet.
+        let mut variants = self.variants.lock().unwrap();
 
-            for thr in 0..self.threads {
-                let words = lines_arc.clone();
+        match variants.original(&hit.url, hit.status, body) {
+            Some(url) => {
+                self.hits
+                    .lock()
+                    .unwrap()
+                    .annotate(&url, |original| original.variants.push(hit.url.clone()));
+                self.send(WorkerMessage::Variant {
+                    url,
+                    variant: hit.url,
+                });
+            }
+            None => self.report_hit(hit, found),
+        }
+    }
 
-                let message_sender = self.message_sender.clone();
+    /// Keeps a hit for the report, warning once hits start going to disk.
+    fn store_hit(&self, hit: HitResult) {
+        let mut store = self.hits.lock().unwrap();
+        let spilling = store.spilled() > 0;
 
-                let client_cloned = client.clone();
-                let url = url.clone();
+        if let Err(err) = store.push(hit) {
+            self.send(WorkerMessage::log(
+                LogLevel::WARN,
+                format!("Can't spill hits to disk: {err}"),
+            ));
+        } else if !spilling && let Some(path) = store.spill_path() {
+            self.send(WorkerMessage::log(
+                LogLevel::WARN,
+                format!(
+                    "{} hits kept in memory, the rest go to {}",
+                    store.in_memory().len(),
+                    path.display()
+                ),
+            ));
+        }
+    }
+
+    /// Reads as much of the body as the capture quota allows and decodes it to UTF-8.
+    ///
+    /// `None` once the quota is spent.
+    fn read_body(&self, res: &mut Response<Body>) -> Option<String> {
+        let allowance = self.capture.reserve(res.status().as_u16());
+        if allowance == 0 {
+            return None;
+        }
 
-                let threads_num = self.threads;
+        let content_type = Self::content_type(res);
+
+        let mut body = Vec::new();
+        let _ = res
+            .body_mut()
+            .as_reader()
+            .take(allowance)
+            .read_to_end(&mut body);
+        self.capture.release(allowance - body.len() as u64);
+
+        Some(charset::decode(content_type.as_deref(), &body))
+    }
+
+    /// Reports the first WAF block page and pauses the scan if asked to.
+    fn check_waf(&self, status: u16, headers: &HeaderMap, body: &str) {
+        if self.waf.get().is_some() {
+            return;
+        }
+
+        let Some(vendor) = waf::detect(status, headers, body) else {
+            return;
+        };
+
+        if self.waf.set(vendor).is_ok() {
+            if self.config.waf_pause {
+                self.control.pause();
+            }
+            self.send(WorkerMessage::WafDetected(vendor));
+        }
+    }
+
+    /// Follows up to `limit` redirects of `res`, the answer to `target`,
+    /// with the scan's method. Returns the last response and the URL of
+    /// every `Location` on the way, the last one not requested when the
+    /// limit was reached.
+    fn follow(
+        &self,
+        client: &Agent,
+        pacer: &mut Pacer,
+        target: &str,
+        mut res: Response<Body>,
+        limit: usize,
+    ) -> (Response<Body>, Vec<String>) {
+        let mut redirects = Vec::new();
+        let Ok(mut current) = Url::parse(target) else {
+            return (res, redirects);
+        };
+
+        while res.status().is_redirection() {
+            let Some(next) =
+                Self::header(&res, "location").and_then(|location| current.join(&location).ok())
+            else {
+                break;
+            };
+            redirects.push(next.to_string());
+            if redirects.len() > limit || !pacer.acquire(next.host_str().unwrap_or_default()) {
+                break;
+            }
+
+            let start = Instant::now();
+            let response = self.fetch(client, next.as_str(), true, []);
+            self.tally.request(start, response.is_ok());
+            let Ok(next_res) = response else {
+                break;
+            };
+            res = next_res;
+            current = next;
+        }
+
+        (res, redirects)
+    }
+
+    /// Reports the known hit `target` again when the conditional request for
+    /// it answered 304; `false` for any other answer.
+    fn report_unchanged(&self, target: &str, status: u16, result: &mut Found) -> bool {
+        let Some(known) = self.config.known_hits.get(target).filter(|_| status == 304) else {
+            return false;
+        };
+
+        let hit = HitResult {
+            unchanged: true,
+            ..known.clone()
+        };
+        result.directories.extend(self.recursion_target(
+            target,
+            hit.status,
+            &hit.redirects,
+            hit.content_type.as_deref(),
+            None,
+        ));
+        self.report_hit(hit, &mut result.hits);
+        true
+    }
+
+    /// Whether the body of an answer is needed to judge it.
+    fn needs_body(&self, status: u16, content_type: Option<&str>, soft404: &[Soft404]) -> bool {
+        self.config.filters.needs_body()
+            || !soft404.is_empty()
+            || (html::is_html(content_type) && status != 404)
+            || (self.waf.get().is_none() && waf::is_block_status(status))
+            || error_page::is_error_status(status)
+    }
+
+    /// Decides whether `res`, the answer to `target` in the directory `url`,
+    /// is a hit and reports it, whichever engine sent the request. `host` is
+    /// the candidate's virtual host.
+    #[allow(clippy::too_many_arguments)]
+    fn assess(
+        &self,
+        url: &Url,
+        target: String,
+        host: Option<&str>,
+        mut res: impl Answer,
+        redirects: Vec<String>,
+        soft404: &[Soft404],
+        result: &mut Found,
+    ) {
+        let status = res.status_code();
+
+        let content_type = header_value(res.header_map(), "content-type");
+        let is_html = html::is_html(content_type.as_deref());
+        let needs_body = self.needs_body(status, content_type.as_deref(), soft404);
+        let mut body = needs_body.then(|| res.read_body(self)).flatten();
+
+        self.check_waf(
+            status,
+            res.header_map(),
+            body.as_deref().unwrap_or_default(),
+        );
+
+        let size = res
+            .content_length()
+            .or(body.as_ref().map(|body| body.len() as u64));
+        let info = ResponseInfo {
+            url: &target,
+            status,
+            headers: res.header_map(),
+            body: body.as_deref(),
+            size,
+        };
+        let keep = self.config.filters.keep(&info)
+            && !soft404.iter().any(|soft404| soft404.matches(&info));
+        if !keep {
+            self.send(WorkerMessage::Progress(ProgressMessage::Current(
+                ProgressChangeMessage::SetMessage(format!(
+                    "{} {target} -> {status}",
+                    self.config.method
+                )),
+            )));
+            return;
+        }
+        let captures = self.config.filters.captures(&info);
+
+        self.check_headers(&target, res.header_map());
+        if let Some(host) = host {
+            self.check_tls(url, Some(host));
+        }
+
+        if !needs_body && !self.config.extensions.is_empty() {
+            body = res.read_body(self);
+        }
+
+        let static_asset = self.config.suppress_static
+            && self
+                .static_assets
+                .is_static(&target, content_type.as_deref(), size);
+
+        result.directories.extend(self.recursion_target(
+            &target,
+            status,
+            &redirects,
+            content_type.as_deref(),
+            body.as_deref(),
+        ));
+
+        let hit = HitResult {
+            url: target.clone(),
+            status,
+            title: body
+                .as_deref()
+                .filter(|_| is_html)
+                .and_then(html::extract_title),
+            content_type,
+            size,
+            words: body.as_deref().map(|body| BodyCount::Words.of(body)),
+            lines: body.as_deref().map(|body| BodyCount::Lines.of(body)),
+            final_url: redirects.last().cloned(),
+            redirects,
+            static_asset,
+            etag: header_value(res.header_map(), "etag"),
+            last_modified: header_value(res.header_map(), "last-modified"),
+            unchanged: false,
+            tags: body
+                .as_deref()
+                .and_then(error_page::detect)
+                .map(|page| page.tags().map(str::to_string).to_vec())
+                .unwrap_or_default(),
+            dom_hash: body
+                .as_deref()
+                .filter(|_| is_html)
+                .map(|body| format!("{:016x}", html::dom_hash(body))),
+            variants: Vec::new(),
+            first_found_by: None,
+            captures,
+            timings: self.time_phases(&target, host),
+            triage: None,
+        };
+
+        self.report_unique_hit(hit, body.as_deref(), &mut result.hits);
+    }
+
+    /// Reports a candidate whose request failed, `in_a_row` of them by now.
+    fn request_failed(&self, target: &str, in_a_row: usize, err: &dyn fmt::Display) {
+        self.check_errors(in_a_row, err);
+        self.send(WorkerMessage::Log(
+            LogLevel::WARN,
+            format!("Error while sending request to {target}: {err}"),
+        ));
+    }
+
+    /// Directory to recurse into below a hit, any hit with forced recursion.
+    fn recursion_target(
+        &self,
+        target: &str,
+        status: u16,
+        redirects: &[String],
+        content_type: Option<&str>,
+        body: Option<&str>,
+    ) -> Option<Url> {
+        let url = Url::parse(target).ok()?;
+        if self.config.force_recursion {
+            return Some(url);
+        }
+        directory::recursion_target(&url, status, redirects, content_type, body)
+    }
+
+    fn behind_proxy(&self) -> bool {
+        self.config.proxy_url.is_some() || self.proxy_pool.is_some()
+    }
+
+    /// Counts a request through a rotated proxy, warning when it is dropped;
+    /// `true` when it failed and another proxy can be tried.
+    fn proxy_used(&self, proxy: &PooledProxy, ok: bool) -> bool {
+        let Some(pool) = &self.proxy_pool else {
+            return false;
+        };
+
+        if pool.record(proxy, ok) {
+            self.send(WorkerMessage::log(
+                LogLevel::WARN,
+                format!(
+                    "Proxy {} failed {} times in a row and was dropped, {} left",
+                    proxy.url,
+                    proxy_pool::MAX_FAILURES,
+                    pool.alive()
+                ),
+            ));
+        }
+        !ok && pool.alive() > 1
+    }
+
+    /// Holds every thread back after a 429, warning when a back-off starts.
+    fn too_many_requests(&self, headers: &HeaderMap) {
+        let retry_after = scheduler::retry_after(headers);
+        if let Some(backoff) = self.scheduler.too_many_requests(retry_after) {
+            self.send(WorkerMessage::log(
+                LogLevel::WARN,
+                format!(
+                    "Too many requests, backing off for {}",
+                    util::format_duration(backoff)
+                ),
+            ));
+        }
+    }
+
+    /// Moves both progress bars `steps` requests on.
+    fn advance(&self, steps: usize) {
+        if steps == 0 {
+            return;
+        }
+        self.send(WorkerMessage::advance_current_by(steps));
+        self.send(WorkerMessage::advance_total_by(steps));
+    }
+
+    /// Parks thread `index` while adaptive concurrency lets fewer threads
+    /// send requests; `false` once the scan is stopped, or once the
+    /// directory is `exhausted` or past its `deadline`, as the limit no
+    /// longer changes then.
+    fn wait_turn(&self, index: usize, exhausted: &AtomicBool, deadline: Option<Instant>) -> bool {
+        let Some(concurrency) = &self.concurrency else {
+            return true;
+        };
+        while index >= concurrency.limit() {
+            if self.control.is_stopped()
+                || exhausted.load(Ordering::Relaxed)
+                || deadline.is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                return false;
+            }
+            thread::sleep(TURN_POLL);
+        }
+        true
+    }
+
+    /// Feeds adaptive concurrency with how a request went; 429 and 503
+    /// answers count as failures, as the target is asking to slow down.
+    fn adapt(&self, ok: bool, latency: Duration) {
+        let Some(threads) = self
+            .concurrency
+            .as_ref()
+            .and_then(|concurrency| concurrency.record(ok, latency))
+        else {
+            return;
+        };
+
+        self.send(WorkerMessage::Concurrency(threads));
+        self.send(WorkerMessage::log(
+            LogLevel::INFO,
+            format!("Concurrency set to {threads} threads"),
+        ));
+    }
+
+    /// Aborts the scan once `max_errors` candidates in a row failed, which
+    /// they keep doing when the target is gone.
+    fn check_errors(&self, in_a_row: usize, err: &dyn fmt::Display) {
+        let Some(max_errors) = self.config.max_errors else {
+            return;
+        };
+        if in_a_row < max_errors {
+            return;
+        }
+
+        let reason = format!(
+            "{in_a_row} requests in a row failed ({} in total), the last with: {err}",
+            self.tally.errors.load(Ordering::Relaxed)
+        );
+        if self.aborted.set(reason.clone()).is_err() {
+            return;
+        }
+
+        self.send(WorkerMessage::log(
+            LogLevel::CRITICAL,
+            format!("Aborting the scan: {reason}"),
+        ));
+        self.send(WorkerMessage::Aborted(reason));
+        self.control.stop();
+    }
+
+    /// Suggests stopping a scan that stopped finding things, or stops it
+    /// with early stop on.
+    fn slowed_down(&self, slowdown: Slowdown) {
+        let msg = format!(
+            "Only {} hits in the last {} requests at {:.0}% of the wordlist, the rest is unlikely to find much more",
+            slowdown.hits,
+            discovery::WINDOW,
+            slowdown.progress * 100.0
+        );
+
+        if self.config.early_stop {
+            self.send(WorkerMessage::log(
+                LogLevel::WARN,
+                format!("{msg}, stopping"),
+            ));
+            self.control.stop();
+        } else {
+            self.send(WorkerMessage::Progress(ProgressMessage::Current(
+                ProgressChangeMessage::Print(format!("{msg} (--early-stop stops here)")),
+            )));
+            self.send(WorkerMessage::log(LogLevel::INFO, msg));
+        }
+    }
+
+    /// Reports what the headers of the hit at `url` reveal, once per finding.
+    fn check_headers(&self, url: &str, headers: &HeaderMap) {
+        let findings = self.headers.lock().unwrap().analyze(url, headers);
+        for finding in findings {
+            self.send(WorkerMessage::HeaderFinding(finding));
+        }
+    }
+
+    /// Checkpoint file, unless the scan is a pipeline.
+    fn checkpoint_path(&self) -> Option<&Path> {
+        self.config
+            .checkpoint
+            .as_deref()
+            .filter(|_| self.config.stages.is_empty())
+    }
+
+    /// How far the scan got, with the number of hits so far.
+    fn checkpoint(&self) -> Checkpoint {
+        let position = self.position.lock().unwrap();
+        let hits = self.restored_hits() + self.hits.lock().unwrap().len();
+        Checkpoint {
+            target: self.config.uri.to_string(),
+            seed: self.config.seed,
+            pending: position
+                .pending
+                .iter()
+                .map(|(url, depth)| (url.to_string(), *depth))
+                .collect(),
+            offset: position.offset(),
+            hits,
+        }
+    }
+
+    /// Saves a checkpoint every [`checkpoint::INTERVAL`] while `scanning`.
+    fn keep_checkpoint(&self, path: &Path, scanning: &AtomicBool) {
+        let mut next = Instant::now() + checkpoint::INTERVAL;
+
+        while scanning.load(Ordering::Relaxed) && !self.control.is_stopped() {
+            if Instant::now() < next {
+                thread::sleep(CANARY_POLL);
+                continue;
+            }
+            next = Instant::now() + checkpoint::INTERVAL;
+
+            if let Err(err) = self.checkpoint().save(path) {
+                self.send(WorkerMessage::log(LogLevel::WARN, format!("{err:#}")));
+            }
+        }
+    }
+
+    /// Says where a resumed scan goes on from. The hits of the run the
+    /// checkpoint was saved by are in the outputs already and aren't
+    /// reported again.
+    fn restore(&self, resume: &Checkpoint) {
+        self.send(WorkerMessage::log(
+            LogLevel::INFO,
+            format!(
+                "Resuming with {} directories left and {} hits found before",
+                resume.pending.len(),
+                resume.hits
+            ),
+        ));
+    }
+
+    /// Hits found by the runs before the checkpoint resumed from.
+    fn restored_hits(&self) -> usize {
+        self.config.resume.as_ref().map_or(0, |resume| resume.hits)
+    }
+
+    /// Requests the canary every `canary_interval` while `scanning`, reporting
+    /// when its answer changes notably and when it recovers.
+    fn watch_canary(&self, client: &Agent, url: &Url, scanning: &AtomicBool) {
+        let mut canary = Canary::default();
+        let mut next = Instant::now();
+
+        while scanning.load(Ordering::Relaxed) && !self.control.is_stopped() {
+            if Instant::now() < next {
+                thread::sleep(CANARY_POLL);
+                continue;
+            }
+            next = Instant::now() + self.config.canary_interval;
+
+            let start = Instant::now();
+            let response = self.get(client, url.as_str()).call();
+            self.tally.request(start, response.is_ok());
+            let status = response.ok().map(|res| res.status().as_u16());
+
+            if let Some(alert) = canary.observe(url.as_str(), status, start.elapsed()) {
+                self.send(WorkerMessage::Canary(alert));
+            }
+        }
+    }
+
+    /// Phase timings of requesting `target` again, for virtual host `vhost` if
+    /// given, when they were asked for. The request is the scan's own, with
+    /// its method, headers and bind address, and waits for a permit like the
+    /// others; it is skipped behind a proxy, which its connection would
+    /// bypass, and with a cookie jar, whose cookies it can't carry.
+    fn time_phases(&self, target: &str, vhost: Option<&str>) -> Option<PhaseTimings> {
+        if !self.config.phase_timings || self.behind_proxy() || self.config.cookie_jar.is_some() {
+            return None;
+        }
+
+        let url = Url::parse(target).ok()?;
+        let mut pacer = self.scheduler.pacer(self.config.delay);
+        if !pacer.acquire(url.host_str().unwrap_or_default()) {
+            return None;
+        }
+
+        let profile = self.config.browser_profile.map(|profile| profile.headers());
+        let headers: Vec<(&str, &str)> = profile
+            .into_iter()
+            .flatten()
+            .copied()
+            .chain(
+                self.config
+                    .headers
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value.as_str())),
+            )
+            .collect();
+
+        let start = Instant::now();
+        let timings = timing::measure(
+            &url,
+            self.config.method,
+            vhost,
+            &headers,
+            &self.config.bind_addresses,
+            self.config.timeout,
+        );
+        self.tally.request(start, timings.is_ok());
+        match timings {
+            Ok(timings) => Some(timings),
+            Err(err) => {
+                self.send(WorkerMessage::log(
+                    LogLevel::WARN,
+                    format!("Can't time the phases of {target}: {err}"),
+                ));
+                None
+            }
+        }
+    }
+
+    /// Records the TLS details of `url`'s host, or of the virtual host `vhost`
+    /// reached through it, the first time it is seen. Skipped behind a proxy,
+    /// which the handshake would bypass.
+    fn check_tls(&self, url: &Url, vhost: Option<&str>) {
+        if url.scheme() != "https" || self.behind_proxy() {
+            return;
+        }
+        let Some(addr) = url.host_str() else {
+            return;
+        };
+        let host = vhost.unwrap_or(addr);
+        let port = url.port_or_known_default().unwrap_or(443);
+
+        {
+            let mut seen = self.tls.lock().unwrap();
+            if seen.iter().any(|tls| tls.host == host && tls.port == port) {
+                return;
+            }
+            // Reserved while the handshake runs, so other threads don't repeat it.
+            seen.push(TlsInfo {
+                host: host.to_string(),
+                port,
+                version: "-".to_string(),
+                cipher: "-".to_string(),
+                subject: None,
+                not_after: None,
+                problem: None,
+            });
+        }
+
+        match tls::probe((addr, port), host, self.config.timeout) {
+            Ok(info) => {
+                let mut seen = self.tls.lock().unwrap();
+                if let Some(known) = seen
+                    .iter_mut()
+                    .find(|tls| tls.host == host && tls.port == port)
+                {
+                    *known = info.clone();
+                }
+                drop(seen);
+                self.send(WorkerMessage::Tls(info));
+            }
+            Err(err) => {
+                self.tls
+                    .lock()
+                    .unwrap()
+                    .retain(|tls| !(tls.host == host && tls.port == port));
+                self.send(WorkerMessage::log(
+                    LogLevel::WARN,
+                    format!("Can't read TLS details of {host}:{port}: {err}"),
+                ));
+            }
+        }
+    }
+
+    /// Builds a GET request carrying the configured browser and extra headers.
+    fn get(&self, client: &Agent, url: &str) -> RequestBuilder<WithoutBody> {
+        self.with_headers(client.get(url))
+    }
+
+    /// Builds a HEAD request carrying the same headers as [`Worker::get`].
+    fn head(&self, client: &Agent, url: &str) -> RequestBuilder<WithoutBody> {
+        self.with_headers(client.head(url))
+    }
+
+    fn with_headers<B>(&self, mut request: RequestBuilder<B>) -> RequestBuilder<B> {
+        if let Some(profile) = self.config.browser_profile {
+            for (name, value) in profile.headers() {
+                request = request.header(*name, *value);
+            }
+        }
+
+        for (name, value) in &self.config.headers {
+            request = request.header(name, value);
+        }
+
+        request
+    }
+
+    /// Requests `url` with the configured method, carrying `headers` on top
+    /// of the browser and extra ones when `with_defaults` is set.
+    fn fetch<'a>(
+        &self,
+        client: &Agent,
+        url: &str,
+        with_defaults: bool,
+        headers: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Response<Body>, ureq::Error> {
+        fn build<'h, B>(
+            worker: &Worker,
+            request: RequestBuilder<B>,
+            with_defaults: bool,
+            headers: impl IntoIterator<Item = (&'h str, &'h str)>,
+        ) -> RequestBuilder<B> {
+            // Redirects are followed by `follow`, so every hop is seen.
+            let request = request.config().max_redirects(0).build();
+            let mut request = if with_defaults {
+                worker.with_headers(request)
+            } else {
+                request
+            };
+            for (name, value) in headers {
+                request = request.header(name, value);
+            }
+            request
+        }
+
+        match self.config.method {
+            HttpMethod::Get => build(self, client.get(url), with_defaults, headers).call(),
+            HttpMethod::Head => build(self, client.head(url), with_defaults, headers).call(),
+            HttpMethod::Options => build(self, client.options(url), with_defaults, headers).call(),
+            HttpMethod::Post => build(self, client.post(url), with_defaults, headers).send_empty(),
+            HttpMethod::Put => build(self, client.put(url), with_defaults, headers).send_empty(),
+        }
+    }
+
+    /// Turns HEAD-first mode off when the target answers HEAD with a success
+    /// for a path that GET says doesn't exist, since HEAD would then let
+    /// every word through.
+    fn check_head(&self, client: &Agent, target: &Url) {
+        let word = format!("{:016x}", self.rng.lock().unwrap().next_u64());
+        let Ok(probe) = target.join(&word) else {
+            return;
+        };
+
+        let start = Instant::now();
+        let head = self.head(client, probe.as_str()).call();
+        self.tally.request(start, head.is_ok());
+        let Ok(head) = head else {
+            return;
+        };
+        if !head.status().is_success() {
+            return;
+        }
+
+        let start = Instant::now();
+        let get = self.get(client, probe.as_str()).call();
+        self.tally.request(start, get.is_ok());
+        if let Ok(get) = get
+            && get.status().as_u16() == 404
+        {
+            self.head_first.store(false, Ordering::Relaxed);
+            self.send(WorkerMessage::log(
+                LogLevel::WARN,
+                format!(
+                    "{target} answers HEAD with {} but GET with 404 for missing paths, sending GET only",
+                    head.status().as_u16()
+                ),
+            ));
+        }
+    }
+
+    /// Whether a HEAD request says `target` doesn't exist, so that the GET
+    /// can be skipped.
+    fn head_missing(&self, client: &Agent, target: &str) -> bool {
+        let start = Instant::now();
+        let response = self.head(client, target).call();
+        self.tally.request(start, response.is_ok());
+        response.is_ok_and(|res| res.status().as_u16() == 404)
+    }
+
+    /// Learns what `url` answers for paths that don't exist, probing with a
+    /// directory and a file name that surely don't.
+    fn calibrate_directory(&self, client: &Agent, url: &Url) -> Vec<Soft404> {
+        let nonce = self.rng.lock().unwrap().next_u64();
+        let extension = self
+            .config
+            .extensions
+            .first()
+            .map_or("html", String::as_str);
+        let slash = if self.config.add_slash { "/" } else { "" };
+
+        let mut baselines = Vec::new();
+        for probe in [
+            format!("yadb-{nonce:016x}{slash}"),
+            format!("yadb-{nonce:016x}.{extension}"),
+        ] {
+            let Some(probe) = candidates::join_path(url, &probe) else {
+                continue;
+            };
+            let probe = probe.to_string();
+            let start = Instant::now();
+            let response = self.fetch(client, &probe, true, []);
+            self.tally.request(start, response.is_ok());
+            let Ok(mut res) = response else {
+                continue;
+            };
+
+            let body = self.read_body(&mut res);
+            let info = ResponseInfo {
+                url: &probe,
+                status: res.status().as_u16(),
+                headers: res.headers(),
+                size: res
+                    .body()
+                    .content_length()
+                    .or(body.as_ref().map(|body| body.len() as u64)),
+                body: body.as_deref(),
+            };
+            if let Some(soft404) = Soft404::learn(&info)
+                && !baselines.contains(&soft404)
+            {
+                baselines.push(soft404);
+            }
+        }
+
+        if let Some(soft404) = baselines.first() {
+            self.send(WorkerMessage::log(
+                LogLevel::INFO,
+                format!(
+                    "{url} answers {} for paths that don't exist, dropping responses like it there",
+                    soft404.status()
+                ),
+            ));
+        }
+        baselines
+    }
+
+    /// Calibrates the filters with a virtual host that surely doesn't exist.
+    fn calibrate_vhost(&self, client: &Agent, url: &Url) {
+        let nonce = self.rng.lock().unwrap().next_u64();
+        let host = format!("yadb-{nonce}.{}", url.host_str().unwrap_or_default());
+
+        let Ok(mut res) = self.get(client, url.as_str()).header("Host", &host).call() else {
+            return;
+        };
+
+        let body = self
+            .config
+            .filters
+            .needs_body()
+            .then(|| self.read_body(&mut res))
+            .flatten();
+        self.config.filters.calibrate(&ResponseInfo {
+            url: url.as_str(),
+            status: res.status().as_u16(),
+            headers: res.headers(),
+            size: res
+                .body()
+                .content_length()
+                .or(body.as_ref().map(|body| body.len() as u64)),
+            body: body.as_deref(),
+        });
+    }
+
+    /// Requests the candidates of `url` from the one at `offset` on,
+    /// returning the hits and the directories among them.
+    pub fn execute(
+        &self,
+        client: &Agent,
+        url: Url,
+        generator: &CandidateGenerator,
+        offset: usize,
+        deadline: Option<Instant>,
+    ) -> Result<Found> {
+        let threads_num = self.config.concurrency;
+        let delay = self.config.delay;
+
+        let mut result = Found::default();
+
+        if self.config.scan_mode == ScanMode::Vhost {
+            self.calibrate_vhost(client, &url);
+        }
+        let soft404 = match self.config.scan_mode {
+            ScanMode::Directory if self.config.calibration => {
+                self.calibrate_directory(client, &url)
+            }
+            _ => Vec::new(),
+        };
+
+        #[cfg(feature = "async")]
+        if self.config.engine == crate::lib::worker::config::Engine::Async {
+            return self.execute_async(&url, generator, offset, deadline, &soft404);
+        }
+
+        let candidates = Mutex::new(generator.candidates(&url).skip(offset));
+        let exhausted = AtomicBool::new(false);
+
+        thread::scope(|s| {
+            let mut threads: Vec<ScopedJoinHandle<Result<Found, WorkerError>>> = Vec::new();
+
+            for index in 0..threads_num {
+                let url = &url;
+                let candidates = &candidates;
+                let exhausted = &exhausted;
+                let soft404 = &soft404;
 
                 threads.push(s.spawn(move || {
-                    let words = words.clone();
-                    let words_slice = if thr != threads_num - 1 {
-                        &words[slice_size * thr..slice_size * thr + slice_size]
-                    } else {
-                        &words[slice_size * thr..]
+                    let mut result = Found::default();
+                    let host = url.host_str().unwrap_or_default();
+                    let seed = self.rng.lock().unwrap().next_u64();
+                    let mut pacer = self
+                        .scheduler
+                        .pacer(delay)
+                        .jitter(self.config.jitter, seed)
+                        .ramp(index, threads_num);
+
+                    let mut progress = PendingProgress::new();
+                    let mut advance = || {
+                        if let Some(steps) = progress.step() {
+                            self.advance(steps);
+                        }
+
+                        if let Some(slowdown) = self.discovery.request() {
+                            self.slowed_down(slowdown);
+                        }
                     };
 
-                    let mut result: Vec<Url> = Vec::new();
+                    loop {
+                        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                            break;
+                        }
+
+                        if !self.wait_turn(index, exhausted, deadline) {
+                            break;
+                        }
 
-                    for word in words_slice {
-                        let url = if url.to_string().ends_with("/") {
-                            format!("{url}{word}/")
-                        } else {
-                            format!("{url}/{word}/")
+                        let (candidate, taken) = {
+                            let mut candidates = candidates.lock().unwrap();
+                            let Some(candidate) = candidates.next() else {
+                                exhausted.store(true, Ordering::Relaxed);
+                                break;
+                            };
+                            (candidate, Position::take(&self.position))
                         };
 
-                        match client_cloned.get(&url).call() {
-                            Ok(res) => {
-                                let status = res.status().as_u16();
-                                if status != 404 {
-                                    // cpb.println(format!("GET {url} -> {}", style(status).cyan()));
-                                    message_sender
-                                        .send(WorkerMessage::Progress(ProgressMessage::Current(
-                                            ProgressChangeMessage::Print(format!(
-                                                "GET {url} -> {status}",
-                                            )),
-                                        )))
-                                        .expect("SENDER ERROR");
-
-                                    // logger.log(LogLevel::INFO, format!("{url} -> {status}"));
-                                    message_sender
-                                        .send(WorkerMessage::Log(
-                                            LogLevel::INFO,
-                                            format!("{url} -> {status}"),
-                                        ))
-                                        .expect("SENDER ERROR");
-
-                                    result.push(Url::parse(&url).unwrap());
-                                } else {
-                                    // cpb.set_message(format!("GET {url} -> {}", style(status).red()));
-                                    message_sender
-                                        .send(WorkerMessage::Progress(ProgressMessage::Current(
-                                            ProgressChangeMessage::SetMessage(format!(
-                                                "GET {url} -> {status}",
-                                            )),
-                                        )))
-                                        .expect("SENDER ERROR");
+                        // Only threads with a candidate wait for a permit, so
+                        // idle ones don't use up the rate limit.
+                        if !pacer.acquire(host) {
+                            taken.abandon();
+                            break;
+                        }
+
+                        let candidate = match candidate {
+                            Ok(candidate) => candidate,
+                            Err(host) => {
+                                self.tally.error();
+                                self.send(WorkerMessage::log(
+                                    LogLevel::WARN,
+                                    format!("Invalid virtual host: {host}"),
+                                ));
+                                advance();
+                                continue;
+                            }
+                        };
+
+                        if candidate.host.is_none() && !self.robots_allow(&candidate.target) {
+                            advance();
+                            continue;
+                        }
+
+                        if candidate.host.is_none() && self.head_first.load(Ordering::Relaxed) {
+                            if self.head_missing(client, &candidate.target) {
+                                self.send(WorkerMessage::Progress(ProgressMessage::Current(
+                                    ProgressChangeMessage::SetMessage(format!(
+                                        "HEAD {} -> 404",
+                                        candidate.target
+                                    )),
+                                )));
+                                advance();
+                                continue;
+                            }
+
+                            // The GET after it counts against the rate limit
+                            // as a request of its own.
+                            if !pacer.acquire(host) {
+                                taken.abandon();
+                                break;
+                            }
+                        }
+
+                        let mut attempt = 0;
+                        let mut throttled = 0;
+                        let mut switched = 0;
+                        let response = loop {
+                            let mut headers = self
+                                .config
+                                .known_hits
+                                .conditional_headers(&candidate.target);
+                            let target = match &candidate.host {
+                                None => candidate.target.as_str(),
+                                Some(host) => {
+                                    headers.push(("Host", host));
+                                    url.as_str()
                                 }
+                            };
+
+                            let proxy = self.proxy_pool.as_deref().map(ProxyPool::next);
+                            let start = Instant::now();
+                            let response = self.fetch(
+                                proxy.map_or(client, |proxy| &proxy.agent),
+                                target,
+                                true,
+                                headers,
+                            );
+                            self.tally.request(start, response.is_ok());
+                            self.adapt(
+                                matches!(&response, Ok(res) if !matches!(res.status().as_u16(), 429 | 503)),
+                                start.elapsed(),
+                            );
+                            // A proxy failing isn't the candidate's fault, so
+                            // the others are tried before using up retries.
+                            if let Some(proxy) = proxy
+                                && self.proxy_used(proxy, response.is_ok())
+                                && switched < MAX_PROXY_SWITCHES
+                                && !self.control.is_stopped()
+                            {
+                                switched += 1;
+                                continue;
                             }
-                            Err(e) => {
-                                // cpb.println(format!(
-                                //     "Error while sending request to {}: {e}",
-                                //     style(&url).red()
-                                // ));
-                                message_sender
-                                    .send(WorkerMessage::Log(
-                                        LogLevel::WARN,
-                                        format!("Error while sending request to {url}: {e}",),
-                                    ))
-                                    .expect("SENDER ERROR")
+                            match &response {
+                                Ok(res) if res.status().as_u16() == 429 => {
+                                    if throttled < MAX_THROTTLED_ATTEMPTS
+                                        && !self.control.is_stopped()
+                                    {
+                                        throttled += 1;
+                                        self.too_many_requests(res.headers());
+                                        if !pacer.acquire(host) {
+                                            break response;
+                                        }
+                                        continue;
+                                    }
+                                }
+                                Ok(_) => self.scheduler.recovered(),
+                                Err(_) => {}
+                            }
+                            if response.is_ok()
+                                || attempt >= self.config.retries
+                                || self.control.is_stopped()
+                            {
+                                break response;
                             }
+                            attempt += 1;
+                        };
+                        let target = candidate.target;
+
+                        if let Ok(res) = &response
+                            && self.report_unchanged(&target, res.status().as_u16(), &mut result)
+                        {
+                            advance();
+                            continue;
                         }
-                        // cpb.advance();
-                        // tpb.advance();
 
-                        message_sender
-                            .send(WorkerMessage::advance_current())
-                            .expect("SENDER ERROR");
+                        // Virtual hosts only get where they redirect to.
+                        let limit = match candidate.host {
+                            None => self.config.follow_redirects,
+                            Some(_) => 0,
+                        };
+                        let (response, redirects) = match response {
+                            Ok(res) => {
+                                let (res, redirects) =
+                                    self.follow(client, &mut pacer, &target, res, limit);
+                                (Ok(res), redirects)
+                            }
+                            Err(err) => (Err(err), Vec::new()),
+                        };
+
+                        let failed_in_a_row = self.tally.candidate(response.is_ok());
+                        match response {
+                            Ok(res) => self.assess(
+                                url,
+                                target,
+                                candidate.host.as_deref(),
+                                res,
+                                redirects,
+                                soft404,
+                                &mut result,
+                            ),
+                            Err(err) => self.request_failed(&target, failed_in_a_row, &err),
+                        }
 
-                        message_sender
-                            .send(WorkerMessage::advance_total())
-                            .expect("SENDER ERROR");
+                        advance();
                     }
 
+                    self.advance(progress.take());
                     Ok(result)
                 }));
             }
@@ -204,20 +2218,15 @@ impl Worker {
             for thread in threads {
                 match thread.join() {
                     Ok(Ok(res)) => {
-                        result.extend(res);
+                        result.hits.extend(res.hits);
+                        result.directories.extend(res.directories);
                     }
 
-                    Ok(Err(err)) => self
-                        .message_sender
-                        .send(WorkerMessage::log(LogLevel::ERROR, err.to_string()))
-                        .expect("SENDER ERROR"),
-                    Err(err) => self
-                        .message_sender
-                        .send(WorkerMessage::log(
-                            LogLevel::CRITICAL,
-                            format!("Panic in thread: {err:?}"),
-                        ))
-                        .expect("SENDER ERROR"),
+                    Ok(Err(err)) => self.send(WorkerMessage::log(LogLevel::ERROR, err.to_string())),
+                    Err(err) => self.send(WorkerMessage::log(
+                        LogLevel::CRITICAL,
+                        format!("Panic in thread: {err:?}"),
+                    )),
                 }
             }
         });
@@ -225,3 +2234,57 @@ impl Worker {
         Ok(result)
     }
 }
+
+/// A response as the hit pipeline sees it, whichever engine received it.
+trait Answer {
+    fn status_code(&self) -> u16;
+    fn header_map(&self) -> &HeaderMap;
+    /// Length the response announced.
+    fn content_length(&self) -> Option<u64>;
+    /// Reads what the capture quota allows of the body.
+    fn read_body(&mut self, worker: &Worker) -> Option<String>;
+}
+
+impl Answer for Response<Body> {
+    fn status_code(&self) -> u16 {
+        self.status().as_u16()
+    }
+
+    fn header_map(&self) -> &HeaderMap {
+        self.headers()
+    }
+
+    fn content_length(&self) -> Option<u64> {
+        self.body().content_length()
+    }
+
+    fn read_body(&mut self, worker: &Worker) -> Option<String> {
+        worker.read_body(self)
+    }
+}
+
+fn header_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Whether `location` is HTTPS on the host of `url`.
+fn is_https_of(location: &Url, url: &Url) -> bool {
+    location.scheme() == "https" && location.host_str() == url.host_str()
+}
+
+/// Settings of a stage, or the scan's ones when the stage has none.
+fn or_scan<T: Clone>(stage: &[T], scan: &[T]) -> Vec<T> {
+    if stage.is_empty() { scan } else { stage }.to_vec()
+}
+
+/// Text of a panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause")
+}
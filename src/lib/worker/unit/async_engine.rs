@@ -0,0 +1,457 @@
+use std::panic;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Instant;
+
+use anyhow::Result;
+use futures_util::future::join_all;
+use reqwest::{Client, Method, Proxy, redirect};
+use tokio::runtime::{self, Runtime};
+use tokio::sync::{Mutex, mpsc};
+use tokio::task;
+use ureq::http::HeaderMap;
+use url::Url;
+
+use super::{
+    Answer, Found, MAX_THROTTLED_ATTEMPTS, PendingProgress, TURN_POLL, Worker, header_value,
+};
+use crate::lib::logger::traits::LogLevel;
+use crate::lib::worker::candidates::{Candidate, CandidateGenerator};
+use crate::lib::worker::charset;
+use crate::lib::worker::checkpoint::Position;
+use crate::lib::worker::config::{HttpMethod, WorkerConfig};
+use crate::lib::worker::messages::WorkerMessage;
+use crate::lib::worker::scheduler::Pacer;
+use crate::lib::worker::soft404::Soft404;
+
+/// Runtime and client of the async engine, made on the first directory
+/// scanned with it.
+#[derive(Debug)]
+pub(super) struct AsyncEngine {
+    runtime: Runtime,
+    client: Client,
+}
+
+impl AsyncEngine {
+    pub(super) fn new(config: &WorkerConfig) -> Result<Self> {
+        let mut runtime = runtime::Builder::new_multi_thread();
+        if let Some(workers) = config.runtime_workers {
+            runtime.worker_threads(workers.max(1));
+        }
+
+        let mut client = Client::builder()
+            .timeout(config.timeout)
+            // Redirects are followed by `follow_async`, so every hop is seen.
+            .redirect(redirect::Policy::none());
+        if let Some(proxy_url) = &config.proxy_url {
+            client = client.proxy(Proxy::all(proxy_url.as_str())?);
+        }
+
+        Ok(Self {
+            runtime: runtime.enable_all().build()?,
+            client: client.build()?,
+        })
+    }
+}
+
+/// A response whose body was read before judging it, as that can't wait
+/// inside the synchronous hit pipeline.
+struct Buffered {
+    status: u16,
+    headers: HeaderMap,
+    content_length: Option<u64>,
+    body: Option<String>,
+}
+
+impl Answer for Buffered {
+    fn status_code(&self) -> u16 {
+        self.status
+    }
+
+    fn header_map(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    fn content_length(&self) -> Option<u64> {
+        self.content_length
+    }
+
+    fn read_body(&mut self, _worker: &Worker) -> Option<String> {
+        self.body.take()
+    }
+}
+
+impl Worker {
+    /// [`Worker::execute`] with a task of a tokio runtime in place of every
+    /// thread, so thousands of requests can be in flight at once.
+    pub(super) fn execute_async(
+        &self,
+        url: &Url,
+        generator: &CandidateGenerator,
+        offset: usize,
+        deadline: Option<Instant>,
+        soft404: &[Soft404],
+    ) -> Result<Found> {
+        if self.async_engine.get().is_none() {
+            let _ = self.async_engine.set(AsyncEngine::new(&self.config)?);
+        }
+        let engine = self.async_engine.get().expect("engine was just set");
+
+        let tasks = self.config.concurrency;
+        let worker = Arc::new(self.clone());
+        let soft404 = Arc::<[Soft404]>::from(soft404);
+        let exhausted = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = mpsc::channel(tasks.max(1));
+        let candidates = Arc::new(Mutex::new(receiver));
+
+        let results = thread::scope(|scope| {
+            // Candidates borrow the generator, so a thread of their own hands
+            // them to the tasks; it ends once the tasks drop the receiver.
+            scope.spawn(move || {
+                for candidate in generator.candidates(url).skip(offset) {
+                    if sender.blocking_send(candidate).is_err() {
+                        break;
+                    }
+                }
+            });
+
+            let handles = (0..tasks)
+                .map(|index| {
+                    engine.runtime.spawn(worker.clone().task(
+                        engine.client.clone(),
+                        url.clone(),
+                        candidates.clone(),
+                        exhausted.clone(),
+                        index,
+                        deadline,
+                        soft404.clone(),
+                    ))
+                })
+                .collect::<Vec<_>>();
+            drop(candidates);
+            engine.runtime.block_on(join_all(handles))
+        });
+
+        let mut found = Found::default();
+        for result in results {
+            let result = match result {
+                Ok(result) => result,
+                // A crashed task fails the worker as a crashed thread would.
+                Err(err) if err.is_panic() => panic::resume_unwind(err.into_panic()),
+                Err(_) => continue,
+            };
+            found.hits.extend(result.hits);
+            found.directories.extend(result.directories);
+        }
+        Ok(found)
+    }
+
+    /// Requests candidates until there are none left, as thread `index`
+    /// of [`Worker::execute`] would. Judging an answer can block, on TLS
+    /// probes, phase timings and the output sinks, so it is done off the
+    /// runtime's threads.
+    #[allow(clippy::too_many_arguments)]
+    async fn task(
+        self: Arc<Self>,
+        client: Client,
+        url: Url,
+        candidates: Arc<Mutex<mpsc::Receiver<Result<Candidate, String>>>>,
+        exhausted: Arc<AtomicBool>,
+        index: usize,
+        deadline: Option<Instant>,
+        soft404: Arc<[Soft404]>,
+    ) -> Found {
+        let mut result = Found::default();
+        let host = url.host_str().unwrap_or_default();
+        let seed = self.rng.lock().unwrap().next_u64();
+        let mut pacer = self
+            .scheduler
+            .pacer(self.config.delay)
+            .jitter(self.config.jitter, seed)
+            .ramp(index, self.config.concurrency);
+
+        let mut progress = PendingProgress::new();
+        let mut advance = || {
+            if let Some(steps) = progress.step() {
+                self.advance(steps);
+            }
+
+            if let Some(slowdown) = self.discovery.request() {
+                self.slowed_down(slowdown);
+            }
+        };
+
+        loop {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                break;
+            }
+
+            if !self.wait_turn_async(index, &exhausted, deadline).await {
+                break;
+            }
+
+            let (candidate, taken) = {
+                let mut candidates = candidates.lock().await;
+                let Some(candidate) = candidates.recv().await else {
+                    exhausted.store(true, Ordering::Relaxed);
+                    break;
+                };
+                (candidate, Position::take(&self.position))
+            };
+
+            if !pacer.acquire_async(host).await {
+                taken.abandon();
+                break;
+            }
+
+            let candidate = match candidate {
+                Ok(candidate) => candidate,
+                Err(host) => {
+                    self.tally.error();
+                    self.send(WorkerMessage::log(
+                        LogLevel::WARN,
+                        format!("Invalid virtual host: {host}"),
+                    ));
+                    advance();
+                    continue;
+                }
+            };
+
+            if candidate.host.is_none() && !self.robots_allow(&candidate.target) {
+                advance();
+                continue;
+            }
+
+            let mut attempt = 0;
+            let mut throttled = 0;
+            let response = loop {
+                let mut headers = self
+                    .config
+                    .known_hits
+                    .conditional_headers(&candidate.target);
+                let target = match &candidate.host {
+                    None => candidate.target.as_str(),
+                    Some(host) => {
+                        headers.push(("Host", host));
+                        url.as_str()
+                    }
+                };
+
+                let start = Instant::now();
+                let response = self.fetch_async(&client, target, headers).await;
+                self.tally.request(start, response.is_ok());
+                self.adapt(
+                    matches!(&response, Ok(res) if !matches!(res.status().as_u16(), 429 | 503)),
+                    start.elapsed(),
+                );
+                match &response {
+                    Ok(res) if res.status().as_u16() == 429 => {
+                        if throttled < MAX_THROTTLED_ATTEMPTS && !self.control.is_stopped() {
+                            throttled += 1;
+                            self.too_many_requests(res.headers());
+                            if !pacer.acquire_async(host).await {
+                                break response;
+                            }
+                            continue;
+                        }
+                    }
+                    Ok(_) => self.scheduler.recovered(),
+                    Err(_) => {}
+                }
+                if response.is_ok() || attempt >= self.config.retries || self.control.is_stopped() {
+                    break response;
+                }
+                attempt += 1;
+            };
+            let target = candidate.target;
+
+            if let Ok(res) = &response
+                && task::block_in_place(|| {
+                    self.report_unchanged(&target, res.status().as_u16(), &mut result)
+                })
+            {
+                advance();
+                continue;
+            }
+
+            // Virtual hosts only get where they redirect to.
+            let limit = match candidate.host {
+                None => self.config.follow_redirects,
+                Some(_) => 0,
+            };
+            let (response, redirects) = match response {
+                Ok(res) => {
+                    let (res, redirects) = self
+                        .follow_async(&client, &mut pacer, &target, res, limit)
+                        .await;
+                    (Ok(res), redirects)
+                }
+                Err(err) => (Err(err), Vec::new()),
+            };
+
+            let failed_in_a_row = self.tally.candidate(response.is_ok());
+            match response {
+                Ok(res) => {
+                    let res = self.buffer(res, &soft404).await;
+                    task::block_in_place(|| {
+                        self.assess(
+                            &url,
+                            target,
+                            candidate.host.as_deref(),
+                            res,
+                            redirects,
+                            &soft404,
+                            &mut result,
+                        )
+                    });
+                }
+                Err(err) => self.request_failed(&target, failed_in_a_row, &err),
+            }
+
+            advance();
+        }
+
+        self.advance(progress.take());
+        result
+    }
+
+    /// [`Worker::wait_turn`] for tasks.
+    async fn wait_turn_async(
+        &self,
+        index: usize,
+        exhausted: &AtomicBool,
+        deadline: Option<Instant>,
+    ) -> bool {
+        let Some(concurrency) = &self.concurrency else {
+            return true;
+        };
+        while index >= concurrency.limit() {
+            if self.control.is_stopped()
+                || exhausted.load(Ordering::Relaxed)
+                || deadline.is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                return false;
+            }
+            tokio::time::sleep(TURN_POLL).await;
+        }
+        true
+    }
+
+    /// [`Worker::fetch`] with the async client, always with the browser and
+    /// extra headers.
+    async fn fetch_async<'a>(
+        &self,
+        client: &Client,
+        url: &str,
+        headers: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        let method = match self.config.method {
+            HttpMethod::Get => Method::GET,
+            HttpMethod::Head => Method::HEAD,
+            HttpMethod::Post => Method::POST,
+            HttpMethod::Put => Method::PUT,
+            HttpMethod::Options => Method::OPTIONS,
+        };
+        let mut request = client.request(method, url);
+        if matches!(self.config.method, HttpMethod::Post | HttpMethod::Put) {
+            request = request.body(Vec::new());
+        }
+
+        if let Some(profile) = self.config.browser_profile {
+            for (name, value) in profile.headers() {
+                request = request.header(*name, *value);
+            }
+        }
+        for (name, value) in &self.config.headers {
+            request = request.header(name, value);
+        }
+        for (name, value) in headers {
+            request = request.header(name, value);
+        }
+
+        request.send().await
+    }
+
+    /// [`Worker::follow`] with the async client.
+    async fn follow_async(
+        &self,
+        client: &Client,
+        pacer: &mut Pacer<'_>,
+        target: &str,
+        mut res: reqwest::Response,
+        limit: usize,
+    ) -> (reqwest::Response, Vec<String>) {
+        let mut redirects = Vec::new();
+        let Ok(mut current) = Url::parse(target) else {
+            return (res, redirects);
+        };
+
+        while res.status().is_redirection() {
+            let Some(next) = header_value(res.headers(), "location")
+                .and_then(|location| current.join(&location).ok())
+            else {
+                break;
+            };
+            redirects.push(next.to_string());
+            if redirects.len() > limit
+                || !pacer
+                    .acquire_async(next.host_str().unwrap_or_default())
+                    .await
+            {
+                break;
+            }
+
+            let start = Instant::now();
+            let response = self.fetch_async(client, next.as_str(), []).await;
+            self.tally.request(start, response.is_ok());
+            let Ok(next_res) = response else {
+                break;
+            };
+            res = next_res;
+            current = next;
+        }
+
+        (res, redirects)
+    }
+
+    /// Reads the body of `res` when judging it could need it. With
+    /// extensions set it is read for every answer, not only for hits, as
+    /// variants are told apart by their bodies.
+    async fn buffer(&self, mut res: reqwest::Response, soft404: &[Soft404]) -> Buffered {
+        let status = res.status().as_u16();
+        let headers = res.headers().clone();
+        let content_length = res.content_length();
+
+        let content_type = header_value(&headers, "content-type");
+        let needs_body = self.needs_body(status, content_type.as_deref(), soft404)
+            || !self.config.extensions.is_empty();
+        let allowance = if needs_body {
+            self.capture.reserve(status)
+        } else {
+            0
+        };
+
+        let body = if allowance == 0 {
+            None
+        } else {
+            let mut body = Vec::new();
+            while (body.len() as u64) < allowance {
+                let Ok(Some(chunk)) = res.chunk().await else {
+                    break;
+                };
+                let room = (allowance - body.len() as u64).min(chunk.len() as u64) as usize;
+                body.extend_from_slice(&chunk[..room]);
+            }
+            self.capture.release(allowance - body.len() as u64);
+            Some(charset::decode(content_type.as_deref(), &body))
+        };
+
+        Buffered {
+            status,
+            headers,
+            content_length,
+            body,
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+/// Remembers the responses of file extension variants of each word, so that
+/// `index.php` answering exactly like `index.html` is folded into one finding.
+#[derive(Debug, Default)]
+pub struct VariantIndex {
+    extensions: Vec<String>,
+    /// Stem, status and body hash → URL of the variant reported first.
+    seen: HashMap<(String, u16, u64), String>,
+}
+
+impl VariantIndex {
+    pub fn new(extensions: Vec<String>) -> Self {
+        Self {
+            extensions,
+            seen: HashMap::new(),
+        }
+    }
+
+    /// URL of an earlier variant of `target` that answered with the same
+    /// status and body; `target` is remembered as the first one otherwise.
+    pub fn original(&mut self, target: &str, status: u16, body: Option<&str>) -> Option<String> {
+        let stem = self.stem(target)?;
+        let body = body?;
+
+        let mut hasher = DefaultHasher::new();
+        body.hash(&mut hasher);
+        let key = (stem.to_string(), status, hasher.finish());
+
+        match self.seen.get(&key) {
+            Some(url) => Some(url.clone()),
+            None => {
+                self.seen.insert(key, target.to_string());
+                None
+            }
+        }
+    }
+
+    /// `target` without one of the fuzzed extensions; `None` for directories
+    /// and other paths.
+    fn stem<'a>(&self, target: &'a str) -> Option<&'a str> {
+        self.extensions.iter().find_map(|ext| {
+            target
+                .strip_suffix(ext.as_str())
+                .and_then(|rest| rest.strip_suffix('.'))
+        })
+    }
+}
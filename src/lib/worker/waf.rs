@@ -0,0 +1,86 @@
+use std::fmt;
+
+use serde::Serialize;
+use ureq::http::HeaderMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum WafVendor {
+    Cloudflare,
+    Akamai,
+    Imperva,
+    Sucuri,
+    AwsWaf,
+    JsChallenge,
+}
+
+impl fmt::Display for WafVendor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let str = match self {
+            WafVendor::Cloudflare => "Cloudflare",
+            WafVendor::Akamai => "Akamai",
+            WafVendor::Imperva => "Imperva/Incapsula",
+            WafVendor::Sucuri => "Sucuri",
+            WafVendor::AwsWaf => "AWS WAF",
+            WafVendor::JsChallenge => "generic JavaScript challenge",
+        };
+        f.write_str(str)
+    }
+}
+
+/// Statuses WAFs answer with when they block a request.
+pub fn is_block_status(status: u16) -> bool {
+    matches!(status, 403 | 406 | 429 | 503)
+}
+
+/// Recognizes a blocked response by its headers and the beginning of its body.
+pub fn detect(status: u16, headers: &HeaderMap, body: &str) -> Option<WafVendor> {
+    if !is_block_status(status) {
+        return None;
+    }
+
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_ascii_lowercase()
+    };
+    let body = body.to_ascii_lowercase();
+
+    if headers.contains_key("cf-ray")
+        || header("server") == "cloudflare"
+        || body.contains("error code: 1020")
+        || body.contains("cf-chl")
+    {
+        return Some(WafVendor::Cloudflare);
+    }
+
+    if header("server").contains("akamaighost")
+        || (body.contains("access denied") && body.contains("reference&#32;"))
+    {
+        return Some(WafVendor::Akamai);
+    }
+
+    if headers.contains_key("x-iinfo")
+        || header("set-cookie").contains("incap_ses")
+        || body.contains("incapsula incident id")
+    {
+        return Some(WafVendor::Imperva);
+    }
+
+    if headers.contains_key("x-sucuri-id") || header("server").contains("sucuri") {
+        return Some(WafVendor::Sucuri);
+    }
+
+    if headers.contains_key("x-amzn-waf-action") {
+        return Some(WafVendor::AwsWaf);
+    }
+
+    if (body.contains("enable javascript") || body.contains("captcha"))
+        && body.contains("<noscript")
+    {
+        return Some(WafVendor::JsChallenge);
+    }
+
+    None
+}